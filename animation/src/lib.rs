@@ -0,0 +1,258 @@
+//! Records the frames that simulations show an [`Observer`] and saves them as pictures.
+//!
+//! Each frame is text with one character per cell, as drawn by the days' `render` methods. Every
+//! character becomes a square of colour, so the frames can be saved as an animated GIF or as a
+//! numbered sequence of PNGs.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::{self, File},
+    io::BufWriter,
+    path::Path,
+    time::Duration,
+};
+
+use aoc_core::Observer;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, DynamicImage, Frame, ImageResult, Rgb, RgbImage,
+};
+
+/// The colour of blank cells, and of the padding around frames smaller than the largest.
+const BACKGROUND: Rgb<u8> = Rgb([24, 24, 32]);
+
+/// Colours handed out in turn to characters without one of their own.
+const PALETTE: [Rgb<u8>; 8] = [
+    Rgb([230, 180, 60]),
+    Rgb([90, 170, 230]),
+    Rgb([220, 90, 80]),
+    Rgb([120, 200, 110]),
+    Rgb([190, 120, 220]),
+    Rgb([240, 140, 40]),
+    Rgb([80, 200, 190]),
+    Rgb([240, 110, 170]),
+];
+
+/// How many frames a recorder keeps unless told otherwise.
+pub const DEFAULT_MAX_FRAMES: usize = 10_000;
+
+/// An [`Observer`] that keeps the frames it is shown, up to a limit.
+///
+/// Frames are kept as text until they're saved, as every picture is as large as the largest
+/// frame, which isn't known until the last. Frames after the first [`DEFAULT_MAX_FRAMES`], or
+/// however many [`Recorder::max_frames`] allows, are counted but not kept.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    frames: Vec<String>,
+    max_frames: usize,
+    skipped: usize,
+    cell_size: u32,
+    delay: Duration,
+    colours: BTreeMap<char, Rgb<u8>>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder {
+            frames: Vec::new(),
+            max_frames: DEFAULT_MAX_FRAMES,
+            skipped: 0,
+            cell_size: 4,
+            delay: Duration::from_millis(50),
+            colours: BTreeMap::from([
+                (' ', BACKGROUND),
+                ('.', BACKGROUND),
+                ('#', Rgb([200, 200, 200])),
+            ]),
+        }
+    }
+}
+
+impl Recorder {
+    /// A recorder drawing each cell as a 4 pixel square, with 50ms between frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps at most `frames` frames.
+    pub fn max_frames(mut self, frames: usize) -> Self {
+        self.max_frames = frames;
+        self
+    }
+
+    /// Draws each cell as a square `pixels` wide.
+    pub fn cell_size(mut self, pixels: u32) -> Self {
+        self.cell_size = pixels.max(1);
+        self
+    }
+
+    /// Shows each frame of an animated GIF for `delay`.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Draws cells showing `c` in `rgb`.
+    ///
+    /// Blank cells (` ` and `.`) and `#` have colours already. Any other character is given the
+    /// next colour of a built in palette.
+    pub fn colour(mut self, c: char, rgb: [u8; 3]) -> Self {
+        self.colours.insert(c, Rgb(rgb));
+        self
+    }
+
+    /// The frames recorded so far, as text.
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// How many frames were shown after the recorder was full, and so weren't kept.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Every frame as a picture.
+    ///
+    /// The pictures are all as large as the largest frame, with smaller frames centred on the
+    /// background.
+    pub fn images(&self) -> impl Iterator<Item = RgbImage> + '_ {
+        let size = |frame: &String| {
+            let width = frame.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+            (width as u32, frame.lines().count() as u32)
+        };
+        let (width, height) = self
+            .frames
+            .iter()
+            .map(size)
+            .fold((0, 0), |(w, h), (fw, fh)| (w.max(fw), h.max(fh)));
+        let mut colours = self.colours.clone();
+        self.frames.iter().map(move |frame| {
+            let (frame_width, frame_height) = size(frame);
+            let left = (width - frame_width) / 2;
+            let top = (height - frame_height) / 2;
+            let mut image = RgbImage::from_pixel(
+                width.max(1) * self.cell_size,
+                height.max(1) * self.cell_size,
+                BACKGROUND,
+            );
+            for (y, line) in frame.lines().enumerate() {
+                for (x, c) in line.chars().enumerate() {
+                    let next = PALETTE[(colours.len() - self.colours.len()) % PALETTE.len()];
+                    let colour = *colours.entry(c).or_insert(next);
+                    let x = (left + x as u32) * self.cell_size;
+                    let y = (top + y as u32) * self.cell_size;
+                    for dy in 0..self.cell_size {
+                        for dx in 0..self.cell_size {
+                            image.put_pixel(x + dx, y + dy, colour);
+                        }
+                    }
+                }
+            }
+            image
+        })
+    }
+
+    /// Writes the frames to `path` as an animated GIF that loops forever.
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let mut encoder = GifEncoder::new_with_speed(BufWriter::new(File::create(path)?), 10);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = Delay::from_saturating_duration(self.delay);
+        encoder.encode_frames(
+            self.images().map(|image| {
+                Frame::from_parts(DynamicImage::from(image).into_rgba8(), 0, 0, delay)
+            }),
+        )
+    }
+
+    /// Writes each frame to a PNG named `frame_00000.png`, `frame_00001.png` and so on inside
+    /// the directory `dir`, which is created if it is missing.
+    pub fn save_pngs(&self, dir: impl AsRef<Path>) -> ImageResult<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (i, image) in self.images().enumerate() {
+            image.save(dir.join(format!("frame_{i:05}.png")))?;
+        }
+        Ok(())
+    }
+
+    /// Writes an animated GIF if `path` ends in `.gif`, or else a directory of PNGs.
+    pub fn save(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let path = path.as_ref();
+        if path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("gif"))
+        {
+            self.save_gif(path)
+        } else {
+            self.save_pngs(path)
+        }
+    }
+}
+
+impl Observer for Recorder {
+    fn frame(&mut self, frame: &dyn Display) {
+        if self.frames.len() < self.max_frames {
+            self.frames.push(frame.to_string());
+        } else {
+            self.skipped += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{codecs::gif::GifDecoder, AnimationDecoder};
+
+    #[test]
+    fn draws_and_centres_frames() {
+        let mut recorder = Recorder::new().cell_size(2).colour('o', [255, 0, 0]);
+        recorder.frame(&"#o.\n...\n");
+        recorder.frame(&"x\n");
+        let images: Vec<_> = recorder.images().collect();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].dimensions(), (6, 4));
+        assert_eq!(images[1].dimensions(), (6, 4));
+        assert_eq!(*images[0].get_pixel(1, 1), Rgb([200, 200, 200]));
+        assert_eq!(*images[0].get_pixel(3, 0), Rgb([255, 0, 0]));
+        assert_eq!(*images[0].get_pixel(5, 3), BACKGROUND);
+        // the single cell sits in the middle column of the top row
+        assert_eq!(*images[1].get_pixel(1, 1), BACKGROUND);
+        assert_eq!(*images[1].get_pixel(2, 0), PALETTE[0]);
+    }
+
+    #[test]
+    fn keeps_only_the_first_frames() {
+        let mut recorder = Recorder::new().max_frames(2);
+        for frame in ["#.", ".#", "##"] {
+            recorder.frame(&frame);
+        }
+        assert_eq!(recorder.frames(), ["#.", ".#"]);
+        assert_eq!(recorder.skipped(), 1);
+    }
+
+    #[test]
+    fn saves_gifs_and_pngs() {
+        let dir = std::env::temp_dir().join(format!("animation-test-{}", std::process::id()));
+        let mut recorder = Recorder::new();
+        for frame in ["#.", ".#", "##"] {
+            recorder.frame(&frame);
+        }
+        fs::create_dir_all(&dir).unwrap();
+        recorder.save(dir.join("frames.gif")).unwrap();
+        let gif = GifDecoder::new(File::open(dir.join("frames.gif")).unwrap()).unwrap();
+        assert_eq!(gif.into_frames().count(), 3);
+        recorder.save(dir.join("pngs")).unwrap();
+        assert_eq!(fs::read_dir(dir.join("pngs")).unwrap().count(), 3);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
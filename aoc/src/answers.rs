@@ -0,0 +1,133 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// The known answers to puzzle inputs, as kept in an answers file.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    pub answers: Vec<Answer>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    /// The input the answer is for, relative to the answers file
+    pub input: String,
+    pub answer: Expected,
+    /// Where the input is, once the answers file has been loaded
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl Answer {
+    /// The input, unless it hasn't been saved or is empty, as is usual for challenge inputs.
+    pub fn read_input(&self) -> Option<String> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .filter(|input| !input.trim().is_empty())
+    }
+}
+
+/// An answer written as a number or as text.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl Expected {
+    /// Whether a solution's answer matches, ignoring line endings at the end of either.
+    pub fn matches(&self, answer: &str) -> bool {
+        match self {
+            Expected::Number(n) => answer == n.to_string(),
+            Expected::Text(text) => {
+                answer.trim_end_matches(['\n', '\r']) == text.trim_end_matches(['\n', '\r'])
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Number(n) => write!(f, "{n}"),
+            Expected::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl Answers {
+    /// Reads an answers file, resolving every input relative to the file's directory.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut answers: Answers = toml::from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        for answer in &mut answers.answers {
+            answer.path = base.join(&answer.input);
+        }
+        Ok(answers)
+    }
+
+    /// Where `day`'s challenge input is expected, going by the inputs its answers are for.
+    pub fn challenge_path(&self, day: u8) -> Option<&Path> {
+        self.answers
+            .iter()
+            .filter(|answer| answer.day == day)
+            .map(|answer| answer.path.as_path())
+            .find(|path| {
+                path.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("challenge"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_text() {
+        let answers: Answers = toml::from_str(
+            r#"
+            answers = [
+                { day = 1, part = 2, input = "day_01/example_1.txt", answer = 45000 },
+                { day = 5, part = 1, input = "day_05/example.txt", answer = "CMZ" },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(answers.answers.len(), 2);
+        assert_eq!(answers.answers[0].answer, Expected::Number(45000));
+        assert!(answers.answers[0].answer.matches("45000"));
+        assert!(answers.answers[1].answer.matches("CMZ\n"));
+        assert!(!answers.answers[1].answer.matches("MCD"));
+    }
+
+    #[test]
+    fn loads_the_repository_answers() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+        let answers = Answers::load(&path).unwrap();
+        for answer in &answers.answers {
+            assert!((1..=25).contains(&answer.day), "{answer:?}");
+            assert!((1..=2).contains(&answer.part), "{answer:?}");
+        }
+        let example = &answers.answers[0];
+        assert_eq!(
+            example.read_input().unwrap(),
+            include_str!("../../day_01/example_1.txt")
+        );
+        assert!(answers
+            .challenge_path(1)
+            .unwrap()
+            .ends_with("day_01/challenge_1.txt"));
+    }
+}
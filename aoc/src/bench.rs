@@ -0,0 +1,207 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{answers::Answers, format_duration, solve};
+
+/// How many times to solve each part when the number of runs isn't given.
+pub enum Runs {
+    /// Exactly this many times
+    Count(usize),
+    /// At least 3 and at most 1000 times, stopping once this long has been spent
+    Budget(Duration),
+}
+
+/// The timings of earlier benchmarks, as kept in the history file.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct History {
+    #[serde(default)]
+    pub runs: Vec<Record>,
+}
+
+/// The timings of solving one part of one input repeatedly.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    /// When the benchmark was run, in seconds since the Unix epoch
+    pub timestamp: u64,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl History {
+    /// Reads the history file, which is empty if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// The latest record for the same part of the same input as `record`.
+    fn previous(&self, record: &Record) -> Option<&Record> {
+        self.runs
+            .iter()
+            .rev()
+            .find(|r| (r.day, r.part, &r.input) == (record.day, record.part, &record.input))
+    }
+}
+
+/// Times `f` according to `runs`, returning every duration in ascending order.
+fn time_runs<T>(runs: &Runs, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    let mut durations = Vec::new();
+    let started = Instant::now();
+    loop {
+        let start = Instant::now();
+        std::hint::black_box(f());
+        durations.push(start.elapsed());
+        let done = match *runs {
+            Runs::Count(count) => durations.len() >= count,
+            Runs::Budget(budget) => {
+                durations.len() >= 1000 || (durations.len() >= 3 && started.elapsed() >= budget)
+            }
+        };
+        if done {
+            break;
+        }
+    }
+    durations.sort();
+    durations
+}
+
+/// Benchmarks every saved input with a known answer, optionally only for `day`, and prints the
+/// timings next to the change in median since the last benchmark in `history`.
+///
+/// Medians more than `threshold` percent slower than last time are flagged as regressions. The
+/// new timings are added to `history`. Returns whether nothing regressed.
+pub fn bench(
+    answers: &Answers,
+    day: Option<u8>,
+    runs: &Runs,
+    threshold: f64,
+    history: &mut History,
+) -> bool {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let input_width = answers
+        .answers
+        .iter()
+        .map(|a| a.input.len())
+        .chain(["Input".len()])
+        .max()
+        .unwrap_or(0);
+    println!(
+        "Day Part {:input_width$} {:>5} {:>9} {:>9} {:>9}  Change",
+        "Input", "Runs", "Min", "Median", "Max"
+    );
+    let mut regressions = 0;
+    for answer in answers
+        .answers
+        .iter()
+        .filter(|a| day.is_none_or(|d| a.day == d))
+    {
+        let Some(input) = answer.read_input() else {
+            continue;
+        };
+        if let Err(e) = solve(answer.day, answer.part, &input, &mut ()) {
+            println!(
+                "{:>3} {:>4} {:input_width$} {e}",
+                answer.day, answer.part, answer.input
+            );
+            continue;
+        }
+        let durations = time_runs(runs, || solve(answer.day, answer.part, &input, &mut ()));
+        let nanos = |d: Duration| d.as_nanos().try_into().unwrap_or(u64::MAX);
+        let record = Record {
+            day: answer.day,
+            part: answer.part,
+            input: answer.input.clone(),
+            timestamp,
+            runs: durations.len(),
+            min_ns: nanos(durations[0]),
+            median_ns: nanos(durations[durations.len() / 2]),
+            max_ns: nanos(durations[durations.len() - 1]),
+        };
+        let change = match history.previous(&record) {
+            None => String::from("new"),
+            Some(previous) => {
+                let percent =
+                    (record.median_ns as f64 / previous.median_ns.max(1) as f64 - 1.0) * 100.0;
+                if percent > threshold {
+                    regressions += 1;
+                    format!("{percent:+.0}% REGRESSED")
+                } else {
+                    format!("{percent:+.0}%")
+                }
+            }
+        };
+        println!(
+            "{:>3} {:>4} {:input_width$} {:>5} {:>9} {:>9} {:>9}  {change}",
+            record.day,
+            record.part,
+            record.input,
+            record.runs,
+            format_duration(durations[0]),
+            format_duration(durations[durations.len() / 2]),
+            format_duration(durations[durations.len() - 1]),
+        );
+        history.runs.push(record);
+    }
+    if regressions > 0 {
+        println!("{regressions} regressed by more than {threshold}%");
+    }
+    regressions == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(part: u8, median_ns: u64) -> Record {
+        Record {
+            day: 1,
+            part,
+            input: String::from("day_01/example_1.txt"),
+            timestamp: 0,
+            runs: 3,
+            min_ns: median_ns / 2,
+            median_ns,
+            max_ns: median_ns * 2,
+        }
+    }
+
+    #[test]
+    fn finds_the_latest_matching_record() {
+        let history = History {
+            runs: vec![record(1, 100), record(2, 200), record(1, 300)],
+        };
+        assert_eq!(history.previous(&record(1, 0)), Some(&record(1, 300)));
+        assert_eq!(history.previous(&record(2, 0)), Some(&record(2, 200)));
+        let text = toml::to_string(&history).unwrap();
+        assert_eq!(toml::from_str::<History>(&text).unwrap(), history);
+    }
+
+    #[test]
+    fn runs_within_the_limits() {
+        assert_eq!(time_runs(&Runs::Count(5), || ()).len(), 5);
+        let durations = time_runs(&Runs::Budget(Duration::ZERO), || ());
+        assert_eq!(durations.len(), 3);
+        assert!(durations.is_sorted());
+    }
+}
@@ -0,0 +1,149 @@
+//! Puzzle inputs and submitted answers kept on disk, apart for each session, so that the site is
+//! only asked for an input once and isn't sent an answer it has already judged.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The cache of one session, in a directory named after a hash of the session so that the
+/// session itself isn't written to disk.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// The answers a session has submitted, as kept in its `submissions.toml`.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+struct Submissions {
+    #[serde(default)]
+    submissions: Vec<Submission>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub correct: bool,
+}
+
+impl Cache {
+    /// Where caches are kept unless another directory is given.
+    pub fn default_root() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("aoc-2022")
+    }
+
+    pub fn new(root: &Path, session: &str) -> Self {
+        let hash = Sha256::digest(session.trim().as_bytes());
+        let key: String = hash[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+        Cache {
+            dir: root.join(key),
+        }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day_{day:02}.txt"))
+    }
+
+    /// The saved input to `day`'s puzzle, if there is one.
+    pub fn input(&self, day: u8) -> Option<String> {
+        fs::read_to_string(self.input_path(day)).ok()
+    }
+
+    pub fn save_input(&self, day: u8, input: &str) -> Result<(), String> {
+        let path = self.input_path(day);
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, input))
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    fn submissions_path(&self) -> PathBuf {
+        self.dir.join("submissions.toml")
+    }
+
+    fn submissions(&self) -> Result<Submissions, String> {
+        let path = self.submissions_path();
+        match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// What the site said of `answer` to `part` of `day` before, or if the part has been
+    /// answered correctly already, that answer.
+    pub fn previous(&self, day: u8, part: u8, answer: &str) -> Result<Option<Submission>, String> {
+        let submissions = self.submissions()?.submissions;
+        let same_part = || {
+            submissions
+                .iter()
+                .filter(|s| (s.day, s.part) == (day, part))
+        };
+        Ok(same_part()
+            .find(|s| s.correct)
+            .or_else(|| same_part().find(|s| s.answer == answer))
+            .cloned())
+    }
+
+    /// Notes what the site said of an answer.
+    pub fn record(&self, submission: Submission) -> Result<(), String> {
+        let mut submissions = self.submissions()?;
+        submissions.submissions.push(submission);
+        let path = self.submissions_path();
+        let text = toml::to_string(&submissions).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, text))
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_sessions_apart() {
+        let root = Path::new("cache");
+        let first = Cache::new(root, "53616c7465645f5f");
+        let second = Cache::new(root, "53616c7465645f5e");
+        assert_ne!(first.input_path(1), second.input_path(1));
+        assert_eq!(
+            first.input_path(1),
+            Cache::new(root, "53616c7465645f5f\n").input_path(1)
+        );
+        let path = first.input_path(7);
+        assert_eq!(path.file_name().unwrap(), "day_07.txt");
+        assert!(!path.to_string_lossy().contains("53616c"));
+    }
+
+    #[test]
+    fn remembers_submissions() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = Cache::new(root.path(), "session");
+        assert_eq!(cache.previous(1, 1, "5").unwrap(), None);
+        let wrong = Submission {
+            day: 1,
+            part: 1,
+            answer: String::from("5"),
+            correct: false,
+        };
+        cache.record(wrong.clone()).unwrap();
+        assert_eq!(cache.previous(1, 1, "5").unwrap(), Some(wrong));
+        assert_eq!(cache.previous(1, 1, "6").unwrap(), None);
+        assert_eq!(cache.previous(1, 2, "5").unwrap(), None);
+        let right = Submission {
+            day: 1,
+            part: 1,
+            answer: String::from("7"),
+            correct: true,
+        };
+        cache.record(right.clone()).unwrap();
+        assert_eq!(cache.previous(1, 1, "5").unwrap(), Some(right));
+    }
+}
@@ -0,0 +1,111 @@
+//! `aoc fetch` and `aoc submit`, which go through the cache to the site.
+
+use std::{fs, path::Path};
+
+use crate::{
+    cache::{Cache, Submission},
+    site::{Site, Verdict},
+    solve,
+};
+
+/// The input to `day`'s puzzle from the cache, or else from the site, in which case it's cached.
+pub fn input(site: &Site, cache: &Cache, day: u8, refresh: bool) -> Result<String, String> {
+    if !refresh {
+        if let Some(input) = cache.input(day) {
+            return Ok(input);
+        }
+    }
+    let input = site.input(day)?;
+    cache.save_input(day, &input)?;
+    Ok(input)
+}
+
+/// Saves the input to `day`'s puzzle to `output`.
+pub fn fetch(
+    site: &Site,
+    cache: &Cache,
+    day: u8,
+    refresh: bool,
+    output: &Path,
+) -> Result<(), String> {
+    let input = input(site, cache, day, refresh)?;
+    if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    fs::write(output, input).map_err(|e| format!("Failed to write {}: {e}", output.display()))?;
+    eprintln!("Saved day {day}'s input to {}", output.display());
+    Ok(())
+}
+
+/// Submits `answer` to part `part` of `day`, or the answer worked out from the cached input when
+/// it's `None`, and prints the verdict.
+///
+/// Answers the site has already judged are checked against the cache instead of being sent
+/// again. Returns whether the answer was right.
+pub fn submit(
+    site: &Site,
+    cache: &Cache,
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+) -> Result<bool, String> {
+    let answer = match answer {
+        Some(answer) => answer.trim().to_owned(),
+        None => {
+            let input = input(site, cache, day, false)?;
+            let answer = solve(day, part, &input, &mut ())?.to_string();
+            if answer.trim().contains('\n') {
+                return Err(format!(
+                    "Day {day} part {part}'s answer is a picture, so give the letters it shows"
+                ));
+            }
+            answer.trim().to_owned()
+        }
+    };
+
+    if let Some(previous) = cache.previous(day, part, &answer)? {
+        let right = previous.correct && previous.answer == answer;
+        if right {
+            println!("{answer} is the right answer, as the site said before");
+        } else if previous.correct {
+            println!(
+                "{answer} isn't the right answer, which the site said was {}",
+                previous.answer
+            );
+        } else {
+            println!("{answer} was already submitted, and the site said it was wrong");
+        }
+        return Ok(right);
+    }
+
+    eprintln!("Submitting {answer} to day {day} part {part}");
+    let verdict = site.submit(day, part, &answer)?;
+    let correct = match &verdict {
+        Verdict::Correct => Some(true),
+        Verdict::Incorrect(_) => Some(false),
+        _ => None,
+    };
+    if let Some(correct) = correct {
+        cache.record(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            correct,
+        })?;
+    }
+    match verdict {
+        Verdict::Correct => println!("That's the right answer!"),
+        Verdict::Incorrect(Some(hint)) => println!("That's not the right answer; {hint}"),
+        Verdict::Incorrect(None) => println!("That's not the right answer"),
+        Verdict::TooSoon(Some(wait)) => {
+            println!("An answer was given too recently; wait {wait} before trying again")
+        }
+        Verdict::TooSoon(None) => println!("An answer was given too recently"),
+        Verdict::WrongLevel => println!(
+            "Day {day} part {part} can't be answered, as it already has been or the part before \
+             hasn't been yet"
+        ),
+        Verdict::Unrecognised(text) => println!("The site replied: {text}"),
+    }
+    Ok(correct == Some(true))
+}
@@ -0,0 +1,115 @@
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::{Number, Value};
+use solutions::{Step, Structured};
+
+/// How solving one part went, written out as a line of JSON by `--format json`.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    /// The input file, or `None` for standard input
+    pub input: Option<&'a str>,
+    pub answer: Option<Value>,
+    pub elapsed_ns: Option<u64>,
+    pub error: Option<String>,
+    /// Whether the answer was the known one, when checking answers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<bool>,
+    /// The key steps taken to reach the answer, when asked to explain it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<Value>,
+}
+
+impl Record<'_> {
+    pub fn print(&self) {
+        println!(
+            "{}",
+            serde_json::to_string(self).expect("records are always valid JSON")
+        );
+    }
+}
+
+pub fn to_json(answer: &Structured) -> Value {
+    match answer {
+        Structured::Integer(n) => i64::try_from(*n)
+            .map(Number::from)
+            .or_else(|_| u64::try_from(*n).map(Number::from))
+            .map_or_else(|_| Value::String(n.to_string()), Value::Number),
+        Structured::Boolean(b) => Value::Bool(*b),
+        Structured::Text(text) => Value::String(text.clone()),
+        Structured::List(items) => Value::Array(items.iter().map(to_json).collect()),
+    }
+}
+
+/// Each step as an object with its `description` and a `values` object of the values it involved.
+pub fn steps_to_json(steps: &[Step]) -> Value {
+    Value::Array(
+        steps
+            .iter()
+            .map(|step| {
+                let values: Map<String, Value> = step
+                    .values
+                    .iter()
+                    .map(|(name, value)| ((*name).to_owned(), to_json(value)))
+                    .collect();
+                let mut object = Map::new();
+                object.insert(
+                    "description".to_owned(),
+                    Value::String(step.description.clone()),
+                );
+                object.insert("values".to_owned(), Value::Object(values));
+                Value::Object(object)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn writes_one_line_per_record() {
+        let record = Record {
+            day: 10,
+            part: 2,
+            input: Some("day_10/example.txt"),
+            answer: Some(to_json(&Structured::List(vec![Structured::List(vec![
+                Structured::Boolean(true),
+                Structured::Boolean(false),
+            ])]))),
+            elapsed_ns: Some(1500),
+            error: None,
+            correct: None,
+            explanation: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":10,"part":2,"input":"day_10/example.txt","answer":[[true,false]],"elapsed_ns":1500,"error":null}"#
+        );
+    }
+
+    #[test]
+    fn writes_steps_with_their_values() {
+        let steps = [Step::new("Round 1 scores 8").with("score", 8u32)];
+        assert_eq!(
+            steps_to_json(&steps),
+            json!([{"description": "Round 1 scores 8", "values": {"score": 8}}])
+        );
+    }
+
+    #[test]
+    fn keeps_large_integers_exact() {
+        assert_eq!(to_json(&Structured::Integer(-5)), json!(-5));
+        assert_eq!(
+            to_json(&Structured::Integer(u64::MAX as i128)),
+            json!(u64::MAX)
+        );
+        assert_eq!(
+            to_json(&Structured::Integer(i128::MAX)),
+            json!(i128::MAX.to_string())
+        );
+    }
+}
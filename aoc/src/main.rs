@@ -0,0 +1,482 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use animation::Recorder;
+use answers::Answers;
+use bench::{History, Runs};
+use cache::Cache;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use json::Record;
+use observe::{Frames, Observers, ProgressBar};
+use site::Site;
+use solutions::{Answer, AnySolution, Explanation, Observer, SolveError};
+
+mod answers;
+mod bench;
+mod cache;
+mod fetch;
+mod json;
+mod observe;
+mod site;
+mod tui;
+mod verify;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Plain text for reading
+    Text,
+    /// One JSON record per line, with the day, part, input, answer, time taken and any error
+    Json,
+}
+
+/// Where the puzzles are and who is solving them, for commands that talk to the site.
+#[derive(Debug, Args)]
+struct SiteArgs {
+    /// The value of the site's `session` cookie, which a browser keeps once logged in
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: String,
+    /// The site to talk to, such as a `puzzle_server` for trying things out
+    #[arg(long, env = "AOC_BASE_URL", default_value = site::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Directory inputs and submitted answers are cached in, apart for each session
+    #[arg(long, env = "AOC_CACHE", value_name = "DIR")]
+    cache: Option<PathBuf>,
+}
+
+impl SiteArgs {
+    fn open(&self) -> (Site, Cache) {
+        let root = self.cache.clone().unwrap_or_else(Cache::default_root);
+        (
+            Site::new(&self.base_url, &self.session),
+            Cache::new(&root, &self.session),
+        )
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve one part of a day's puzzle and print the answer
+    Run {
+        /// Day of the puzzle, 1 to 25
+        day: u8,
+        /// Part of the puzzle, 1 or 2
+        part: u8,
+        /// File containing the puzzle input. Standard input is read when omitted
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Show a progress bar on standard error for the slow solutions
+        #[arg(long)]
+        progress: bool,
+        /// Print each step of the simulating solutions to standard error
+        #[arg(long)]
+        frames: bool,
+        /// Save the first 10 000 steps of the simulating solutions as an animated GIF, if the
+        /// path ends in `.gif`, or else as a directory of PNGs
+        #[arg(long, value_name = "PATH")]
+        record: Option<PathBuf>,
+        /// Print the key steps taken to reach the answer, for the days that explain them
+        #[arg(long)]
+        explain: bool,
+        /// How to print the answer
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Change one of the puzzle's settings, such as `--param rounds=5` on day 20. Can be
+        /// given more than once
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+    },
+    /// Step through one of the simulating solutions in the terminal, a tick at a time
+    Step {
+        /// Day of the puzzle: 14, 17, 23 or 24
+        day: u8,
+        /// Part of the puzzle, 1 or 2
+        part: u8,
+        /// File containing the puzzle input. Standard input is read when omitted
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Change one of the puzzle's settings, as with `run`
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+    },
+    /// Solve every input with a known answer and print which were right and how long they took
+    Verify {
+        /// Only check this day's answers
+        day: Option<u8>,
+        /// File listing the known answers
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Download puzzle inputs, saving them where the known answers expect the challenge inputs
+    Fetch {
+        /// Only fetch this day's input
+        day: Option<u8>,
+        /// Where to save the input, instead of where the answers file expects it
+        #[arg(short, long, requires = "day")]
+        output: Option<PathBuf>,
+        /// Download the input again even if it's cached
+        #[arg(long)]
+        refresh: bool,
+        /// File listing the known answers, whose challenge inputs say where to save inputs
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Submit an answer to the site and print whether it was right
+    Submit {
+        /// Day of the puzzle, 1 to 25
+        day: u8,
+        /// Part of the puzzle, 1 or 2
+        part: u8,
+        /// The answer. When omitted, the part is solved with the cached input, which is
+        /// downloaded first if need be
+        answer: Option<String>,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Time every input with a known answer, comparing against the previous benchmark
+    Bench {
+        /// Only benchmark this day
+        day: Option<u8>,
+        /// File listing the inputs to benchmark, with their answers
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// How many times to solve each part. By default parts are solved for about two
+        /// seconds each, and at least three times
+        #[arg(short, long)]
+        runs: Option<usize>,
+        /// File the timings are recorded in and compared against
+        #[arg(long, default_value = "bench_history.toml")]
+        history: PathBuf,
+        /// How many percent slower than last time a median can be before it's a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+}
+
+fn parse_param(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(name, value)| (name.trim().to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("expected NAME=VALUE, found `{arg}`"))
+}
+
+fn solve(
+    day: u8,
+    part: u8,
+    input: &str,
+    observer: &mut dyn Observer,
+) -> Result<Box<dyn Answer>, String> {
+    let solution = solutions::get(day).ok_or_else(|| format!("There is no day {day}"))?;
+    solve_with(solution, day, part, input, observer)
+}
+
+/// Like [`solve`], with a solution that may have had its parameters changed.
+fn solve_with(
+    solution: &dyn AnySolution,
+    day: u8,
+    part: u8,
+    input: &str,
+    observer: &mut dyn Observer,
+) -> Result<Box<dyn Answer>, String> {
+    solution
+        .solve_answer(part, input, observer)
+        .map_err(|e| match e {
+            SolveError::NoSuchPart => format!("Day {day} has no part {part}"),
+            e => e.to_string(),
+        })
+}
+
+/// A duration rounded to a sensible unit for a table of timings.
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros == 0 {
+        format!("{}ns", duration.as_nanos())
+    } else if micros < 1_000 {
+        format!("{micros}µs")
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+fn read_input(path: Option<PathBuf>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            progress,
+            frames,
+            record,
+            explain,
+            format,
+            params,
+        } => {
+            let path = input;
+            let input = match read_input(path.clone()) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Failed to read input: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let unexplained = solutions::get(day).is_some_and(|solution| !solution.explains(part));
+            if explain && unexplained {
+                eprintln!("Day {day} part {part} has no explanation");
+                return ExitCode::FAILURE;
+            }
+            let mut recorder = record.as_ref().map(|_| Recorder::new());
+            let mut explanation = explain.then(Explanation::default);
+            let mut observers = Observers::default();
+            if progress {
+                observers.push(ProgressBar::new(40));
+            }
+            if frames {
+                observers.push(Frames::default());
+            }
+            if let Some(recorder) = &mut recorder {
+                observers.push(recorder);
+            }
+            if let Some(explanation) = &mut explanation {
+                observers.push(explanation);
+            }
+            let params: Vec<_> = params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            let start = Instant::now();
+            let answer = solutions::get_with(day, &params).and_then(|solution| {
+                solve_with(solution.as_ref(), day, part, &input, &mut observers)
+            });
+            let elapsed = start.elapsed();
+            drop(observers);
+            if let (Some(recorder), Some(path)) = (recorder, record) {
+                if recorder.is_empty() {
+                    eprintln!("Day {day} part {part} has no frames to record");
+                } else if let Err(e) = recorder.save(&path) {
+                    eprintln!("Failed to save {}: {e}", path.display());
+                    return ExitCode::FAILURE;
+                }
+                if recorder.skipped() > 0 {
+                    eprintln!(
+                        "Only the first {} of {} frames were recorded",
+                        recorder.len(),
+                        recorder.len() + recorder.skipped()
+                    );
+                }
+            }
+            if format == Format::Json {
+                let path = path.as_ref().map(|path| path.to_string_lossy());
+                Record {
+                    day,
+                    part,
+                    input: path.as_deref(),
+                    answer: answer
+                        .as_ref()
+                        .ok()
+                        .map(|answer| json::to_json(&answer.structured())),
+                    elapsed_ns: Some(elapsed.as_nanos() as u64),
+                    error: answer.as_ref().err().cloned(),
+                    correct: None,
+                    explanation: explanation.map(|explanation| json::steps_to_json(&explanation.0)),
+                }
+                .print();
+                return if answer.is_ok() {
+                    ExitCode::SUCCESS
+                } else {
+                    ExitCode::FAILURE
+                };
+            }
+            if let Some(explanation) = explanation.filter(|_| answer.is_ok()) {
+                for (number, step) in explanation.0.iter().enumerate() {
+                    println!("{}. {step}", number + 1);
+                }
+            }
+            match answer {
+                Ok(answer) => {
+                    println!("{answer}");
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Step {
+            day,
+            part,
+            input,
+            params,
+        } => {
+            let input = match read_input(input) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Failed to read input: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let params: Vec<_> = params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            let stepper = solutions::get_with(day, &params).and_then(|solution| {
+                solution
+                    .stepper(part, &input)
+                    .ok_or_else(|| format!("Day {day} part {part} can't be stepped through"))?
+                    .map_err(|e| e.to_string())
+            });
+            let stepper = match stepper {
+                Ok(stepper) => stepper,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(e) = tui::run(format!("Day {day} part {part}"), stepper) {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Verify {
+            day,
+            answers,
+            format,
+        } => {
+            let answers = match Answers::load(&answers) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if verify::verify(&answers, day, format) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Command::Fetch {
+            day,
+            output,
+            refresh,
+            answers,
+            site,
+        } => {
+            let (site, cache) = site.open();
+            let days = match day {
+                Some(day) => day..=day,
+                None => 1..=25,
+            };
+            let answers = match output {
+                Some(_) => None,
+                None => match Answers::load(&answers) {
+                    Ok(loaded) => Some((loaded, answers)),
+                    Err(e) => {
+                        eprintln!("{e}");
+                        return ExitCode::FAILURE;
+                    }
+                },
+            };
+            let mut fetched_all = true;
+            for day in days {
+                let output = match (&output, &answers) {
+                    (Some(output), _) => output.clone(),
+                    (None, Some((answers, path))) => match answers.challenge_path(day) {
+                        Some(challenge) => challenge.to_owned(),
+                        None => path
+                            .parent()
+                            .unwrap_or(Path::new(""))
+                            .join(format!("day_{day:02}/challenge.txt")),
+                    },
+                    (None, None) => unreachable!("the answers are loaded without an output"),
+                };
+                if let Err(e) = fetch::fetch(&site, &cache, day, refresh, &output) {
+                    eprintln!("{e}");
+                    fetched_all = false;
+                }
+            }
+            if fetched_all {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        } => {
+            let (site, cache) = site.open();
+            match fetch::submit(&site, &cache, day, part, answer) {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::FAILURE,
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Bench {
+            day,
+            answers,
+            runs,
+            history: history_path,
+            threshold,
+        } => {
+            let loaded = Answers::load(&answers)
+                .and_then(|answers| History::load(&history_path).map(|history| (answers, history)));
+            let (answers, mut history) = match loaded {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let runs = match runs {
+                Some(count) => Runs::Count(count.max(1)),
+                None => Runs::Budget(Duration::from_secs(2)),
+            };
+            let unchanged = bench::bench(&answers, day, &runs, threshold, &mut history);
+            if let Err(e) = history.save(&history_path) {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+            if unchanged {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    mem,
+};
+
+use solutions::{Observer, Step};
+
+/// Redraws a progress bar on standard error as the solution reports its progress.
+pub struct ProgressBar {
+    width: usize,
+    drawn: Option<usize>,
+}
+
+impl ProgressBar {
+    pub fn new(width: usize) -> Self {
+        ProgressBar { width, drawn: None }
+    }
+}
+
+impl Observer for ProgressBar {
+    fn progress(&mut self, done: u64, total: u64) {
+        let filled = if total == 0 {
+            self.width
+        } else {
+            (done.min(total) as u128 * self.width as u128 / total as u128) as usize
+        };
+        if self.drawn == Some(filled) && done < total {
+            return;
+        }
+        self.drawn = Some(filled);
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r[{}{}] {done}/{total}",
+            "#".repeat(filled),
+            " ".repeat(self.width - filled)
+        );
+        if done >= total {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
+
+/// Prints every frame of a simulation to standard error, separated by blank lines.
+#[derive(Default)]
+pub struct Frames {
+    shown: bool,
+}
+
+impl Observer for Frames {
+    fn frame(&mut self, frame: &dyn Display) {
+        // frames may or may not end in a line break of their own
+        let frame = frame.to_string();
+        let separator = if mem::replace(&mut self.shown, true) {
+            "\n"
+        } else {
+            ""
+        };
+        eprintln!("{separator}{}", frame.trim_end_matches(['\r', '\n']));
+    }
+}
+
+/// Passes everything on to each of a list of observers.
+#[derive(Default)]
+pub struct Observers<'a>(Vec<Box<dyn Observer + 'a>>);
+
+impl<'a> Observers<'a> {
+    pub fn push(&mut self, observer: impl Observer + 'a) {
+        self.0.push(Box::new(observer));
+    }
+}
+
+impl Observer for Observers<'_> {
+    fn progress(&mut self, done: u64, total: u64) {
+        for observer in &mut self.0 {
+            observer.progress(done, total);
+        }
+    }
+
+    fn frame(&mut self, frame: &dyn Display) {
+        for observer in &mut self.0 {
+            observer.frame(frame);
+        }
+    }
+
+    fn explain(&mut self, step: &dyn Fn() -> Step) {
+        for observer in &mut self.0 {
+            observer.explain(step);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Talks to the Advent of Code website, or to anything that serves the same paths.
+
+use std::time::Duration;
+
+/// The real website, used unless another base URL is given.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const YEAR: u16 = 2022;
+
+const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/ccouzens/advent-of-code)"
+);
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, possibly with a hint such as "your answer is too high"
+    Incorrect(Option<String>),
+    /// Another answer was given too recently, with how long is left to wait if the site said
+    TooSoon(Option<String>),
+    /// The part has already been answered, or part one hasn't been yet
+    WrongLevel,
+    /// A reply that isn't one of the above, as the text of its article
+    Unrecognised(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the article of the page the site replies with.
+    pub fn from_page(page: &str) -> Self {
+        let text = page
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(page, |(article, _)| article);
+        let text = strip_tags(text);
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = text
+                .split_once("; ")
+                .and_then(|(_, rest)| rest.split_once('.'))
+                .map(|(hint, _)| hint.to_owned())
+                .filter(|hint| hint.starts_with("your answer is"));
+            Verdict::Incorrect(hint)
+        } else if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_owned());
+            Verdict::TooSoon(wait)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unrecognised(text.trim().to_owned())
+        }
+    }
+}
+
+/// Drops the HTML tags from `html`, leaving the text between them.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// A logged in player's view of the site.
+pub struct Site {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Site {
+    /// `session` is the value of the site's `session` cookie.
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Site {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn day_url(&self, day: u8, page: &str) -> String {
+        format!("{}/{YEAR}/day/{day}/{page}", self.base_url)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the player's input to `day`'s puzzle.
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let url = self.day_url(day, "input");
+        self.agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| describe(e, &url))?
+            .into_string()
+            .map_err(|e| format!("Failed to read {url}: {e}"))
+    }
+
+    /// Submits `answer` to part `part` of `day`'s puzzle.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+        let url = self.day_url(day, "answer");
+        let page = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| describe(e, &url))?
+            .into_string()
+            .map_err(|e| format!("Failed to read {url}: {e}"))?;
+        Ok(Verdict::from_page(&page))
+    }
+}
+
+/// Describes a failed request, with what the site said if it replied.
+fn describe(error: ureq::Error, url: &str) -> String {
+    match error {
+        ureq::Error::Status(status, response) => {
+            let said = response.into_string().unwrap_or_default();
+            let said = said.trim();
+            if said.is_empty() {
+                format!("{url} replied with status {status}")
+            } else {
+                format!("{url} replied with status {status}: {said}")
+            }
+        }
+        ureq::Error::Transport(transport) => format!("Failed to reach {url}: {transport}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_verdicts() {
+        let page = |text| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's the right answer!  You are one gold star closer. \
+                 <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a>"
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's not the right answer; your answer is too low.  If you're stuck, \
+                 make sure you're using the full input data."
+            )),
+            Verdict::Incorrect(Some(String::from("your answer is too low")))
+        );
+        assert_eq!(
+            Verdict::from_page(&page("That's not the right answer.  If you're stuck, ...")),
+            Verdict::Incorrect(None)
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 41s left to wait. <a href=\"/2022/day/1\">\
+                 [Return to Day 1]</a>"
+            )),
+            Verdict::TooSoon(Some(String::from("41s")))
+        );
+        assert_eq!(
+            Verdict::from_page(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            Verdict::from_page("<p>Something <em>else</em></p>"),
+            Verdict::Unrecognised(String::from("Something else"))
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! A terminal UI for stepping through a simulation one tick at a time, for `aoc step`.
+
+use std::{io, time::Duration};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+use solutions::Stepper;
+
+/// How long each frame is shown for while playing.
+const FRAME: Duration = Duration::from_millis(50);
+
+const HELP: &str =
+    " space play/pause · n step · g go to tick · +/- speed · arrows move cursor · q quit ";
+
+struct App {
+    title: String,
+    stepper: Box<dyn Stepper>,
+    playing: bool,
+    /// How many ticks to take each frame while playing
+    speed: u64,
+    /// The column and row of the cell being inspected
+    cursor: (usize, usize),
+    /// The tick typed so far after pressing `g`
+    goto: Option<String>,
+    quit: bool,
+}
+
+impl App {
+    fn new(title: String, stepper: Box<dyn Stepper>) -> Self {
+        App {
+            title,
+            stepper,
+            playing: false,
+            speed: 1,
+            cursor: (0, 0),
+            goto: None,
+            quit: false,
+        }
+    }
+
+    fn key(&mut self, code: KeyCode) {
+        if let Some(goto) = &mut self.goto {
+            match code {
+                KeyCode::Char(c @ '0'..='9') => goto.push(c),
+                KeyCode::Backspace => {
+                    goto.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(tick) = goto.parse() {
+                        self.stepper.run_to(tick);
+                    }
+                    self.goto = None;
+                }
+                KeyCode::Esc => self.goto = None,
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char(' ') => self.playing = !self.playing && !self.stepper.is_finished(),
+            KeyCode::Char('n') | KeyCode::Char('.') => {
+                self.playing = false;
+                self.stepper.step();
+            }
+            KeyCode::Char('g') => {
+                self.playing = false;
+                self.goto = Some(String::new());
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = self.speed.saturating_mul(2);
+            }
+            KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Left | KeyCode::Char('h') => self.cursor.0 = self.cursor.0.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.cursor.0 += 1,
+            KeyCode::Up | KeyCode::Char('k') => self.cursor.1 = self.cursor.1.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor.1 += 1,
+            _ => {}
+        }
+    }
+
+    /// Moves the simulation on by a frame's worth of ticks, if it's playing.
+    fn advance(&mut self) {
+        if self.playing {
+            for _ in 0..self.speed {
+                if !self.stepper.step() {
+                    break;
+                }
+            }
+            self.playing = !self.stepper.is_finished();
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [drawing_area, status_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(4)]).areas(frame.area());
+        let drawing = self.stepper.render();
+        let rows: Vec<&str> = drawing.lines().collect();
+        // keep the cursor on the drawing, which can change size as the simulation goes on
+        let widest = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        self.cursor = (
+            self.cursor.0.min(widest.saturating_sub(1)),
+            self.cursor.1.min(rows.len().saturating_sub(1)),
+        );
+        let block = Block::bordered().title(format!(" {} ", self.title));
+        let inner = block.inner(drawing_area);
+        let scroll = (
+            scroll_to(self.cursor.1, inner.height),
+            scroll_to(self.cursor.0, inner.width),
+        );
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .map(|(y, row)| self.highlight(y, row))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).scroll(scroll).block(block),
+            drawing_area,
+        );
+        frame.render_widget(self.status(), status_area);
+    }
+
+    /// Draws `row` of the drawing, with the cell under the cursor highlighted.
+    fn highlight<'a>(&self, y: usize, row: &'a str) -> Line<'a> {
+        if y != self.cursor.1 {
+            return Line::raw(row);
+        }
+        let (x, _) = self.cursor;
+        let before: String = row.chars().take(x).collect();
+        let under = row.chars().nth(x).unwrap_or(' ');
+        let after: String = row.chars().skip(x + 1).collect();
+        Line::from(vec![
+            Span::raw(before),
+            Span::styled(
+                under.to_string(),
+                Style::default().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(after),
+        ])
+    }
+
+    fn status(&self) -> Paragraph<'_> {
+        let state = if self.stepper.is_finished() {
+            "finished".to_owned()
+        } else if self.playing {
+            format!("playing ×{}", self.speed)
+        } else {
+            "paused".to_owned()
+        };
+        let (x, y) = self.cursor;
+        let inspected = match &self.goto {
+            Some(goto) => format!("Go to tick: {goto}_"),
+            None => format!(
+                "({x}, {y}) {}",
+                self.stepper
+                    .inspect(x, y)
+                    .unwrap_or_else(|| "Outside the drawing".to_owned())
+            ),
+        };
+        Paragraph::new(vec![
+            Line::raw(format!(
+                "Tick {} ({state}) · {}",
+                self.stepper.tick(),
+                self.stepper.summary()
+            )),
+            Line::raw(inspected),
+        ])
+        .block(Block::bordered().title_bottom(HELP))
+    }
+}
+
+/// How far to scroll so that `position` is within a view `size` long.
+fn scroll_to(position: usize, size: u16) -> u16 {
+    (position + 1).saturating_sub(size.into()) as u16
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        let wait = if app.playing {
+            FRAME
+        } else {
+            Duration::from_secs(60)
+        };
+        if event::poll(wait)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.key(key.code);
+                }
+            }
+        }
+        app.advance();
+    }
+    Ok(())
+}
+
+/// Takes over the terminal to step through `stepper` until the user quits.
+pub fn run(title: String, stepper: Box<dyn Stepper>) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, &mut App::new(title, stepper));
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    fn example_app(day: u8, example: &str) -> App {
+        let stepper = solutions::get(day).unwrap().stepper(1, example).unwrap();
+        App::new(format!("Day {day} part 1"), stepper.unwrap())
+    }
+
+    #[test]
+    fn steps_plays_and_goes_to_a_tick() {
+        let mut app = example_app(23, include_str!("../../day_23/example.txt"));
+        app.key(KeyCode::Char('n'));
+        assert_eq!(app.stepper.tick(), 1);
+        app.key(KeyCode::Char(' '));
+        app.key(KeyCode::Char('+'));
+        app.advance();
+        assert_eq!(app.stepper.tick(), 3);
+        app.key(KeyCode::Char('g'));
+        for c in "42".chars() {
+            app.key(KeyCode::Char(c));
+        }
+        assert!(!app.playing);
+        app.key(KeyCode::Enter);
+        // part one stops after ten rounds
+        assert_eq!(app.stepper.tick(), 10);
+        app.key(KeyCode::Char(' '));
+        assert!(!app.playing);
+        app.key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn draws_the_state_with_the_cursor_inspected() {
+        let mut app = example_app(14, include_str!("../../day_14/example.txt"));
+        for _ in 0..7 {
+            app.key(KeyCode::Right);
+        }
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y| (0..60).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        // inside the border, the source is the eighth cell of the first row
+        assert_eq!(buffer[(8, 1)].symbol(), "p");
+        assert!(buffer[(8, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(row(17).contains("Tick 0 (paused) · 0 units of sand at rest"));
+        assert!(row(18).contains("(7, 0) The source of the sand at 500,0"));
+    }
+}
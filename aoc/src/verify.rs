@@ -0,0 +1,118 @@
+use std::{collections::HashMap, path::Path, time::Instant};
+
+use solutions::Structured;
+
+use crate::{
+    answers::Answers,
+    format_duration,
+    json::{self, Record},
+    solve, Format,
+};
+
+/// How checking one answer turned out.
+enum Outcome {
+    Pass(Structured),
+    Fail(String, Structured),
+    Error(String),
+    /// The input isn't saved locally, or is empty, as is usual for challenge inputs
+    Missing,
+}
+
+/// Solves every registered input, optionally only for `day`, and prints a table of the results
+/// or a JSON record for each.
+///
+/// Returns whether every answer that could be checked was right.
+pub fn verify(answers: &Answers, day: Option<u8>, format: Format) -> bool {
+    let mut inputs: HashMap<&Path, Option<String>> = HashMap::new();
+    let mut rows = Vec::new();
+    for answer in answers
+        .answers
+        .iter()
+        .filter(|a| day.is_none_or(|d| a.day == d))
+    {
+        let input = inputs
+            .entry(&answer.path)
+            .or_insert_with(|| answer.read_input());
+        let (outcome, elapsed) = match input {
+            None => (Outcome::Missing, None),
+            Some(input) => {
+                let start = Instant::now();
+                let result = solve(answer.day, answer.part, input, &mut ());
+                let elapsed = start.elapsed();
+                let outcome = match result {
+                    Ok(got) if answer.answer.matches(&got.to_string()) => {
+                        Outcome::Pass(got.structured())
+                    }
+                    Ok(got) => Outcome::Fail(got.to_string(), got.structured()),
+                    Err(e) => Outcome::Error(e),
+                };
+                (outcome, Some(elapsed))
+            }
+        };
+        rows.push((answer, outcome, elapsed));
+    }
+
+    if format == Format::Json {
+        for (answer, outcome, elapsed) in &rows {
+            let (got, error, correct) = match outcome {
+                Outcome::Pass(got) => (Some(got), None, Some(true)),
+                Outcome::Fail(_, got) => (Some(got), None, Some(false)),
+                Outcome::Error(e) => (None, Some(e.clone()), Some(false)),
+                Outcome::Missing => (None, Some(String::from("The input is missing")), None),
+            };
+            Record {
+                day: answer.day,
+                part: answer.part,
+                input: Some(&answer.input),
+                answer: got.map(json::to_json),
+                elapsed_ns: elapsed.map(|elapsed| elapsed.as_nanos() as u64),
+                error,
+                correct,
+                explanation: None,
+            }
+            .print();
+        }
+        return rows
+            .iter()
+            .all(|(_, outcome, _)| !matches!(outcome, Outcome::Fail(..) | Outcome::Error(_)));
+    }
+
+    let input_width = rows
+        .iter()
+        .map(|(a, _, _)| a.input.len())
+        .chain(["Input".len()])
+        .max()
+        .unwrap_or(0);
+    println!("Day Part {:input_width$} Result  Time", "Input");
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = 0;
+    for (answer, outcome, elapsed) in &rows {
+        let result = match outcome {
+            Outcome::Pass(_) => "pass",
+            Outcome::Fail(..) => "FAIL",
+            Outcome::Error(_) => "ERROR",
+            Outcome::Missing => "missing",
+        };
+        let time = elapsed.map(format_duration).unwrap_or_default();
+        let row = format!(
+            "{:>3} {:>4} {:input_width$} {result:7} {time}",
+            answer.day, answer.part, answer.input
+        );
+        println!("{}", row.trim_end());
+        match outcome {
+            Outcome::Pass(_) => passed += 1,
+            Outcome::Fail(got, _) => {
+                failed += 1;
+                println!("         expected {}, got {got}", answer.answer);
+            }
+            Outcome::Error(e) => {
+                failed += 1;
+                println!("         {e}");
+            }
+            Outcome::Missing => missing += 1,
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    failed == 0
+}
@@ -0,0 +1,144 @@
+//! Runs `aoc fetch` and `aoc submit` against a stand-in for the website.
+
+use std::{fs, path::Path, process::Command, time::Duration};
+
+use puzzle_server::{Puzzle, PuzzleServer};
+
+const EXAMPLE: &str = include_str!("../../day_01/example_1.txt");
+
+fn server() -> PuzzleServer {
+    let server = PuzzleServer::start("127.0.0.1:0").unwrap();
+    server.add_puzzle(
+        Some("alice"),
+        1,
+        Puzzle {
+            input: String::from(EXAMPLE),
+            answers: [Some(String::from("24000")), Some(String::from("45000"))],
+        },
+    );
+    server
+}
+
+/// Runs `aoc` as `session`, returning whether it succeeded and what it printed.
+fn aoc(server: &PuzzleServer, cache: &Path, session: &str, args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .env("AOC_SESSION", session)
+        .env("AOC_BASE_URL", server.url())
+        .env("AOC_CACHE", cache)
+        .output()
+        .unwrap();
+    let printed = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    (output.status.success(), printed.into_owned())
+}
+
+#[test]
+fn fetches_each_input_once_per_session() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("cache");
+    let output = dir.path().join("day_01/challenge_1.txt");
+    let output = output.to_str().unwrap();
+
+    let (fetched, printed) = aoc(&server, &cache, "alice", &["fetch", "1", "-o", output]);
+    assert!(fetched, "{printed}");
+    assert_eq!(fs::read_to_string(output).unwrap(), EXAMPLE);
+    assert_eq!(server.requests(), ["GET /2022/day/1/input"]);
+
+    fs::remove_file(output).unwrap();
+    let (fetched, printed) = aoc(&server, &cache, "alice", &["fetch", "1", "-o", output]);
+    assert!(fetched, "{printed}");
+    assert_eq!(fs::read_to_string(output).unwrap(), EXAMPLE);
+    assert_eq!(
+        server.requests().len(),
+        1,
+        "the input should come from the cache"
+    );
+
+    let (fetched, _) = aoc(
+        &server,
+        &cache,
+        "alice",
+        &["fetch", "1", "-o", output, "--refresh"],
+    );
+    assert!(fetched);
+    assert_eq!(server.requests().len(), 2);
+
+    // bob has no puzzles, and alice's aren't served to other sessions
+    let (fetched, printed) = aoc(&server, &cache, "bob", &["fetch", "1", "-o", output]);
+    assert!(!fetched);
+    assert!(printed.contains("replied with status 404"), "{printed}");
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn fetches_to_where_the_answers_expect() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.toml");
+    fs::write(
+        &answers,
+        r#"answers = [{ day = 1, part = 1, input = "day_01/challenge_1.txt", answer = 24000 }]"#,
+    )
+    .unwrap();
+    let (fetched, printed) = aoc(
+        &server,
+        &dir.path().join("cache"),
+        "alice",
+        &["fetch", "1", "--answers", answers.to_str().unwrap()],
+    );
+    assert!(fetched, "{printed}");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("day_01/challenge_1.txt")).unwrap(),
+        EXAMPLE
+    );
+}
+
+#[test]
+fn submits_answers_the_site_hasnt_judged() {
+    let server = server();
+    server.set_cooldown(Duration::ZERO);
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("cache");
+
+    // with no answer given, part one is solved from the input, which is fetched for it
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "1"]);
+    assert!(right, "{printed}");
+    assert!(printed.contains("That's the right answer!"), "{printed}");
+    assert_eq!(
+        server.requests(),
+        ["GET /2022/day/1/input", "POST /2022/day/1/answer"]
+    );
+
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "1"]);
+    assert!(right, "{printed}");
+    assert!(printed.contains("as the site said before"), "{printed}");
+    assert_eq!(server.requests().len(), 2);
+
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "2", "1000"]);
+    assert!(!right);
+    assert!(printed.contains("your answer is too low"), "{printed}");
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "2", "1000"]);
+    assert!(!right);
+    assert!(printed.contains("already submitted"), "{printed}");
+    assert_eq!(server.requests().len(), 3);
+
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "2"]);
+    assert!(right, "{printed}");
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[test]
+fn makes_wrong_answers_wait() {
+    let server = server();
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("cache");
+    let (right, _) = aoc(&server, &cache, "alice", &["submit", "1", "1", "30000"]);
+    assert!(!right);
+    let (right, printed) = aoc(&server, &cache, "alice", &["submit", "1", "1", "24000"]);
+    assert!(!right);
+    assert!(printed.contains("too recently; wait"), "{printed}");
+    // the site didn't judge the answer, so it can be sent again later
+    let (_, printed) = aoc(&server, &cache, "alice", &["submit", "1", "1", "24000"]);
+    assert!(printed.contains("too recently"), "{printed}");
+}
@@ -0,0 +1,119 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::Infallible, fmt::Display};
+
+/// An answer as data rather than text, so that it can be written out as JSON or similar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Structured {
+    Integer(i128),
+    Boolean(bool),
+    Text(String),
+    List(Vec<Structured>),
+}
+
+/// A puzzle's answer, which is shown as text but can also be given as [`Structured`] data.
+pub trait Answer: Display {
+    /// The answer as data, which by default is just its text.
+    fn structured(&self) -> Structured {
+        Structured::Text(self.to_string())
+    }
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn structured(&self) -> Structured {
+                    Structured::Integer(i128::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl Answer for usize {
+    fn structured(&self) -> Structured {
+        Structured::Integer(*self as i128)
+    }
+}
+
+impl Answer for bool {
+    fn structured(&self) -> Structured {
+        Structured::Boolean(*self)
+    }
+}
+
+impl Answer for String {}
+
+impl Answer for &str {}
+
+impl Answer for char {}
+
+impl Answer for Infallible {
+    fn structured(&self) -> Structured {
+        match *self {}
+    }
+}
+
+/// A picture drawn in lit and unlit pixels, shown as rows of `#` and `.`, and structured as a
+/// list of rows of booleans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pixels(pub Vec<Vec<bool>>);
+
+impl Display for Pixels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for row in &self.0 {
+            for &lit in row {
+                write!(f, "{}", if lit { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Answer for Pixels {
+    fn structured(&self) -> Structured {
+        Structured::List(
+            self.0
+                .iter()
+                .map(|row| {
+                    Structured::List(row.iter().map(|&lit| Structured::Boolean(lit)).collect())
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn numbers_are_integers() {
+        assert_eq!(42u64.structured(), Structured::Integer(42));
+        assert_eq!((-3i64).structured(), Structured::Integer(-3));
+        assert_eq!(
+            String::from("CMZ").structured(),
+            Structured::Text(String::from("CMZ"))
+        );
+    }
+
+    #[test]
+    fn pixels_are_rows() {
+        let pixels = Pixels(vec![vec![true, false], vec![false, true]]);
+        assert_eq!(pixels.to_string(), "#.\n.#\n");
+        assert_eq!(
+            pixels.structured(),
+            Structured::List(vec![
+                Structured::List(vec![Structured::Boolean(true), Structured::Boolean(false)]),
+                Structured::List(vec![Structured::Boolean(false), Structured::Boolean(true)]),
+            ])
+        );
+    }
+}
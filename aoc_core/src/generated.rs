@@ -0,0 +1,86 @@
+use alloc::string::String;
+use core::fmt::{self, Debug, Display};
+
+/// A random puzzle input, with its answers worked out by a slow but simple reference solution.
+///
+/// An answer is `None` where no reference solution is feasible, so only the input can be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated<A, B> {
+    pub input: String,
+    pub part_one: Option<A>,
+    pub part_two: Option<B>,
+}
+
+/// An input on which a solution gave a different answer to the one expected of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<T> {
+    pub input: String,
+    pub expected: T,
+    pub actual: T,
+}
+
+impl<T: Debug> Display for Disagreement<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "expected {:?} but got {:?} from this input:",
+            self.expected, self.actual
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Runs `solution` on every input, returning the shortest one on which its answer isn't the
+/// expected one, as that is the easiest to debug.
+pub fn smallest_disagreement<T: PartialEq>(
+    cases: impl IntoIterator<Item = (String, T)>,
+    solution: impl Fn(&str) -> T,
+) -> Option<Disagreement<T>> {
+    cases
+        .into_iter()
+        .filter_map(|(input, expected)| {
+            let actual = solution(&input);
+            (actual != expected).then_some(Disagreement {
+                input,
+                expected,
+                actual,
+            })
+        })
+        .min_by_key(|disagreement| disagreement.input.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn finds_the_shortest_disagreement() {
+        let cases = vec![
+            (String::from("1 2 3 4"), 4),
+            (String::from("1 2 3"), 4),
+            (String::from("1 2"), 2),
+            (String::from("1"), 2),
+            (String::from("1 2 3 4 5"), 4),
+        ];
+        let disagreement = smallest_disagreement(cases, |input| input.split(' ').count());
+        assert_eq!(
+            disagreement,
+            Some(Disagreement {
+                input: String::from("1"),
+                expected: 2,
+                actual: 1,
+            })
+        );
+        assert_eq!(
+            format!("{}", disagreement.unwrap()),
+            "expected 2 but got 1 from this input:\n1"
+        );
+    }
+
+    #[test]
+    fn agreeing_is_not_a_disagreement() {
+        let cases = vec![(String::from("a"), 1), (String::from("bb"), 2)];
+        assert_eq!(smallest_disagreement(cases, str::len), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+use crate::parse::{final_parse, IResult, ParseError};
+
+/// Reasons input couldn't be read from a reader.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    /// A line couldn't be parsed. The error's line is counted from the start of the reader.
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "Failed to read input: {e}"),
+            ReadError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// The values read from a reader, ending early at the first error.
+///
+/// The error is kept back rather than yielded, so that solutions can be written against plain
+/// iterators. [`with_lines`] and [`with_bytes`] hand it back once the solution is done.
+pub struct Reading<I> {
+    inner: I,
+    count: usize,
+    error: Option<ReadError>,
+}
+
+impl<I: Iterator<Item = io::Result<T>>, T> Iterator for Reading<I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.inner.next()? {
+            Ok(value) => {
+                self.count += 1;
+                Some(value)
+            }
+            Err(e) => {
+                self.error = Some(e.into());
+                None
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Reading<io::Lines<R>> {
+    /// Parses each remaining line with `parser`, ending early at the first line that doesn't
+    /// parse.
+    ///
+    /// As with [`lines`](crate::parse::lines), blank lines are only allowed at the end.
+    pub fn parsed<'r, O: 'r>(
+        &'r mut self,
+        mut parser: impl FnMut(&str) -> IResult<&str, O> + 'r,
+    ) -> impl Iterator<Item = O> + 'r {
+        let mut first_blank_line = None;
+        core::iter::from_fn(move || loop {
+            let line = self.next()?;
+            if line.trim().is_empty() {
+                first_blank_line.get_or_insert((self.count, line));
+                continue;
+            }
+            let (number, line) = first_blank_line.take().unwrap_or((self.count, line));
+            match final_parse(&line, &mut parser) {
+                Ok(output) => return Some(output),
+                Err(e) => {
+                    self.error = Some(ReadError::Parse(ParseError { line: number, ..e }));
+                    return None;
+                }
+            }
+        })
+    }
+}
+
+fn with_reading<I, T>(inner: I, f: impl FnOnce(&mut Reading<I>) -> T) -> Result<T, ReadError> {
+    let mut reading = Reading {
+        inner,
+        count: 0,
+        error: None,
+    };
+    let output = f(&mut reading);
+    match reading.error {
+        Some(e) => Err(e),
+        None => Ok(output),
+    }
+}
+
+/// Passes the lines of `reader`, without their line endings, to `f`.
+///
+/// Only the current line is held in memory. If reading or parsing fails, the lines stop and
+/// the error is returned in place of whatever `f` made of them.
+pub fn with_lines<R: BufRead, T>(
+    reader: R,
+    f: impl FnOnce(&mut Reading<io::Lines<R>>) -> T,
+) -> Result<T, ReadError> {
+    with_reading(reader.lines(), f)
+}
+
+/// Passes the bytes of `reader` to `f`, returning the first read error if there was one.
+pub fn with_bytes<R: BufRead, T>(
+    reader: R,
+    f: impl FnOnce(&mut Reading<io::Bytes<R>>) -> T,
+) -> Result<T, ReadError> {
+    with_reading(reader.bytes(), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Expected;
+    use alloc::vec::Vec;
+    use nom::{character::complete::digit1, combinator::map_res};
+
+    struct Broken;
+
+    impl io::Read for Broken {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    fn number(input: &str) -> IResult<&str, u32> {
+        map_res(digit1, str::parse)(input)
+    }
+
+    #[test]
+    fn parses_lines_incrementally() {
+        let sum = with_lines("1\r\n2\n3\n\n".as_bytes(), |lines| {
+            lines.parsed(number).sum::<u32>()
+        });
+        assert_eq!(sum.unwrap(), 6);
+    }
+
+    #[test]
+    fn counts_lines_in_parse_errors() {
+        let numbers = with_lines("1\n2\nthree\n4\n".as_bytes(), |lines| {
+            lines.parsed(number).collect::<Vec<_>>()
+        });
+        match numbers {
+            Err(ReadError::Parse(e)) => assert_eq!(
+                e,
+                ParseError {
+                    line: 3,
+                    column: 1,
+                    expected: Expected::Described("a digit"),
+                    found: Some('t'),
+                    within: None,
+                }
+            ),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+        let numbers = with_lines("1\n\n2\n".as_bytes(), |lines| lines.parsed(number).count());
+        assert!(matches!(
+            numbers,
+            Err(ReadError::Parse(ParseError { line: 2, .. }))
+        ));
+    }
+
+    #[test]
+    fn returns_read_errors() {
+        let count = with_bytes(io::BufReader::new(Broken), |bytes| bytes.count());
+        assert!(matches!(count, Err(ReadError::Io(_))));
+    }
+}
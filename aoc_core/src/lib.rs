@@ -0,0 +1,176 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+pub use answer::{Answer, Pixels, Structured};
+pub use generated::{smallest_disagreement, Disagreement, Generated};
+pub use observe::{Explanation, Observer, Step};
+pub use params::{parse_param, ParamError, Parameters};
+pub use parse::ParseError;
+pub use step::Stepper;
+
+mod answer;
+mod generated;
+#[cfg(feature = "std")]
+pub mod io;
+mod observe;
+mod params;
+pub mod parse;
+mod step;
+
+/// Reasons a puzzle couldn't be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The day doesn't have the requested part.
+    NoSuchPart,
+    /// The input made sense, but there was no answer to be found in it.
+    NoAnswer(&'static str),
+    /// The input didn't describe a valid puzzle.
+    Invalid(&'static str),
+    /// The input couldn't be parsed.
+    Parse(ParseError),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NoSuchPart => write!(f, "The puzzle doesn't have that part"),
+            SolveError::NoAnswer(reason) | SolveError::Invalid(reason) => write!(f, "{reason}"),
+            SolveError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(e)
+    }
+}
+
+impl From<&'static str> for SolveError {
+    fn from(reason: &'static str) -> Self {
+        SolveError::Invalid(reason)
+    }
+}
+
+/// Both parts of a day's puzzle.
+///
+/// Any extra arguments a day needs, such as the row to inspect, are carried by the implementing
+/// value so that every day can be solved from the input alone.
+pub trait Solution {
+    type PartOne: Answer + 'static;
+    type PartTwo: Answer + 'static;
+
+    fn part_one(&self, input: &str) -> Result<Self::PartOne, SolveError>;
+
+    fn part_two(&self, input: &str) -> Result<Self::PartTwo, SolveError>;
+
+    /// Like [`Solution::part_one`], reporting to `observer` as it goes.
+    ///
+    /// Days without anything to report don't need to implement this.
+    fn part_one_observed(
+        &self,
+        input: &str,
+        _observer: &mut dyn Observer,
+    ) -> Result<Self::PartOne, SolveError> {
+        self.part_one(input)
+    }
+
+    /// Like [`Solution::part_two`], reporting to `observer` as it goes.
+    fn part_two_observed(
+        &self,
+        input: &str,
+        _observer: &mut dyn Observer,
+    ) -> Result<Self::PartTwo, SolveError> {
+        self.part_two(input)
+    }
+
+    /// A [`Stepper`] through the simulation behind part `1` or `2`, or `None` if the part isn't
+    /// a simulation that can be stepped through.
+    fn stepper(&self, _part: u8, _input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        None
+    }
+
+    /// Whether part `1` or `2` explains the key steps to its answer to an observer.
+    fn explains(&self, _part: u8) -> bool {
+        false
+    }
+}
+
+/// A [`Solution`] with its answer types erased, so that different days can be kept together.
+pub trait AnySolution: Send + Sync {
+    /// Solves part `1` or `2`, rendering the answer with its `Display` implementation.
+    fn solve(&self, part: u8, input: &str) -> Result<String, SolveError> {
+        self.solve_observed(part, input, &mut ())
+    }
+
+    /// Like [`AnySolution::solve`], reporting to `observer` as it goes.
+    fn solve_observed(
+        &self,
+        part: u8,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<String, SolveError> {
+        self.solve_answer(part, input, observer)
+            .map(|a| a.to_string())
+    }
+
+    /// Like [`AnySolution::solve_observed`], keeping the answer so that it can also be
+    /// [structured](Answer::structured).
+    fn solve_answer(
+        &self,
+        part: u8,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<Box<dyn Answer>, SolveError>;
+
+    /// Like [`AnySolution::solve_answer`], along with the key steps taken to reach the answer.
+    /// Days that don't explain their answers give no steps.
+    fn explain(&self, part: u8, input: &str) -> Result<(Box<dyn Answer>, Vec<Step>), SolveError> {
+        let mut explanation = Explanation::default();
+        let answer = self.solve_answer(part, input, &mut explanation)?;
+        Ok((answer, explanation.0))
+    }
+
+    /// Like [`Solution::stepper`].
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>>;
+
+    /// Like [`Solution::explains`].
+    fn explains(&self, part: u8) -> bool;
+}
+
+impl<S: Solution + Send + Sync> AnySolution for S {
+    fn solve_answer(
+        &self,
+        part: u8,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<Box<dyn Answer>, SolveError> {
+        match part {
+            1 => self
+                .part_one_observed(input, observer)
+                .map(|a| Box::new(a) as Box<dyn Answer>),
+            2 => self
+                .part_two_observed(input, observer)
+                .map(|a| Box::new(a) as Box<dyn Answer>),
+            _ => Err(SolveError::NoSuchPart),
+        }
+    }
+
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        Solution::stepper(self, part, input)
+    }
+
+    fn explains(&self, part: u8) -> bool {
+        Solution::explains(self, part)
+    }
+}
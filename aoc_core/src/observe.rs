@@ -0,0 +1,108 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+use crate::{Answer, Structured};
+
+/// Watches a long-running solution while it works.
+///
+/// Solutions call these hooks instead of printing, so that a caller can choose to show a
+/// progress bar, capture the frames of a simulation, or ignore it all. The hooks do nothing
+/// by default, and `()` is an observer that ignores everything.
+pub trait Observer {
+    /// `done` out of `total` units of work have been finished.
+    fn progress(&mut self, _done: u64, _total: u64) {}
+
+    /// The simulation has reached a new state, drawn as text with one character per cell.
+    fn frame(&mut self, _frame: &dyn Display) {}
+
+    /// The solution has taken a key step towards the answer, which `step` describes.
+    ///
+    /// Describing every step can take longer than solving, so `step` is only called by observers
+    /// that keep an [`Explanation`].
+    fn explain(&mut self, _step: &dyn Fn() -> Step) {}
+}
+
+impl Observer for () {}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn progress(&mut self, done: u64, total: u64) {
+        (**self).progress(done, total)
+    }
+
+    fn frame(&mut self, frame: &dyn Display) {
+        (**self).frame(frame)
+    }
+
+    fn explain(&mut self, step: &dyn Fn() -> Step) {
+        (**self).explain(step)
+    }
+}
+
+/// A key step on the way to an answer, such as the score of one round of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// What happened, as a sentence
+    pub description: String,
+    /// The values the step involved, by name
+    pub values: Vec<(&'static str, Structured)>,
+}
+
+impl Step {
+    pub fn new(description: impl Into<String>) -> Self {
+        Step {
+            description: description.into(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds a value the step involved.
+    pub fn with(self, name: &'static str, value: impl Answer) -> Self {
+        self.with_structured(name, value.structured())
+    }
+
+    /// Adds a value the step involved, such as a list, that is already structured.
+    pub fn with_structured(mut self, name: &'static str, value: Structured) -> Self {
+        self.values.push((name, value));
+        self
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+/// An [`Observer`] that keeps every step it's shown, to explain how an answer was reached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explanation(pub Vec<Step>);
+
+impl Observer for Explanation {
+    fn explain(&mut self, step: &dyn Fn() -> Step) {
+        self.0.push(step());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn only_explanations_describe_steps() {
+        fn solve(observer: &mut dyn Observer) {
+            observer.explain(&|| Step::new("Added 2 and 3").with("sum", 5u8));
+        }
+
+        solve(&mut ());
+        let mut explanation = Explanation::default();
+        solve(&mut &mut explanation);
+        assert_eq!(
+            explanation.0,
+            vec![Step {
+                description: String::from("Added 2 and 3"),
+                values: vec![("sum", Structured::Integer(5))],
+            }]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// A day's tunable settings, which can be changed one at a time by name, as on the command line.
+///
+/// Implementors are the day's `Params` struct, whose `Default` is the puzzle as set.
+pub trait Parameters {
+    /// Changes the setting called `name` to `value`, parsed from text.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+}
+
+/// For the days that have nothing to tune.
+impl Parameters for () {
+    fn set(&mut self, _name: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown(&[]))
+    }
+}
+
+/// Reasons a setting couldn't be changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// There is no setting by that name. The names there are are given.
+    Unknown(&'static [&'static str]),
+    /// The value couldn't be parsed. What was expected is given.
+    Invalid(&'static str),
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown([]) => write!(f, "There are no parameters to set"),
+            ParamError::Unknown(names) => {
+                write!(f, "Unknown parameter, expected one of ")?;
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}")?;
+                }
+                Ok(())
+            }
+            ParamError::Invalid(expected) => write!(f, "Expected {expected}"),
+        }
+    }
+}
+
+/// Parses a number or other simple value for [`Parameters::set`].
+pub fn parse_param<T: FromStr>(value: &str, expected: &'static str) -> Result<T, ParamError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ParamError::Invalid(expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parses_values() {
+        assert_eq!(parse_param::<u32>(" 26 ", "a number"), Ok(26));
+        assert_eq!(
+            parse_param::<u32>("-1", "a number"),
+            Err(ParamError::Invalid("a number"))
+        );
+    }
+
+    #[test]
+    fn lists_the_known_names() {
+        assert_eq!(
+            ParamError::Unknown(&["rounds", "divisor"]).to_string(),
+            "Unknown parameter, expected one of rounds, divisor"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+use nom::{
+    character::complete::line_ending,
+    error::{ContextError, ErrorKind, FromExternalError},
+    InputLength, Offset,
+};
+
+/// What a parser was looking for when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Char(char),
+    Tag(&'static str),
+    /// A description of the expected input, from a nom error kind or a `context` label.
+    Described(&'static str),
+    EndOfInput,
+}
+
+impl From<ErrorKind> for Expected {
+    fn from(kind: ErrorKind) -> Self {
+        Expected::Described(match kind {
+            ErrorKind::Eof => return Expected::EndOfInput,
+            ErrorKind::Digit => "a digit",
+            ErrorKind::CrLf => "a line ending",
+            ErrorKind::MultiSpace => "whitespace",
+            ErrorKind::Space => "a space",
+            ErrorKind::Alpha => "a letter",
+            ErrorKind::TakeWhile1 | ErrorKind::TakeWhileMN => "a matching character",
+            ErrorKind::MapRes => "a value in range",
+            ErrorKind::Many1 | ErrorKind::SeparatedList | ErrorKind::Count => "at least one item",
+            _ => "valid input",
+        })
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Char(c) => write!(f, "'{}'", c.escape_debug()),
+            Expected::Tag(t) => write!(f, "\"{}\"", t.escape_debug()),
+            Expected::Described(d) => write!(f, "{d}"),
+            Expected::EndOfInput => write!(f, "end of input"),
+        }
+    }
+}
+
+/// The nom error type used by the day crates' parsers.
+///
+/// Unlike `nom::error::Error` it remembers the character, tag or label that was expected, and
+/// when alternatives fail it keeps whichever got furthest into the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<I> {
+    pub input: I,
+    pub expected: Expected,
+}
+
+impl<I: InputLength> nom::error::ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Error {
+            input,
+            expected: kind.into(),
+        }
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Error {
+            input,
+            expected: Expected::Char(c),
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        if other.input.input_len() < self.input.input_len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<I: InputLength> ContextError<I> for Error<I> {
+    fn add_context(input: I, ctx: &'static str, other: Self) -> Self {
+        if other.input.input_len() == input.input_len() {
+            Error {
+                input,
+                expected: Expected::Described(ctx),
+            }
+        } else {
+            other
+        }
+    }
+}
+
+impl<I: InputLength, E> FromExternalError<I, E> for Error<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _e: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+pub type IResult<I, O> = nom::IResult<I, O, Error<I>>;
+
+/// Where in the input parsing failed, and why.
+///
+/// Lines and columns are counted from 1, with columns counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: Expected,
+    pub found: Option<char>,
+    /// The numbered group of lines the line belongs to, such as `("Elf", 3)`, for inputs made
+    /// of groups whose numbers mean more to the reader than line numbers do
+    pub within: Option<(&'static str, usize)>,
+}
+
+impl ParseError {
+    /// Describes a failure at `position`, which must be a suffix of `input`.
+    pub fn new(input: &str, position: &str, expected: Expected) -> Self {
+        let consumed = &input[..input.offset(position)];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            expected,
+            found: position.chars().next(),
+            within: None,
+        }
+    }
+
+    /// The same error, placed in group `number` of the groups called `name`.
+    pub fn within(self, name: &'static str, number: usize) -> Self {
+        ParseError {
+            within: Some((name, number)),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.within {
+            Some((name, number)) => write!(f, "{name} {number}, line")?,
+            None => write!(f, "Line")?,
+        }
+        write!(
+            f,
+            " {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found {
+            Some(c) => write!(f, "found '{}'", c.escape_debug()),
+            None => write!(f, "found end of input"),
+        }
+    }
+}
+
+/// Runs `parser` over the whole of `input`, which may only have whitespace left over.
+pub fn final_parse<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    match parser(input) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(ParseError::new(input, rest, Expected::EndOfInput)),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            Err(ParseError::new(input, e.input, e.expected))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            Expected::Described("more input"),
+        )),
+    }
+}
+
+/// Matches `t` exactly, remembering it as the expected token on failure.
+pub fn tag<'a>(t: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |input: &'a str| match input.strip_prefix(t) {
+        Some(rest) => Ok((rest, &input[..t.len()])),
+        None => Err(nom::Err::Error(Error {
+            input,
+            expected: Expected::Tag(t),
+        })),
+    }
+}
+
+/// Repeats `item` between `separator`s until only whitespace is left.
+///
+/// Unlike `separated_list0` a failing item is an error rather than the end of the list, so
+/// that mistakes part way through the input are reported instead of silently ignored.
+pub fn list<'a, O, S>(
+    mut item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+    mut separator: impl FnMut(&'a str) -> IResult<&'a str, S>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    move |mut input: &'a str| {
+        let mut items = Vec::new();
+        while !input.trim().is_empty() {
+            let (rest, output) = item(input)?;
+            items.push(output);
+            input = match separator(rest) {
+                Ok((rest, _)) => rest,
+                Err(nom::Err::Error(_)) if rest.trim().is_empty() => rest,
+                Err(e) => return Err(e),
+            };
+        }
+        Ok((input, items))
+    }
+}
+
+/// Repeats `item` once per line until only whitespace is left.
+pub fn lines<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    list(item, line_ending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+    use nom::{
+        character::complete::{char, digit1},
+        combinator::map_res,
+        sequence::separated_pair,
+    };
+
+    fn pair(input: &str) -> IResult<&str, (u8, u8)> {
+        separated_pair(
+            map_res(digit1, str::parse),
+            char(','),
+            map_res(digit1, str::parse),
+        )(input)
+    }
+
+    #[test]
+    fn parses_every_line() {
+        assert_eq!(
+            final_parse("1,2\n3,4\n", lines(pair)),
+            Ok(vec![(1, 2), (3, 4)])
+        );
+    }
+
+    #[test]
+    fn reports_position_of_bad_line() {
+        assert_eq!(
+            final_parse("1,2\n3;4\n5,6\n", lines(pair)),
+            Err(ParseError {
+                line: 2,
+                column: 2,
+                expected: Expected::Char(','),
+                found: Some(';'),
+                within: None,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_tags_and_leftovers() {
+        assert_eq!(
+            final_parse("move 1", tag("mvoe")).unwrap_err().expected,
+            Expected::Tag("mvoe")
+        );
+        assert_eq!(
+            final_parse("1,2 3", pair),
+            Err(ParseError {
+                line: 1,
+                column: 4,
+                expected: Expected::EndOfInput,
+                found: Some(' '),
+                within: None,
+            })
+        );
+    }
+
+    #[test]
+    fn names_the_group_of_the_line() {
+        let e = final_parse("1,2\n3;4\n", lines(pair)).unwrap_err();
+        assert_eq!(e.to_string(), "Line 2, column 2: expected ',', found ';'");
+        assert_eq!(
+            e.within("Pair", 2).to_string(),
+            "Pair 2, line 2, column 2: expected ',', found ';'"
+        );
+    }
+}
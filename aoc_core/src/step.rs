@@ -0,0 +1,33 @@
+use alloc::string::String;
+
+/// A simulation that can be advanced one tick at a time, so that it can be stepped through by
+/// hand.
+///
+/// What a tick is depends on the day: a unit of sand moving once, a rock being pushed and then
+/// falling, a round of the elves' moves, or a minute of the expedition's journey.
+pub trait Stepper {
+    /// Advances by one tick, or returns `false` without changing anything if the simulation has
+    /// already finished.
+    fn step(&mut self) -> bool;
+
+    /// How many ticks have been taken.
+    fn tick(&self) -> u64;
+
+    /// Whether the simulation has reached its answer, after which [`Stepper::step`] does nothing.
+    fn is_finished(&self) -> bool;
+
+    /// The current state, drawn as text with one character per cell.
+    fn render(&self) -> String;
+
+    /// What is in the cell drawn at `column` of `row` by [`Stepper::render`], if it is in the
+    /// drawing.
+    fn inspect(&self, column: usize, row: usize) -> Option<String>;
+
+    /// A line about how the simulation is going, which includes the answer once it is finished.
+    fn summary(&self) -> String;
+
+    /// Steps until tick `tick` or the end of the simulation, whichever is first.
+    fn run_to(&mut self, tick: u64) {
+        while self.tick() < tick && self.step() {}
+    }
+}
@@ -0,0 +1,68 @@
+//! Random inventories with known answers, for testing.
+
+use alloc::{format, string::String, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated inventory is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub elves: usize,
+    /// The most food items a single elf carries. Every elf carries at least one
+    pub max_items: usize,
+    pub max_calories: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            elves: 20,
+            max_items: 8,
+            max_calories: 10000,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<u64, u64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let elves: Vec<Vec<u64>> = (0..size.elves)
+        .map(|_| {
+            (0..rng.gen_range(1..=size.max_items.max(1)))
+                .map(|_| rng.gen_range(1..=size.max_calories.max(1)))
+                .collect()
+        })
+        .collect();
+    let input = elves
+        .iter()
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| format!("{item}\n"))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut totals: Vec<u64> = elves.iter().map(|items| items.iter().sum()).collect();
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    Generated {
+        input,
+        part_one: Some(totals.first().copied().unwrap_or(0)),
+        part_two: Some(totals.iter().take(3).sum()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,43 +1,567 @@
-use std::collections::BinaryHeap;
+#![no_std]
 
-pub fn part_one(input: &str) -> u64 {
-    input
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|item| item.parse().unwrap_or(0)).sum())
-        .max()
-        .unwrap_or(0)
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{collections::BinaryHeap, format, string::String, vec::Vec};
+use core::{
+    cell::OnceCell,
+    cmp::Reverse,
+    fmt::{self, Display},
+    mem,
+};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use aoc_core::io::{with_lines, ReadError};
+use aoc_core::{
+    parse::{Expected, ParseError},
+    Observer, Solution, SolveError, Step,
+};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// Why an inventory couldn't be read. Elves and lines are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    /// An item's line wasn't a number of calories, or was too big a number
+    Item {
+        elf: usize,
+        line: usize,
+        item: String,
+    },
+    /// More than one blank line separated two elves, or blank lines came before the first elf
+    NoItems { elf: usize, line: usize },
+}
+
+impl Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryError::Item { elf, line, item } => write!(
+                f,
+                "Elf {elf}, line {line}: \"{}\" isn't a number of calories",
+                item.escape_debug()
+            ),
+            InventoryError::NoItems { elf, line } => {
+                write!(f, "Elf {elf}, line {line}: the elf has no items")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InventoryError {}
+
+impl From<InventoryError> for ParseError {
+    fn from(e: InventoryError) -> Self {
+        match e {
+            InventoryError::Item { elf, line, item } => {
+                let bad = item.char_indices().find(|(_, c)| !c.is_ascii_digit());
+                match bad {
+                    Some((at, found)) => ParseError {
+                        line,
+                        column: item[..at].chars().count() + 1,
+                        expected: Expected::Described("a number of calories"),
+                        found: Some(found),
+                        within: Some(("Elf", elf)),
+                    },
+                    None => ParseError {
+                        line,
+                        column: 1,
+                        expected: Expected::Described("a smaller number of calories"),
+                        found: item.chars().next(),
+                        within: Some(("Elf", elf)),
+                    },
+                }
+            }
+            InventoryError::NoItems { elf, line } => ParseError {
+                line,
+                column: 1,
+                expected: Expected::Described("a number of calories"),
+                found: Some('\n'),
+                within: Some(("Elf", elf)),
+            },
+        }
+    }
+}
+
+impl From<InventoryError> for SolveError {
+    fn from(e: InventoryError) -> Self {
+        SolveError::Parse(e.into())
+    }
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut totals: BinaryHeap<_> = input
-        .split("\n\n")
-        .map(|elf| elf.lines().map(|item| item.parse().unwrap_or(0)).sum())
-        .collect();
+/// Hands each elf's items to `elf`, where elves are separated by a blank line.
+///
+/// Lines may end in `\r\n`, and any number of blank lines may come after the last elf.
+fn read_elves<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = S>,
+    mut elf: impl FnMut(Vec<u64>),
+) -> Result<(), InventoryError> {
+    let mut items = Vec::new();
+    let mut number = 1;
+    // the first blank line that didn't end an elf, which is only allowed at the end
+    let mut extra_blank_line = None;
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            if items.is_empty() {
+                extra_blank_line.get_or_insert(index + 1);
+            } else {
+                elf(mem::take(&mut items));
+                number += 1;
+            }
+            continue;
+        }
+        if let Some(line) = extra_blank_line {
+            return Err(InventoryError::NoItems { elf: number, line });
+        }
+        let calories = Some(line)
+            .filter(|line| line.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|line| line.parse().ok())
+            .ok_or_else(|| InventoryError::Item {
+                elf: number,
+                line: index + 1,
+                item: String::from(line),
+            })?;
+        items.push(calories);
+    }
+    if !items.is_empty() {
+        elf(items);
+    }
+    Ok(())
+}
+
+/// The calories of the food items each elf is carrying.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inventory {
+    elves: Vec<Vec<u64>>,
+}
+
+impl Inventory {
+    pub fn new(input: &str) -> Result<Self, InventoryError> {
+        let mut elves = Vec::new();
+        read_elves(input.lines(), |items| elves.push(items))?;
+        Ok(Inventory { elves })
+    }
 
-    (0..3).map(|_| totals.pop().unwrap_or(0)).sum()
+    /// Each elf's items, in the order the elves are listed.
+    pub fn elves(&self) -> &[Vec<u64>] {
+        &self.elves
+    }
+
+    /// Each elf's total calories, in the order the elves are listed.
+    pub fn totals(&self) -> impl Iterator<Item = u64> + '_ {
+        self.elves.iter().map(|items| items.iter().sum())
+    }
+
+    /// The `k` elves carrying the most calories, most first.
+    pub fn top_elves(&self, k: usize) -> Vec<RankedElf> {
+        top_totals(self.totals(), k)
+            .into_iter()
+            .map(|(index, total)| RankedElf {
+                elf: index + 1,
+                total,
+                items: self.elves[index].clone(),
+            })
+            .collect()
+    }
+
+    /// Statistics of the elves' totals, or `None` if there are no elves.
+    pub fn summary(&self) -> Option<Summary> {
+        let mut tally = Tally::default();
+        let mut totals = Vec::with_capacity(self.elves.len());
+        for total in self.totals() {
+            tally.add(total);
+            totals.push(total);
+        }
+        tally.summary(median(&mut totals)?)
+    }
+}
+
+/// An elf among those carrying the most calories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedElf {
+    /// Where the elf is in the inventory, counted from 1 as in [`InventoryError`]
+    pub elf: usize,
+    pub total: u64,
+    pub items: Vec<u64>,
+}
+
+/// Statistics of the total calories carried by each elf of an inventory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// How many elves there are
+    pub count: usize,
+    pub mean: f64,
+    /// The middle total, or the mean of the middle two if there are an even number of elves
+    pub median: f64,
+    pub min: u64,
+}
+
+/// The `k` largest totals seen so far, each with the index of its elf.
+///
+/// Only `k` totals are kept at a time, so ranking n totals takes O(n log k).
+struct TopTotals {
+    k: usize,
+    seen: usize,
+    // the smallest total, and of those the latest listed, is at the top of the heap
+    kept: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl TopTotals {
+    fn new(k: usize) -> Self {
+        TopTotals {
+            k,
+            seen: 0,
+            kept: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    /// Takes the total of the next elf.
+    fn add(&mut self, total: u64) {
+        self.kept.push(Reverse((total, Reverse(self.seen))));
+        self.seen += 1;
+        if self.kept.len() > self.k {
+            self.kept.pop();
+        }
+    }
+
+    /// The kept totals with the indexes of their elves, largest first. Equal totals are in the
+    /// order they're listed.
+    fn ranked(self) -> Vec<(usize, u64)> {
+        self.kept
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect()
+    }
+
+    fn sum(&self) -> u64 {
+        self.kept.iter().map(|Reverse((total, _))| total).sum()
+    }
+}
+
+/// The `k` largest totals, each with the index of its elf, largest first.
+fn top_totals(totals: impl IntoIterator<Item = u64>, k: usize) -> Vec<(usize, u64)> {
+    let mut top = TopTotals::new(k);
+    totals.into_iter().for_each(|total| top.add(total));
+    top.ranked()
+}
+
+/// The statistics of a [`Summary`] that can be kept up a total at a time.
+#[derive(Debug, Default)]
+struct Tally {
+    count: usize,
+    sum: f64,
+    min: Option<u64>,
+}
+
+impl Tally {
+    fn add(&mut self, total: u64) {
+        self.count += 1;
+        self.sum += total as f64;
+        self.min = Some(self.min.map_or(total, |min| min.min(total)));
+    }
+
+    /// The summary of the totals added, which had `median` as their median.
+    fn summary(&self, median: f64) -> Option<Summary> {
+        Some(Summary {
+            count: self.count,
+            mean: self.sum / self.count as f64,
+            median,
+            min: self.min?,
+        })
+    }
+}
+
+/// The middle total, or the mean of the middle two, or `None` if there are no totals.
+///
+/// Unlike the rest of a [`Summary`] this needs every total at once, though not in order.
+fn median(totals: &mut [u64]) -> Option<f64> {
+    let count = totals.len();
+    let middle = count.checked_sub(1)? / 2;
+    let (_, &mut below, above) = totals.select_nth_unstable(middle);
+    if count % 2 == 1 {
+        Some(below as f64)
+    } else {
+        let above = above.iter().min().copied().unwrap_or(below);
+        Some((below as f64 + above as f64) / 2.0)
+    }
+}
+
+/// The `k` elves carrying the most calories, most first.
+pub fn top_elves(input: &str, k: usize) -> Result<Vec<RankedElf>, InventoryError> {
+    Ok(Inventory::new(input)?.top_elves(k))
+}
+
+/// Statistics of the elves' totals, or `None` if there are no elves.
+pub fn summary(input: &str) -> Result<Option<Summary>, InventoryError> {
+    Ok(Inventory::new(input)?.summary())
+}
+
+/// The sum of the `k` largest totals.
+fn top_calories(totals: impl IntoIterator<Item = u64>, k: usize) -> u64 {
+    let mut top = TopTotals::new(k);
+    totals.into_iter().for_each(|total| top.add(total));
+    top.sum()
+}
+
+/// Shows `observer` the `count` elves carrying the most calories, most first.
+fn explain_top_elves(inventory: &Inventory, count: usize, observer: &mut dyn Observer) {
+    let ranked = OnceCell::new();
+    for place in 0..count {
+        observer.explain(&|| {
+            let ranked = ranked.get_or_init(|| top_totals(inventory.totals(), count));
+            let Some(&(elf, total)) = ranked.get(place) else {
+                return Step::new(format!("There is no elf in place {}", place + 1))
+                    .with("place", place + 1);
+            };
+            Step::new(format!(
+                "Elf {} is in place {} with {total} calories",
+                elf + 1,
+                place + 1
+            ))
+            .with("place", place + 1)
+            .with("elf", elf + 1)
+            .with("calories", total)
+        });
+    }
+}
+
+pub fn part_one(input: &str) -> Result<u64, InventoryError> {
+    part_one_observed(input, &mut ())
+}
+
+/// Like [`part_one`], explaining to `observer` which elf carries the most.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<u64, InventoryError> {
+    let inventory = Inventory::new(input)?;
+    explain_top_elves(&inventory, 1, observer);
+    Ok(top_calories(inventory.totals(), 1))
+}
+
+pub fn part_two(input: &str) -> Result<u64, InventoryError> {
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], explaining to `observer` which three elves carry the most.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<u64, InventoryError> {
+    let inventory = Inventory::new(input)?;
+    explain_top_elves(&inventory, 3, observer);
+    Ok(top_calories(inventory.totals(), 3))
+}
+
+/// The sum of the `k` largest totals of the elves in `reader`, which is read an elf at a time.
+#[cfg(feature = "std")]
+fn top_calories_from_reader(reader: impl BufRead, k: usize) -> Result<u64, ReadError> {
+    let mut top = TopTotals::new(k);
+    with_lines(reader, |lines| {
+        read_elves(lines, |items| top.add(items.iter().sum()))
+    })?
+    .map_err(|e| ReadError::Parse(e.into()))?;
+    Ok(top.sum())
+}
+
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    top_calories_from_reader(reader, 1)
+}
+
+#[cfg(feature = "std")]
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    top_calories_from_reader(reader, 3)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn part_one(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_two(input)?)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_one_observed(input, observer)?)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_two_observed(input, observer)?)
+    }
+
+    fn explains(&self, _part: u8) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::{string::ToString, vec};
+    use aoc_core::Explanation;
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example_1.txt")), 24000);
+        assert_eq!(part_one(include_str!("../example_1.txt")).unwrap(), 24000);
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge_1.txt")), 72602);
+        assert_eq!(part_one(include_str!("../challenge_1.txt")).unwrap(), 72602);
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example_1.txt")), 45000);
+        assert_eq!(part_two(include_str!("../example_1.txt")).unwrap(), 45000);
+    }
+
+    #[test]
+    fn explains_the_top_three() {
+        let mut explanation = Explanation::default();
+        part_two_observed(include_str!("../example_1.txt"), &mut explanation).unwrap();
+        let steps: Vec<_> = explanation.0.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            steps,
+            [
+                "Elf 4 is in place 1 with 24000 calories",
+                "Elf 3 is in place 2 with 11000 calories",
+                "Elf 5 is in place 3 with 10000 calories",
+            ]
+        );
+    }
+
+    #[test]
+    fn ranks_the_top_elves() {
+        let example = include_str!("../example_1.txt");
+        assert_eq!(
+            top_elves(example, 3).unwrap(),
+            [
+                RankedElf {
+                    elf: 4,
+                    total: 24000,
+                    items: vec![7000, 8000, 9000],
+                },
+                RankedElf {
+                    elf: 3,
+                    total: 11000,
+                    items: vec![5000, 6000],
+                },
+                RankedElf {
+                    elf: 5,
+                    total: 10000,
+                    items: vec![10000],
+                },
+            ]
+        );
+        assert_eq!(top_elves(example, 0).unwrap(), []);
+        assert_eq!(top_elves(example, 9).unwrap().len(), 5);
+        let tied: Vec<_> = top_elves("3\n\n5\n\n1\n2\n\n5\n", 3)
+            .unwrap()
+            .iter()
+            .map(|ranked| ranked.elf)
+            .collect();
+        assert_eq!(tied, [2, 4, 1]);
+    }
+
+    #[test]
+    fn summarises_the_totals() {
+        assert_eq!(
+            summary(include_str!("../example_1.txt")).unwrap(),
+            Some(Summary {
+                count: 5,
+                mean: 11000.0,
+                median: 10000.0,
+                min: 4000,
+            })
+        );
+        assert_eq!(
+            summary("1\n\n4\n\n2\n\n10\n").unwrap(),
+            Some(Summary {
+                count: 4,
+                mean: 4.25,
+                median: 3.0,
+                min: 1,
+            })
+        );
+        assert_eq!(summary("\n").unwrap(), None);
+    }
+
+    #[test]
+    fn accepts_crlf_and_trailing_blank_lines() {
+        let input = "1000\r\n2000\r\n\r\n4000\r\n\r\n\r\n";
+        assert_eq!(
+            Inventory::new(input).unwrap().elves(),
+            [vec![1000, 2000], vec![4000]]
+        );
+        assert_eq!(part_one(input), Ok(4000));
+    }
+
+    #[test]
+    fn names_the_elf_and_line_of_bad_items() {
+        assert_eq!(
+            part_one("1000\n\n2000\n12O0\n"),
+            Err(InventoryError::Item {
+                elf: 2,
+                line: 4,
+                item: String::from("12O0"),
+            })
+        );
+        assert_eq!(
+            part_two("1000\n\n\n2000\n"),
+            Err(InventoryError::NoItems { elf: 2, line: 3 })
+        );
+        assert_eq!(
+            Day01.part_one("1000\n-5\n").unwrap_err().to_string(),
+            "Elf 1, line 2, column 1: expected a number of calories, found '-'"
+        );
+        assert_eq!(
+            Day01.part_two("1\n\n2\n\n3\n4x\n").unwrap_err().to_string(),
+            "Elf 3, line 6, column 2: expected a number of calories, found 'x'"
+        );
+        assert_eq!(
+            Day01.part_two("1000\n\n\n2000\n").unwrap_err().to_string(),
+            "Elf 2, line 3, column 1: expected a number of calories, found '\\n'"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn example_from_reader() {
+        let example = include_bytes!("../example_1.txt");
+        assert_eq!(part_one_from_reader(&example[..]).unwrap(), 24000);
+        assert_eq!(part_two_from_reader(&example[..]).unwrap(), 45000);
+        assert!(matches!(
+            part_one_from_reader(&b"1000\r\n\r\nx\r\n"[..]),
+            Err(ReadError::Parse(ParseError {
+                line: 3,
+                within: Some(("Elf", 2)),
+                ..
+            }))
+        ));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge_1.txt")), 207410);
+        assert_eq!(
+            part_two(include_str!("../challenge_1.txt")).unwrap(),
+            207410
+        );
     }
 }
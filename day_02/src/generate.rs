@@ -0,0 +1,63 @@
+//! Random strategy guides with known answers, for testing.
+
+use alloc::{format, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated strategy guide is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub rounds: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size { rounds: 100 }
+    }
+}
+
+/// The score of every line of the guide, read as moves in part one and as an opponent's move and
+/// an outcome in part two, spelled out rather than calculated as the shape's score plus the
+/// outcome's.
+#[allow(clippy::identity_op)]
+const SCORES: [(&str, u64, u64); 9] = [
+    ("A X", 1 + 3, 3 + 0),
+    ("A Y", 2 + 6, 1 + 3),
+    ("A Z", 3 + 0, 2 + 6),
+    ("B X", 1 + 0, 1 + 0),
+    ("B Y", 2 + 3, 2 + 3),
+    ("B Z", 3 + 6, 3 + 6),
+    ("C X", 1 + 6, 2 + 0),
+    ("C Y", 2 + 0, 3 + 3),
+    ("C Z", 3 + 3, 1 + 6),
+];
+
+pub fn generate(seed: u64, size: &Size) -> Generated<u64, u64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let rounds: Vec<_> = (0..size.rounds)
+        .map(|_| SCORES[rng.gen_range(0..SCORES.len())])
+        .collect();
+    Generated {
+        input: rounds
+            .iter()
+            .map(|(line, _, _)| format!("{line}\n"))
+            .collect(),
+        part_one: Some(rounds.iter().map(|(_, score, _)| score).sum()),
+        part_two: Some(rounds.iter().map(|(_, _, score)| score).sum()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(Some(crate::part_one(&generated.input)), generated.part_one);
+            assert_eq!(Some(crate::part_two(&generated.input)), generated.part_two);
+        }
+    }
+}
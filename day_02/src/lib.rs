@@ -1,55 +1,441 @@
-// Rock: 0
-// Paper: 1
-// Scissors: 2
+#![no_std]
 
-// Lose: 0
-// Draw: 1
-// Win: 2
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-struct Round {
-    outcome: u8,
-    ours: u8,
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use aoc_core::io::{with_lines, ReadError};
+use core::{
+    cmp::Reverse,
+    fmt::{self, Display},
+};
+
+use aoc_core::{Observer, Solution, SolveError, Step};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// How a round turned out for us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        }
+    }
+}
+
+/// What the second column of the strategy guide says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The move we should play
+    Move,
+    /// How the round should turn out
+    Outcome,
+}
+
+/// A game like rock paper scissors, with an odd number of moves in a cycle.
+///
+/// Each move beats the half of the other moves that come just before it in the cycle, and loses
+/// to the half that come just after it, so every pair of different moves has a winner. The
+/// strategy guide writes their move with one set of letters, and our move or the outcome with
+/// another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<String>,
+    /// The letter for each move in the first column
+    their_letters: Vec<char>,
+    /// The letter for each move in the second column, when it says what to play
+    our_letters: Vec<char>,
+    /// The letter for each outcome in the second column, when it says how the round ends
+    outcome_letters: [char; 3],
+    shape_scores: Vec<u64>,
+    outcome_scores: [u64; 3],
 }
 
-impl Round {
-    fn new_from_our_move(input: &[u8]) -> Option<Self> {
-        let theirs = input.first()? - b'A';
-        let ours = input.get(2)? - b'X';
-        let outcome = (ours + 4 - theirs) % 3;
-        Some(Self { outcome, ours })
+/// A round of a [`Game`], with moves numbered by their place in the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub theirs: usize,
+    pub ours: usize,
+    pub outcome: Outcome,
+}
+
+/// Checks that `letters` has `count` letters, with none repeated.
+fn distinct_letters(letters: &str, count: usize) -> Result<Vec<char>, &'static str> {
+    let letters: Vec<char> = letters.chars().collect();
+    if letters.len() != count {
+        return Err("There must be a letter for each move or outcome");
+    }
+    if letters
+        .iter()
+        .enumerate()
+        .any(|(i, letter)| letters[..i].contains(letter))
+    {
+        return Err("The same letter can't stand for two moves or outcomes");
+    }
+    Ok(letters)
+}
+
+impl Game {
+    /// A game of `moves`, listed so that each beats the half of the others before it.
+    ///
+    /// Their moves are written `A`, `B`, `C`…, our moves are written with the letters up to `Z`,
+    /// and the outcomes `X`, `Y` and `Z`. Moves score 1, 2, 3… and outcomes 0, 3 and 6.
+    pub fn new(moves: &[&str]) -> Result<Self, &'static str> {
+        let count = moves.len();
+        // with one move there's nothing to win or lose against
+        if count.is_multiple_of(2) || !(3..=25).contains(&count) {
+            return Err("A game must have an odd number of moves, from 3 to 25");
+        }
+        let letters = |first: u8| (first..first + count as u8).map(char::from).collect();
+        Ok(Game {
+            moves: moves.iter().map(|name| name.to_string()).collect(),
+            their_letters: letters(b'A'),
+            our_letters: letters(b'Z' + 1 - count as u8),
+            outcome_letters: ['X', 'Y', 'Z'],
+            shape_scores: (1..=count as u64).collect(),
+            outcome_scores: [0, 3, 6],
+        })
+    }
+
+    /// The puzzle's game.
+    pub fn rock_paper_scissors() -> Self {
+        Game::new(&["rock", "paper", "scissors"]).expect("three moves make a game")
+    }
+
+    /// Rock paper scissors lizard Spock, written `A` to `E` and `V` to `Z`.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Game::new(&["rock", "Spock", "paper", "lizard", "scissors"])
+            .expect("five moves make a game")
+    }
+
+    /// Changes the letters for their moves and ours, with one letter per move in the order of
+    /// the moves, and the letters for a loss, a draw and a win.
+    pub fn with_letters(
+        self,
+        theirs: &str,
+        ours: &str,
+        outcomes: &str,
+    ) -> Result<Self, &'static str> {
+        let outcome_letters = distinct_letters(outcomes, 3)?;
+        Ok(Game {
+            their_letters: distinct_letters(theirs, self.moves.len())?,
+            our_letters: distinct_letters(ours, self.moves.len())?,
+            outcome_letters: [outcome_letters[0], outcome_letters[1], outcome_letters[2]],
+            ..self
+        })
     }
 
-    fn new_from_round_outcome(input: &[u8]) -> Option<Self> {
-        let theirs = input.first()? - b'A';
-        let outcome = input.get(2)? - b'X';
-        let ours = (outcome + 2 + theirs) % 3;
-        Some(Self { ours, outcome })
+    /// Changes the score of playing each move, in the order of the moves, and of a loss, a draw
+    /// and a win.
+    pub fn with_scores(self, shapes: &[u64], outcomes: [u64; 3]) -> Result<Self, &'static str> {
+        if shapes.len() != self.moves.len() {
+            return Err("There must be a score for each move");
+        }
+        Ok(Game {
+            shape_scores: shapes.to_vec(),
+            outcome_scores: outcomes,
+            ..self
+        })
     }
 
-    fn score(&self) -> u64 {
-        (self.ours + 1 + self.outcome * 3) as u64
+    pub fn moves(&self) -> impl Iterator<Item = &str> {
+        self.moves.iter().map(String::as_str)
+    }
+
+    /// How the round turns out for us when we play `ours` against `theirs`.
+    pub fn play(&self, theirs: usize, ours: usize) -> Outcome {
+        let count = self.moves.len();
+        match (ours + count - theirs) % count {
+            0 => Outcome::Draw,
+            ahead if ahead <= count / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// The move to play against `theirs` for the round to end in `outcome`. Where several moves
+    /// would do, it's the highest scoring, or the first of those in the cycle.
+    pub fn response(&self, theirs: usize, outcome: Outcome) -> usize {
+        (0..self.moves.len())
+            .filter(|&ours| self.play(theirs, ours) == outcome)
+            .min_by_key(|&ours| Reverse(self.shape_scores[ours]))
+            .expect("every move can be won, lost and drawn against")
+    }
+
+    /// Reads a line of the strategy guide, such as `A Y`, or `None` if it isn't one.
+    pub fn round(&self, line: &str, strategy: Strategy) -> Option<Round> {
+        let mut letters = line.chars();
+        let first = letters.next()?;
+        let second = letters.nth(1)?;
+        let theirs = self.their_letters.iter().position(|&l| l == first)?;
+        Some(match strategy {
+            Strategy::Move => {
+                let ours = self.our_letters.iter().position(|&l| l == second)?;
+                Round {
+                    theirs,
+                    ours,
+                    outcome: self.play(theirs, ours),
+                }
+            }
+            Strategy::Outcome => {
+                let outcome =
+                    Outcome::ALL[self.outcome_letters.iter().position(|&l| l == second)?];
+                Round {
+                    theirs,
+                    ours: self.response(theirs, outcome),
+                    outcome,
+                }
+            }
+        })
+    }
+
+    pub fn score(&self, round: &Round) -> u64 {
+        self.shape_scores[round.ours] + self.outcome_scores[round.outcome as usize]
+    }
+
+    /// The total score of following the strategy guide, skipping lines that aren't rounds.
+    pub fn total_score<S: AsRef<str>>(
+        &self,
+        lines: impl IntoIterator<Item = S>,
+        strategy: Strategy,
+        observer: &mut dyn Observer,
+    ) -> u64 {
+        lines
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, line)| Some((i + 1, self.round(line.as_ref(), strategy)?)))
+            .map(|(number, round)| {
+                observer.explain(&|| self.explain(&round, number));
+                self.score(&round)
+            })
+            .sum()
+    }
+
+    /// Scores the strategy guide under every way of reading its second column, as any ordering
+    /// of our letters for the moves and of the outcome letters for the outcomes, highest first.
+    ///
+    /// Readings that score the same are listed moves first, and then in the order of the letters.
+    ///
+    /// A game of n moves has n! orderings of our letters, and each is scored over the whole
+    /// guide, so games of more than [`MAX_DECODED_MOVES`] moves are refused.
+    pub fn decode<S: AsRef<str>>(
+        &self,
+        lines: impl IntoIterator<Item = S>,
+    ) -> Result<Vec<Decoding>, &'static str> {
+        if self.moves.len() > MAX_DECODED_MOVES {
+            return Err("Only games of up to 8 moves can be decoded");
+        }
+        let lines: Vec<S> = lines.into_iter().collect();
+        let mut decodings = Vec::new();
+        let mut decode = |strategy, game: &Game, meanings: Vec<(char, String)>| {
+            let score = game.total_score(lines.iter().map(AsRef::as_ref), strategy, &mut ());
+            decodings.push(Decoding {
+                strategy,
+                meanings,
+                score,
+            });
+        };
+
+        let mut order: Vec<usize> = (0..self.moves.len()).collect();
+        loop {
+            let game = Game {
+                our_letters: order.iter().map(|&i| self.our_letters[i]).collect(),
+                ..self.clone()
+            };
+            let meanings = meanings(&game.our_letters, &self.moves);
+            decode(Strategy::Move, &game, meanings);
+            if !next_permutation(&mut order) {
+                break;
+            }
+        }
+
+        let outcome_names = Outcome::ALL.map(|outcome| outcome.name().to_string());
+        let mut order = [0, 1, 2];
+        loop {
+            let game = Game {
+                outcome_letters: order.map(|i| self.outcome_letters[i]),
+                ..self.clone()
+            };
+            let meanings = meanings(&game.outcome_letters, &outcome_names);
+            decode(Strategy::Outcome, &game, meanings);
+            if !next_permutation(&mut order) {
+                break;
+            }
+        }
+
+        decodings.sort_by_key(|decoding| Reverse(decoding.score));
+        Ok(decodings)
+    }
+
+    /// Describes round number `number`.
+    fn explain(&self, round: &Round, number: usize) -> Step {
+        let theirs = &self.moves[round.theirs];
+        let ours = &self.moves[round.ours];
+        let outcome = round.outcome.name();
+        let shape_score = self.shape_scores[round.ours];
+        let outcome_score = self.outcome_scores[round.outcome as usize];
+        let score = self.score(round);
+        Step::new(format!(
+            "Round {number}: they play {theirs} and we play {ours}, a {outcome} scoring \
+             {shape_score} + {outcome_score} = {score}",
+        ))
+        .with("round", number)
+        .with("theirs", theirs.as_str())
+        .with("ours", ours.as_str())
+        .with("outcome", outcome)
+        .with("score", score)
+    }
+}
+
+/// The most moves a game can have for [`Game::decode`], which tries all 8! = 40320 orderings of
+/// them.
+pub const MAX_DECODED_MOVES: usize = 8;
+
+/// A way of reading the second column of a strategy guide, and the score of following it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoding {
+    pub strategy: Strategy,
+    /// Each letter of the second column, with the name of the move or outcome it stands for
+    pub meanings: Vec<(char, String)>,
+    pub score: u64,
+}
+
+impl Display for Decoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.strategy {
+            Strategy::Move => "Playing",
+            Strategy::Outcome => "Aiming for",
+        };
+        write!(f, "{what} ")?;
+        for (i, (letter, meaning)) in self.meanings.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(f, "{separator}{meaning} for {letter}")?;
+        }
+        write!(f, " scores {}", self.score)
     }
 }
 
+/// Pairs each of `letters` with the name of what it stands for, in alphabetical order.
+fn meanings(letters: &[char], names: &[String]) -> Vec<(char, String)> {
+    let mut meanings: Vec<_> = letters.iter().copied().zip(names.iter().cloned()).collect();
+    meanings.sort_unstable();
+    meanings
+}
+
+/// Rearranges `order` into the next permutation in lexicographic order, or returns `false` if
+/// it's already the last.
+fn next_permutation(order: &mut [usize]) -> bool {
+    let Some(pivot) = order.windows(2).rposition(|pair| pair[0] < pair[1]) else {
+        return false;
+    };
+    let successor = order
+        .iter()
+        .rposition(|&value| value > order[pivot])
+        .expect("the value after the pivot is larger");
+    order.swap(pivot, successor);
+    order[pivot + 1..].reverse();
+    true
+}
+
+/// Every reading of the puzzle's strategy guide, highest scoring first.
+pub fn decode(input: &str) -> Vec<Decoding> {
+    Game::rock_paper_scissors()
+        .decode(input.lines())
+        .expect("three moves can be decoded")
+}
+
 pub fn part_one(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|line| Round::new_from_our_move(line.as_bytes()))
-        .map(|r| Round::score(&r))
-        .sum()
+    part_one_observed(input, &mut ())
+}
+
+/// Like [`part_one`], explaining each round's score to `observer`.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> u64 {
+    Game::rock_paper_scissors().total_score(input.lines(), Strategy::Move, observer)
 }
 
 pub fn part_two(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|line| Round::new_from_round_outcome(line.as_bytes()))
-        .map(|r| Round::score(&r))
-        .sum()
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], explaining each round's score to `observer`.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> u64 {
+    Game::rock_paper_scissors().total_score(input.lines(), Strategy::Outcome, observer)
+}
+
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    with_lines(reader, |lines| {
+        Game::rock_paper_scissors().total_score(lines, Strategy::Move, &mut ())
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    with_lines(reader, |lines| {
+        Game::rock_paper_scissors().total_score(lines, Strategy::Outcome, &mut ())
+    })
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn part_one(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_one(input))
+    }
+
+    fn part_two(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_two(input))
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_one_observed(input, observer))
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_two_observed(input, observer))
+    }
+
+    fn explains(&self, _part: u8) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::{string::ToString, vec, vec::Vec};
+    use aoc_core::Explanation;
 
     #[test]
     fn example_part_one() {
@@ -66,6 +452,132 @@ mod tests {
         assert_eq!(part_two(include_str!("../example_1.txt")), 12);
     }
 
+    #[test]
+    fn explains_each_round() {
+        let mut explanation = Explanation::default();
+        part_two_observed(include_str!("../example_1.txt"), &mut explanation);
+        let steps: Vec<_> = explanation.0.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            steps,
+            [
+                "Round 1: they play rock and we play rock, a draw scoring 1 + 3 = 4",
+                "Round 2: they play paper and we play rock, a loss scoring 1 + 0 = 1",
+                "Round 3: they play scissors and we play rock, a win scoring 1 + 6 = 7",
+            ]
+        );
+    }
+
+    #[test]
+    fn moves_beat_half_the_others() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let names: Vec<_> = game.moves().collect();
+        let beaten_by = |name| {
+            let ours = names.iter().position(|&n| n == name).unwrap();
+            let mut beaten: Vec<_> = (0..names.len())
+                .filter(|&theirs| game.play(theirs, ours) == Outcome::Win)
+                .map(|theirs| names[theirs])
+                .collect();
+            beaten.sort_unstable();
+            beaten
+        };
+        assert_eq!(beaten_by("scissors"), ["lizard", "paper"]);
+        assert_eq!(beaten_by("Spock"), ["rock", "scissors"]);
+        assert_eq!(beaten_by("lizard"), ["Spock", "paper"]);
+        assert_eq!(game.play(2, 2), Outcome::Draw);
+        assert_eq!(game.play(0, 3), Outcome::Loss);
+    }
+
+    #[test]
+    fn plays_with_other_letters_and_scores() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        // their lizard beats our Spock, and scissors draw with scissors
+        let guide = "D W\nE Z\n";
+        assert_eq!(game.total_score(guide.lines(), Strategy::Move, &mut ()), 10);
+        // W isn't an outcome, and of rock and Spock, which both beat scissors, Spock scores more
+        assert_eq!(
+            game.total_score(guide.lines(), Strategy::Outcome, &mut ()),
+            8
+        );
+
+        let game = Game::rock_paper_scissors()
+            .with_letters("RPS", "rps", "ldw")
+            .unwrap()
+            .with_scores(&[10, 20, 30], [0, 1, 2])
+            .unwrap();
+        assert_eq!(
+            game.total_score(["R p", "S w"], Strategy::Move, &mut ()),
+            22
+        );
+        assert_eq!(
+            game.total_score(["R p", "S w"], Strategy::Outcome, &mut ()),
+            12
+        );
+        assert!(Game::new(&["rock", "paper"]).is_err());
+        assert_eq!(
+            Game::new(&["rock"]).err(),
+            Some("A game must have an odd number of moves, from 3 to 25")
+        );
+        assert!(Game::rock_paper_scissors()
+            .with_letters("AAB", "XYZ", "XYZ")
+            .is_err());
+    }
+
+    #[test]
+    fn decodes_every_reading() {
+        let decodings = decode(include_str!("../example_1.txt"));
+        assert_eq!(decodings.len(), 12);
+        assert!(decodings
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        let described: Vec<_> = decodings.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            described[0],
+            "Playing scissors for X, paper for Y, rock for Z scores 24"
+        );
+        assert!(described.contains(&String::from(
+            "Playing rock for X, paper for Y, scissors for Z scores 15"
+        )));
+        assert!(described.contains(&String::from(
+            "Aiming for loss for X, draw for Y, win for Z scores 12"
+        )));
+    }
+
+    #[test]
+    fn permutes_in_order() {
+        let mut order = [0, 1, 2];
+        let mut seen = vec![order];
+        while next_permutation(&mut order) {
+            seen.push(order);
+        }
+        assert_eq!(
+            seen,
+            [
+                [0, 1, 2],
+                [0, 2, 1],
+                [1, 0, 2],
+                [1, 2, 0],
+                [2, 0, 1],
+                [2, 1, 0]
+            ]
+        );
+        assert_eq!(
+            Game::rock_paper_scissors_lizard_spock()
+                .decode(["A V"])
+                .map(|decodings| decodings.len()),
+            Ok(120 + 6)
+        );
+        let nine = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+        assert!(Game::new(&nine).unwrap().decode(["A R"]).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn example_from_reader() {
+        let example = include_bytes!("../example_1.txt");
+        assert_eq!(part_one_from_reader(&example[..]).unwrap(), 15);
+        assert_eq!(part_two_from_reader(&example[..]).unwrap(), 12);
+    }
+
     #[test]
     fn challenge_part_two() {
         assert_eq!(part_two(include_str!("../challenge_1.txt")), 13726);
@@ -0,0 +1,102 @@
+//! Random rucksacks with known answers, for testing.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How big a generated list of rucksacks is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// Groups of three elves, each sharing one badge
+    pub groups: usize,
+    /// The most items in one compartment of a rucksack
+    pub max_compartment: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            groups: 10,
+            max_compartment: 16,
+        }
+    }
+}
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn priority(item: u8) -> u64 {
+    ITEMS.iter().position(|&i| i == item).unwrap() as u64 + 1
+}
+
+/// A rucksack holding `badge` whose compartments share only one item, chosen from `own` items
+/// that no other rucksack in the group holds.
+fn rucksack(rng: &mut StdRng, badge: u8, own: &[u8], max_compartment: usize) -> Vec<u8> {
+    let misplaced = if rng.gen_bool(0.2) { badge } else { own[0] };
+    let (left, right) = own[1..].split_at(own.len() / 2);
+    let length = rng.gen_range(2..=max_compartment.max(2));
+    let mut compartments = [vec![misplaced], vec![misplaced]];
+    if misplaced != badge {
+        compartments[rng.gen_range(0..2)].push(badge);
+    }
+    for (compartment, others) in compartments.iter_mut().zip([left, right]) {
+        while compartment.len() < length {
+            compartment.push(*others.choose(rng).unwrap());
+        }
+        compartment.shuffle(rng);
+    }
+    compartments.concat()
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<u64, u64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rucksacks = Vec::new();
+    for _ in 0..size.groups {
+        let mut items = ITEMS.to_vec();
+        items.shuffle(&mut rng);
+        let (badge, others) = items.split_first().unwrap();
+        for own in others.chunks(others.len() / 3) {
+            rucksacks.push(rucksack(&mut rng, *badge, own, size.max_compartment));
+        }
+    }
+
+    let shared = |rucksacks: &[&[u8]]| -> u64 {
+        ITEMS
+            .iter()
+            .filter(|item| rucksacks.iter().all(|r| r.contains(item)))
+            .map(|&item| priority(item))
+            .sum()
+    };
+    Generated {
+        input: rucksacks
+            .iter()
+            .map(|r| format!("{}\n", String::from_utf8_lossy(r)))
+            .collect(),
+        part_one: Some(
+            rucksacks
+                .iter()
+                .map(|r| shared(&[&r[..r.len() / 2], &r[r.len() / 2..]]))
+                .sum(),
+        ),
+        part_two: Some(
+            rucksacks
+                .chunks(3)
+                .map(|group| shared(&[&group[0], &group[1], &group[2]]))
+                .sum(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(Some(crate::part_one(&generated.input)), generated.part_one);
+            assert_eq!(Some(crate::part_two(&generated.input)), generated.part_two);
+        }
+    }
+}
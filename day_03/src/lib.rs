@@ -1,4 +1,19 @@
-use std::collections::BTreeSet;
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{collections::BTreeSet, format};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use aoc_core::io::{with_lines, ReadError};
+use aoc_core::{Observer, Solution, SolveError, Step};
+
+#[cfg(feature = "generate")]
+pub mod generate;
 
 fn item_priority(item: u8) -> u64 {
     (match item {
@@ -8,48 +23,158 @@ fn item_priority(item: u8) -> u64 {
     }) as u64
 }
 
-pub fn part_one(input: &str) -> u64 {
-    input
-        .lines()
-        .filter_map(|backpack| {
+/// Describes the item `shared` by rucksack or group `number`, called `what` in the text and
+/// `key` among the values.
+fn explain_shared(
+    what: &str,
+    key: &'static str,
+    number: usize,
+    shared: Option<u8>,
+    place: &str,
+) -> Step {
+    let step = match shared {
+        Some(item) => Step::new(format!(
+            "{what} {number}: {} is {place}, with priority {}",
+            item as char,
+            item_priority(item)
+        ))
+        .with("item", item as char),
+        None => Step::new(format!("{what} {number} has nothing {place}")),
+    };
+    step.with(key, number)
+        .with("priority", shared.map_or(0, item_priority))
+}
+
+fn misplaced_priorities<S: AsRef<str>>(
+    backpacks: impl IntoIterator<Item = S>,
+    observer: &mut dyn Observer,
+) -> u64 {
+    backpacks
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, backpack)| {
+            let backpack = backpack.as_ref();
             let (a, b) = backpack.as_bytes().split_at(backpack.len() / 2);
-            a.iter()
+            let misplaced = a
+                .iter()
                 .collect::<BTreeSet<_>>()
                 .intersection(&b.iter().collect())
-                .map(|&&item| item_priority(item))
-                .next()
+                .map(|&&item| item)
+                .next();
+            observer.explain(&|| {
+                explain_shared(
+                    "Rucksack",
+                    "rucksack",
+                    i + 1,
+                    misplaced,
+                    "in both compartments",
+                )
+            });
+            misplaced.map(item_priority)
         })
         .sum()
 }
 
-pub fn part_two(input: &str) -> u64 {
-    let mut backpacks_iter = input.lines();
+fn badge_priorities<S: AsRef<str>>(
+    backpacks: impl IntoIterator<Item = S>,
+    observer: &mut dyn Observer,
+) -> u64 {
+    let mut backpacks_iter = backpacks.into_iter();
     let mut sum = 0;
+    let mut group = 0;
 
     while let (Some(a), Some(b), Some(c)) = (
         backpacks_iter.next(),
         backpacks_iter.next(),
         backpacks_iter.next(),
     ) {
-        sum += a
+        group += 1;
+        let badge = a
+            .as_ref()
             .bytes()
             .collect::<BTreeSet<_>>()
-            .intersection(&b.bytes().collect())
+            .intersection(&b.as_ref().bytes().collect())
             .cloned()
             .collect::<BTreeSet<_>>()
-            .intersection(&c.bytes().collect())
+            .intersection(&c.as_ref().bytes().collect())
             .cloned()
-            .map(item_priority)
-            .next()
-            .unwrap_or(0);
+            .next();
+        observer
+            .explain(&|| explain_shared("Group", "group", group, badge, "in all three rucksacks"));
+        sum += badge.map_or(0, item_priority);
     }
 
     sum
 }
 
+pub fn part_one(input: &str) -> u64 {
+    part_one_observed(input, &mut ())
+}
+
+/// Like [`part_one`], explaining to `observer` which item each rucksack has in both halves.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> u64 {
+    misplaced_priorities(input.lines(), observer)
+}
+
+pub fn part_two(input: &str) -> u64 {
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], explaining to `observer` each group's badge.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> u64 {
+    badge_priorities(input.lines(), observer)
+}
+
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    with_lines(reader, |backpacks| misplaced_priorities(backpacks, &mut ()))
+}
+
+#[cfg(feature = "std")]
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    with_lines(reader, |backpacks| badge_priorities(backpacks, &mut ()))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn part_one(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_one(input))
+    }
+
+    fn part_two(&self, input: &str) -> Result<u64, SolveError> {
+        Ok(part_two(input))
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_one_observed(input, observer))
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u64, SolveError> {
+        Ok(part_two_observed(input, observer))
+    }
+
+    fn explains(&self, _part: u8) -> bool {
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::{string::ToString, vec::Vec};
+    use aoc_core::Explanation;
 
     #[test]
     fn example_part_one() {
@@ -66,6 +191,36 @@ mod tests {
         assert_eq!(part_two(include_str!("../example.txt")), 70);
     }
 
+    #[test]
+    fn explains_the_shared_items() {
+        let example = include_str!("../example.txt");
+        let mut explanation = Explanation::default();
+        part_one_observed(example, &mut explanation);
+        assert_eq!(explanation.0.len(), 6);
+        assert_eq!(
+            explanation.0[0].to_string(),
+            "Rucksack 1: p is in both compartments, with priority 16"
+        );
+        let mut explanation = Explanation::default();
+        part_two_observed(example, &mut explanation);
+        let steps: Vec<_> = explanation.0.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            steps,
+            [
+                "Group 1: r is in all three rucksacks, with priority 18",
+                "Group 2: Z is in all three rucksacks, with priority 52",
+            ]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn example_from_reader() {
+        let example = include_bytes!("../example.txt");
+        assert_eq!(part_one_from_reader(&example[..]).unwrap(), 157);
+        assert_eq!(part_two_from_reader(&example[..]).unwrap(), 70);
+    }
+
     #[test]
     fn challenge_part_two() {
         assert_eq!(part_two(include_str!("../challenge.txt")), 2548);
@@ -0,0 +1,69 @@
+//! Random section assignments with known answers, for testing.
+
+use alloc::{format, string::String, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated list of assignments is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub pairs: usize,
+    /// The highest section number an elf can be assigned
+    pub sections: u64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            pairs: 100,
+            sections: 20,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let sections = size.sections.max(1);
+    let mut assignment = || {
+        let start = rng.gen_range(1..=sections);
+        (start, rng.gen_range(start..=sections))
+    };
+    let pairs: Vec<_> = (0..size.pairs)
+        .map(|_| (assignment(), assignment()))
+        .collect();
+
+    let within = |(a, b): (u64, u64), (c, d): (u64, u64)| (a..=b).all(|s| (c..=d).contains(&s));
+    Generated {
+        input: pairs
+            .iter()
+            .map(|((a, b), (c, d))| format!("{a}-{b},{c}-{d}\n"))
+            .collect::<String>(),
+        part_one: Some(
+            pairs
+                .iter()
+                .filter(|&&(x, y)| within(x, y) || within(y, x))
+                .count(),
+        ),
+        part_two: Some(
+            pairs
+                .iter()
+                .filter(|((a, b), (c, d))| (*a..=*b).any(|s| (*c..=*d).contains(&s)))
+                .count(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,12 +1,28 @@
 #![no_std]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use aoc_core::io::{with_lines, ReadError};
+use aoc_core::{
+    parse::{final_parse, lines, IResult},
+    ParseError, Solution, SolveError,
+};
 use nom::{
     character::complete::{char, digit1},
     combinator::{map, map_res},
     sequence::separated_pair,
-    IResult,
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
 #[derive(Debug)]
 struct SectionAssignment(u64, u64);
 
@@ -39,43 +55,114 @@ fn parse_elf_pair(input: &str) -> IResult<&str, (SectionAssignment, SectionAssig
     )(input)
 }
 
-fn elf_pairs(input: &str) -> impl Iterator<Item = (SectionAssignment, SectionAssignment)> + '_ {
-    input
-        .lines()
-        .filter_map(|line| parse_elf_pair(line).ok().map(|(_, pair)| pair))
+fn elf_pairs(input: &str) -> Result<Vec<(SectionAssignment, SectionAssignment)>, ParseError> {
+    final_parse(input, lines(parse_elf_pair))
 }
 
-pub fn part_one(input: &str) -> usize {
-    elf_pairs(input)
+fn fully_contained_count(
+    elf_pairs: impl IntoIterator<Item = (SectionAssignment, SectionAssignment)>,
+) -> usize {
+    elf_pairs
+        .into_iter()
         .filter(|(a, b)| a.fully_contains(b) || b.fully_contains(a))
         .count()
 }
 
-pub fn part_two(input: &str) -> usize {
-    elf_pairs(input).filter(|(a, b)| a.overlaps(b)).count()
+fn overlapping_count(
+    elf_pairs: impl IntoIterator<Item = (SectionAssignment, SectionAssignment)>,
+) -> usize {
+    elf_pairs.into_iter().filter(|(a, b)| a.overlaps(b)).count()
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    Ok(fully_contained_count(elf_pairs(input)?))
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    Ok(overlapping_count(elf_pairs(input)?))
+}
+
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    with_lines(reader, |lines| {
+        fully_contained_count(lines.parsed(parse_elf_pair))
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    with_lines(reader, |lines| {
+        overlapping_count(lines.parsed(parse_elf_pair))
+    })
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::parse::Expected;
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 2);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(2));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 584);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(584));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 4);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(4));
+    }
+
+    #[test]
+    fn reports_bad_line() {
+        assert_eq!(
+            part_one("2-4,6-8\n2-3,4_5\n"),
+            Err(ParseError {
+                line: 2,
+                column: 6,
+                expected: Expected::Char('-'),
+                found: Some('_'),
+                within: None,
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn example_from_reader() {
+        let example = include_bytes!("../example.txt");
+        assert_eq!(part_one_from_reader(&example[..]).unwrap(), 2);
+        assert_eq!(part_two_from_reader(&example[..]).unwrap(), 4);
+        assert!(matches!(
+            part_one_from_reader(&b"2-4,6-8\n2-3,4_5\n"[..]),
+            Err(ReadError::Parse(ParseError {
+                line: 2,
+                column: 6,
+                ..
+            }))
+        ));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 933);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(933));
     }
 }
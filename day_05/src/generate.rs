@@ -0,0 +1,105 @@
+//! Random crate stacks and rearrangements with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated drawing and list of moves are.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// At most 9, so that every stack is labelled with one digit
+    pub stacks: usize,
+    pub max_height: usize,
+    pub moves: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            stacks: 5,
+            max_height: 6,
+            moves: 30,
+        }
+    }
+}
+
+/// The crates on top of the stacks after following `moves`, where the crane lifts `lift` crates
+/// at a time.
+fn rearrange(mut stacks: Vec<Vec<char>>, moves: &[(usize, usize, usize)], lift: bool) -> String {
+    for &(count, from, to) in moves {
+        let mut held = Vec::new();
+        for _ in 0..count {
+            held.push(stacks[from].pop().unwrap());
+            if !lift {
+                stacks[to].push(held.pop().unwrap());
+            }
+        }
+        while let Some(c) = held.pop() {
+            stacks[to].push(c);
+        }
+    }
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<String, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let stack_count = size.stacks.clamp(1, 9);
+    let stacks: Vec<Vec<char>> = (0..stack_count)
+        .map(|_| {
+            (0..rng.gen_range(0..=size.max_height))
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect()
+        })
+        .collect();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0).max(1);
+
+    let mut input = String::new();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => String::from("   "),
+            })
+            .collect();
+        input += &row.join(" ");
+        input.push('\n');
+    }
+    let labels: Vec<String> = (1..=stack_count).map(|i| format!(" {i} ")).collect();
+    input += &labels.join(" ");
+    input += "\n\n";
+
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    let mut moves = Vec::new();
+    for _ in 0..size.moves {
+        let from = rng.gen_range(0..stack_count);
+        let to = rng.gen_range(0..stack_count);
+        if heights[from] == 0 || from == to {
+            continue;
+        }
+        let count = rng.gen_range(1..=heights[from]);
+        heights[from] -= count;
+        heights[to] += count;
+        moves.push((count, from, to));
+        input += &format!("move {count} from {} to {}\n", from + 1, to + 1);
+    }
+
+    Generated {
+        input,
+        part_one: Some(rearrange(stacks.clone(), &moves, false)),
+        part_two: Some(rearrange(stacks, &moves, true)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
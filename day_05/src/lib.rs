@@ -1,13 +1,19 @@
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    Observer, Solution, SolveError, Step,
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while},
+    bytes::complete::take_while,
     character::complete::{anychar, char, digit1, line_ending},
     combinator::{map, map_res},
     multi::{count, fold_many1, many1, separated_list1},
     sequence::{delimited, terminated, tuple},
-    IResult,
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
 fn parse_crate(input: &str) -> IResult<&str, char> {
     delimited(char('['), anychar, char(']'))(input)
 }
@@ -75,25 +81,104 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     )(input)
 }
 
-fn crane(input: &str, follow_instruction: impl Fn(&mut Vec<char>)) -> String {
-    let (input, mut stacks) = parse_starting_stacks(input).unwrap();
+fn tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
 
-    for instruction_line in input.lines() {
-        let instruction = parse_instruction(instruction_line).unwrap().1;
-        let from_stack = &mut stacks[instruction.from - 1];
-        let mut lift_stack = from_stack.split_off(from_stack.len() - instruction.num);
+fn crane(
+    input: &str,
+    follow_instruction: impl Fn(&mut Vec<char>),
+    observer: &mut dyn Observer,
+) -> Result<String, SolveError> {
+    let (mut stacks, instructions) = final_parse(
+        input,
+        tuple((parse_starting_stacks, lines(parse_instruction))),
+    )?;
+
+    for (number, instruction) in (1..).zip(instructions) {
+        let from_stack = stacks
+            .get_mut(instruction.from.wrapping_sub(1))
+            .ok_or("Instruction moves from a stack that doesn't exist")?;
+        let lift_from = from_stack
+            .len()
+            .checked_sub(instruction.num)
+            .ok_or("Instruction moves more crates than are in the stack")?;
+        let mut lift_stack = from_stack.split_off(lift_from);
         follow_instruction(&mut lift_stack);
-        stacks[instruction.to - 1].append(&mut lift_stack);
+        let stacked: String = lift_stack.iter().collect();
+        stacks
+            .get_mut(instruction.to.wrapping_sub(1))
+            .ok_or("Instruction moves to a stack that doesn't exist")?
+            .append(&mut lift_stack);
+        observer.explain(&|| {
+            let Instruction { num, from, to } = instruction;
+            let tops = tops(&stacks);
+            Step::new(format!(
+                "Move {number}: {num} from stack {from} to stack {to}, stacking {stacked} \
+                 there, so the tops read {tops}"
+            ))
+            .with("move", number)
+            .with("count", num)
+            .with("from", from)
+            .with("to", to)
+            .with("stacked", stacked.as_str())
+            .with("tops", tops)
+        });
     }
-    stacks.iter().filter_map(|stack| stack.last()).collect()
+    Ok(tops(&stacks))
 }
 
-pub fn part_one(input: &str) -> String {
-    crane(input, |lift_stack| lift_stack.reverse())
+pub fn part_one(input: &str) -> Result<String, SolveError> {
+    part_one_observed(input, &mut ())
 }
 
-pub fn part_two(input: &str) -> String {
-    crane(input, |_| {})
+/// Like [`part_one`], explaining each move of the crane to `observer`.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<String, SolveError> {
+    crane(input, |lift_stack| lift_stack.reverse(), observer)
+}
+
+pub fn part_two(input: &str) -> Result<String, SolveError> {
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], explaining each move of the crane to `observer`.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<String, SolveError> {
+    crane(input, |_| {}, observer)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn part_one(&self, input: &str) -> Result<String, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<String, SolveError> {
+        part_two(input)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<String, SolveError> {
+        part_one_observed(input, observer)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<String, SolveError> {
+        part_two_observed(input, observer)
+    }
+
+    fn explains(&self, _part: u8) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -102,21 +187,49 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), "CMZ");
+        assert_eq!(
+            part_one(include_str!("../example.txt")).as_deref(),
+            Ok("CMZ")
+        );
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), "QNHWJVJZW");
+        assert_eq!(
+            part_one(include_str!("../challenge.txt")).as_deref(),
+            Ok("QNHWJVJZW")
+        );
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), "MCD");
+        assert_eq!(
+            part_two(include_str!("../example.txt")).as_deref(),
+            Ok("MCD")
+        );
+    }
+
+    #[test]
+    fn explains_each_move() {
+        let mut explanation = aoc_core::Explanation::default();
+        part_one_observed(include_str!("../example.txt"), &mut explanation).unwrap();
+        let steps: Vec<_> = explanation.0.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            steps,
+            [
+                "Move 1: 1 from stack 2 to stack 1, stacking D there, so the tops read DCP",
+                "Move 2: 3 from stack 1 to stack 3, stacking DNZ there, so the tops read CZ",
+                "Move 3: 2 from stack 2 to stack 1, stacking CM there, so the tops read MZ",
+                "Move 4: 1 from stack 1 to stack 2, stacking M there, so the tops read CMZ",
+            ]
+        );
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), "BPCZJLFJW");
+        assert_eq!(
+            part_two(include_str!("../challenge.txt")).as_deref(),
+            Ok("BPCZJLFJW")
+        );
     }
 }
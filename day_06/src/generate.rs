@@ -0,0 +1,68 @@
+//! Random datastreams with known answers, for testing.
+
+use alloc::{string::String, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How big a generated datastream is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub length: usize,
+    /// How many different letters the datastream uses. Fewer letters make markers rarer
+    pub letters: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            length: 200,
+            letters: 15,
+        }
+    }
+}
+
+/// The number of characters read when the last `size` are first all different.
+fn marker_end(stream: &[u8], size: usize) -> Option<usize> {
+    (size..=stream.len()).find(|&end| {
+        let mut window = stream[end - size..end].to_vec();
+        window.sort_unstable();
+        window.dedup();
+        window.len() == size
+    })
+}
+
+/// A datastream of lowercase letters. Unless it's too short, somewhere in it are 14 different
+/// letters in a row, so that both parts have an answer.
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let letters = &b"abcdefghijklmnopqrstuvwxyz"[..size.letters.clamp(1, 26)];
+    let mut stream: Vec<u8> = (0..size.length)
+        .map(|_| *letters.choose(&mut rng).unwrap())
+        .collect();
+    if stream.len() >= 14 {
+        let mut marker = b"abcdefghijklmnopqrstuvwxyz".to_vec();
+        marker.shuffle(&mut rng);
+        let start = rng.gen_range(0..=stream.len() - 14);
+        stream[start..start + 14].copy_from_slice(&marker[..14]);
+    }
+    Generated {
+        part_one: marker_end(&stream, 4),
+        part_two: marker_end(&stream, 14),
+        input: String::from_utf8(stream).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input), generated.part_two);
+        }
+    }
+}
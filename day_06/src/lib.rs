@@ -1,25 +1,77 @@
-fn unique_window_index(input: &str, size: usize) -> Option<usize> {
-    let input: Vec<char> = input.chars().collect();
-    let mut index = 0;
-    while let Some(window) = input.get(index..index + size) {
-        if window
-            .iter()
-            .enumerate()
-            .all(|(i, v)| window.iter().skip(i + 1).all(|w| w != v))
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use aoc_core::io::{with_bytes, ReadError};
+use aoc_core::{Solution, SolveError};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+fn unique_window_index<T: PartialEq>(
+    input: impl IntoIterator<Item = T>,
+    size: usize,
+) -> Option<usize> {
+    let mut window = VecDeque::with_capacity(size);
+    for (index, value) in input.into_iter().enumerate() {
+        if window.len() == size {
+            window.pop_front();
+        }
+        window.push_back(value);
+        if window.len() == size
+            && window
+                .iter()
+                .enumerate()
+                .all(|(i, v)| window.iter().skip(i + 1).all(|w| w != v))
         {
-            return Some(index + size);
+            return Some(index + 1);
         }
-        index += 1;
     }
     None
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    unique_window_index(input, 4)
+    unique_window_index(input.chars(), 4)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    unique_window_index(input, 14)
+    unique_window_index(input.chars(), 14)
+}
+
+/// Like [`part_one`], but reading the datastream only as far as the marker.
+///
+/// The datastream is compared byte by byte, which matches [`part_one`] for ASCII input.
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<Option<usize>, ReadError> {
+    with_bytes(reader, |bytes| unique_window_index(bytes, 4))
+}
+
+/// Like [`part_two`], but reading the datastream only as far as the marker.
+#[cfg(feature = "std")]
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<Option<usize>, ReadError> {
+    with_bytes(reader, |bytes| unique_window_index(bytes, 14))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        part_one(input).ok_or(SolveError::NoAnswer("No start-of-packet marker"))
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        part_two(input).ok_or(SolveError::NoAnswer("No start-of-message marker"))
+    }
 }
 
 #[cfg(test)]
@@ -57,6 +109,21 @@ mod tests {
         assert_eq!(part_two(EXAMPLES[4]), Some(26));
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn examples_from_reader() {
+        for example in EXAMPLES {
+            assert_eq!(
+                part_one_from_reader(example.as_bytes()).unwrap(),
+                part_one(example)
+            );
+            assert_eq!(
+                part_two_from_reader(example.as_bytes()).unwrap(),
+                part_two(example)
+            );
+        }
+    }
+
     #[test]
     fn part_two_challenge() {
         assert_eq!(part_two(include_str!("../challenge.txt")), Some(2313));
@@ -0,0 +1,129 @@
+//! Random terminal sessions exploring a file system, with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated file system is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// Directories besides `/`
+    pub directories: usize,
+    pub files: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            directories: 30,
+            files: 80,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Directory {
+    name: String,
+    files: Vec<(String, u64)>,
+    children: Vec<usize>,
+}
+
+fn name(rng: &mut StdRng, taken: impl Fn(&str) -> bool) -> String {
+    loop {
+        let name: String = (0..rng.gen_range(1..=6))
+            .map(|_| rng.gen_range(b'a'..=b'z') as char)
+            .collect();
+        if !taken(&name) {
+            return name;
+        }
+    }
+}
+
+/// Puts a file of `size` in a random directory.
+fn add_file(rng: &mut StdRng, directories: &mut [Directory], size: u64) {
+    let index = rng.gen_range(0..directories.len());
+    let directory = &mut directories[index];
+    let name = name(rng, |n| directory.files.iter().any(|(f, _)| f == n));
+    directory.files.push((name, size));
+}
+
+/// Writes the commands that change into each directory and list it, depth first.
+fn explore(directories: &[Directory], index: usize, session: &mut String) {
+    let directory = &directories[index];
+    *session += "$ ls\n";
+    for &child in &directory.children {
+        *session += &format!("dir {}\n", directories[child].name);
+    }
+    for (name, size) in &directory.files {
+        *session += &format!("{size} {name}\n");
+    }
+    for &child in &directory.children {
+        *session += &format!("$ cd {}\n", directories[child].name);
+        explore(directories, child, session);
+        *session += "$ cd ..\n";
+    }
+}
+
+fn total_size(directories: &[Directory], index: usize) -> u64 {
+    let directory = &directories[index];
+    directory.files.iter().map(|(_, size)| size).sum::<u64>()
+        + directory
+            .children
+            .iter()
+            .map(|&child| total_size(directories, child))
+            .sum::<u64>()
+}
+
+/// A session exploring a file system that uses between 40,000,001 and 70,000,000 of the disk,
+/// so that something must be deleted to make room for the update.
+pub fn generate(seed: u64, size: &Size) -> Generated<u64, u64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut directories = vec![Directory::default()];
+    for index in 1..=size.directories {
+        let parent = rng.gen_range(0..index);
+        let name = name(&mut rng, |n| {
+            directories[parent]
+                .children
+                .iter()
+                .any(|&c| directories[c].name == n)
+        });
+        directories[parent].children.push(index);
+        directories.push(Directory {
+            name,
+            ..Directory::default()
+        });
+    }
+    let max_file = 30_000_000 / (size.files as u64 + 1);
+    for _ in 0..size.files {
+        let file_size = rng.gen_range(1..=max_file.max(1));
+        add_file(&mut rng, &mut directories, file_size);
+    }
+    let used = total_size(&directories, 0);
+    let target = rng.gen_range(40_000_001..=70_000_000);
+    add_file(&mut rng, &mut directories, target - used);
+
+    let mut input = String::from("$ cd /\n");
+    explore(&directories, 0, &mut input);
+    let sizes: Vec<u64> = (0..directories.len())
+        .map(|index| total_size(&directories, index))
+        .collect();
+    let needed = sizes[0] - 40_000_000;
+    Generated {
+        input,
+        part_one: Some(sizes.iter().filter(|&&s| s <= 100_000).sum()),
+        part_two: sizes.iter().filter(|&&s| s >= needed).min().copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
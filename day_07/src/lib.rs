@@ -1,14 +1,59 @@
 use std::collections::HashMap;
 
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    parse_param, ParamError, Parameters, Solution, SolveError,
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, digit1, line_ending, not_line_ending},
-    combinator::{iterator, map, map_res},
-    sequence::{preceded, terminated, tuple},
-    IResult,
+    character::complete::{char, digit1, not_line_ending},
+    combinator::{map, map_res},
+    error::context,
+    sequence::{preceded, tuple},
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// The sizes the puzzle is set with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// Directories at most this big are counted in part one
+    pub small_directory: u64,
+    pub disk_size: u64,
+    /// The free space the update needs
+    pub space_needed: u64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            small_directory: 100000,
+            disk_size: 70000000,
+            space_needed: 30000000,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        let size = || parse_param(value, "a size");
+        match name {
+            "small_directory" => self.small_directory = size()?,
+            "disk_size" => self.disk_size = size()?,
+            "space_needed" => self.space_needed = size()?,
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "small_directory",
+                    "disk_size",
+                    "space_needed",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 enum ConsoleLine<'a> {
     Cd(&'a str),
@@ -17,16 +62,19 @@ enum ConsoleLine<'a> {
     File { size: u64 },
 }
 
-fn parse_command(input: &str) -> IResult<&str, ConsoleLine> {
-    alt((
-        preceded(tag("$ cd "), map(not_line_ending, ConsoleLine::Cd)),
-        map(tag("$ ls"), |_| ConsoleLine::Ls),
-        preceded(tag("dir "), map(not_line_ending, ConsoleLine::Directory)),
-        map(
-            tuple((map_res(digit1, str::parse), char(' '), not_line_ending)),
-            |(size, _, _name)| ConsoleLine::File { size },
-        ),
-    ))(input)
+fn parse_command(input: &str) -> IResult<&str, ConsoleLine<'_>> {
+    context(
+        "a command or a directory entry",
+        alt((
+            preceded(tag("$ cd "), map(not_line_ending, ConsoleLine::Cd)),
+            map(tag("$ ls"), |_| ConsoleLine::Ls),
+            preceded(tag("dir "), map(not_line_ending, ConsoleLine::Directory)),
+            map(
+                tuple((map_res(digit1, str::parse), char(' '), not_line_ending)),
+                |(size, _, _name)| ConsoleLine::File { size },
+            ),
+        )),
+    )(input)
 }
 
 #[derive(Debug, Default)]
@@ -35,7 +83,8 @@ struct FSTreeDirectory<'a> {
     size: u64,
 }
 
-struct FileSystem<'a> {
+/// The directory tree pieced together from a terminal session.
+pub struct FileSystem<'a> {
     nodes: Vec<FSTreeDirectory<'a>>,
 }
 
@@ -46,13 +95,14 @@ impl<'a> FileSystem<'a> {
             .ok_or("Failed to find node in filesystem")
     }
 
-    fn new_from_observations(input: &'a str) -> Result<Self, &'static str> {
-        let mut command_iter = iterator(input, terminated(parse_command, line_ending));
+    /// Replays the `cd` and `ls` commands and their output.
+    pub fn new_from_observations(input: &'a str) -> Result<Self, SolveError> {
+        let commands = final_parse(input, lines(parse_command))?;
         let mut filesystem = FileSystem {
             nodes: vec![FSTreeDirectory::default()],
         };
         let mut stack = vec![0];
-        for command in &mut command_iter {
+        for command in commands {
             let dir_count = filesystem.nodes.len();
             let current_index = *stack.last().ok_or("Expected stack of directories")?;
             let current_directory = filesystem.dir_at_index(current_index)?;
@@ -91,25 +141,76 @@ impl<'a> FileSystem<'a> {
     fn traverse_dir_sizes(&self) -> impl Iterator<Item = u64> + '_ {
         self.nodes.iter().map(|n| n.size)
     }
+
+    /// Every directory's path, such as `/a/e`, with the total size of the files within it, in
+    /// order of path.
+    pub fn directory_sizes(&self) -> Vec<(String, u64)> {
+        let mut sizes = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![(String::from("/"), 0)];
+        while let Some((path, index)) = stack.pop() {
+            let Some(directory) = self.nodes.get(index) else {
+                continue;
+            };
+            for (name, &child) in directory.children.iter() {
+                let separator = if path.ends_with('/') { "" } else { "/" };
+                stack.push((format!("{path}{separator}{name}"), child));
+            }
+            sizes.push((path, directory.size));
+        }
+        sizes.sort();
+        sizes
+    }
 }
 
-pub fn part_one(input: &str) -> Result<u64, &'static str> {
+pub fn part_one(input: &str) -> Result<u64, SolveError> {
+    part_one_with(input, &Params::default())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<u64, SolveError> {
     let tree = FileSystem::new_from_observations(input)?;
     Ok(tree
         .traverse_dir_sizes()
-        .filter(|&size| size <= 100000)
+        .filter(|&size| size <= params.small_directory)
         .sum())
 }
 
-pub fn part_two(input: &str) -> Result<u64, &'static str> {
+pub fn part_two(input: &str) -> Result<u64, SolveError> {
+    part_two_with(input, &Params::default())
+}
+
+pub fn part_two_with(input: &str, params: &Params) -> Result<u64, SolveError> {
     let tree = FileSystem::new_from_observations(input)?;
     let root = tree.nodes.first().ok_or("Failed to find root node")?;
-    let space_needed = 30000000 - (70000000 - root.size);
+    let free_space = params.disk_size.saturating_sub(root.size);
+    let space_needed = params.space_needed.saturating_sub(free_space);
 
     tree.traverse_dir_sizes()
         .filter(|&size| size >= space_needed)
         .min()
-        .ok_or("Failed to find big enough directory")
+        .ok_or(SolveError::NoAnswer("Failed to find big enough directory"))
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(u64, u64), SolveError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day07 {
+    pub params: Params,
+}
+
+impl Solution for Day07 {
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn part_one(&self, input: &str) -> Result<u64, SolveError> {
+        part_one_with(input, &self.params)
+    }
+
+    fn part_two(&self, input: &str) -> Result<u64, SolveError> {
+        part_two_with(input, &self.params)
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +232,35 @@ mod tests {
         assert_eq!(part_two(include_str!("../example.txt")), Ok(24933642));
     }
 
+    #[test]
+    fn example_directory_sizes() {
+        let file_system =
+            FileSystem::new_from_observations(include_str!("../example.txt")).unwrap();
+        assert_eq!(
+            file_system.directory_sizes(),
+            [
+                (String::from("/"), 48381165),
+                (String::from("/a"), 94853),
+                (String::from("/a/e"), 584),
+                (String::from("/d"), 24933642),
+            ]
+        );
+    }
+
+    #[test]
+    fn example_with_a_bigger_disk() {
+        let params = Params {
+            small_directory: 1000,
+            disk_size: 100000000,
+            ..Params::default()
+        };
+        // with room to spare, deleting the smallest directory is enough
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((584, 584))
+        );
+    }
+
     #[test]
     fn challenge_part_two() {
         assert_eq!(part_two(include_str!("../challenge.txt")), Ok(549173));
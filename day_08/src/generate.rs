@@ -0,0 +1,95 @@
+//! Random forests with known answers, for testing.
+
+use alloc::{string::String, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated forest is. It needn't be square.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            width: 12,
+            height: 7,
+        }
+    }
+}
+
+/// The heights of the trees seen looking from `(x, y)` in each direction, nearest first.
+fn views(trees: &[Vec<u8>], x: usize, y: usize) -> [Vec<u8>; 4] {
+    let row = &trees[y];
+    let column: Vec<u8> = trees.iter().map(|r| r[x]).collect();
+    [
+        column[..y].iter().rev().copied().collect(),
+        row[x + 1..].to_vec(),
+        column[y + 1..].to_vec(),
+        row[..x].iter().rev().copied().collect(),
+    ]
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let trees: Vec<Vec<u8>> = (0..size.height.max(1))
+        .map(|_| {
+            (0..size.width.max(1))
+                .map(|_| rng.gen_range(0..=9))
+                .collect()
+        })
+        .collect();
+
+    let mut visible = 0;
+    let mut best_score = 0;
+    for (y, row) in trees.iter().enumerate() {
+        for (x, &tree) in row.iter().enumerate() {
+            let views = views(&trees, x, y);
+            if views.iter().any(|view| view.iter().all(|&h| h < tree)) {
+                visible += 1;
+            }
+            let score: usize = views
+                .iter()
+                .map(|view| match view.iter().position(|&h| h >= tree) {
+                    Some(blocked) => blocked + 1,
+                    None => view.len(),
+                })
+                .product();
+            best_score = best_score.max(score);
+        }
+    }
+    Generated {
+        input: trees
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|h| char::from(b'0' + h))
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect(),
+        part_one: Some(visible),
+        part_two: Some(best_score),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let size = Size {
+                width: 1 + seed as usize % 13,
+                height: 1 + seed as usize % 7,
+            };
+            let generated = generate(seed, &size);
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,115 +1,54 @@
-#[derive(Debug)]
-struct Forest {
-    width: usize,
-    heights: Vec<u8>,
-}
+#![no_std]
 
-#[derive(Clone, Copy)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+extern crate alloc;
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::North,
-    Direction::East,
-    Direction::South,
-    Direction::West,
-];
-
-struct TreesIter<'a> {
-    forest: &'a Forest,
-    direction: Direction,
-    index: usize,
-}
+use aoc_core::{ParseError, Solution, SolveError};
+use grid::{Direction, Grid, Point};
 
-impl<'a> Iterator for TreesIter<'a> {
-    type Item = u8;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let i = self.index;
-        let w = self.forest.width;
-        let y = i / w;
-        let x = i % w;
-        self.index = match self.direction {
-            Direction::North => {
-                if y == 0 {
-                    None
-                } else {
-                    Some(i - w)
-                }
-            }
-            Direction::East => {
-                if x + 1 == w {
-                    None
-                } else {
-                    Some(i + 1)
-                }
-            }
-            Direction::South => {
-                if y + 1 == w {
-                    None
-                } else {
-                    Some(i + w)
-                }
-            }
-            Direction::West => {
-                if x == 0 {
-                    None
-                } else {
-                    Some(i - 1)
-                }
-            }
-        }?;
-        Some(self.forest.heights[self.index])
-    }
+#[cfg(feature = "generate")]
+pub mod generate;
+
+#[derive(Debug)]
+struct Forest {
+    heights: Grid<u8>,
 }
 
 impl Forest {
-    fn new_from_str(input: &str) -> Self {
-        Forest {
-            width: input.lines().count(),
-            heights: input.lines().flat_map(|l| l.bytes()).collect(),
-        }
+    fn new_from_str(input: &str) -> Result<Self, ParseError> {
+        Ok(Forest {
+            heights: Grid::parse(input, "a tree height", |c| c.to_digit(10).map(|h| h as u8))?,
+        })
     }
 
-    fn line_of_sight(&self, index: usize, direction: Direction) -> impl Iterator<Item = u8> + '_ {
-        TreesIter {
-            forest: self,
-            index,
-            direction,
-        }
+    fn line_of_sight(&self, tree: Point, direction: Direction) -> impl Iterator<Item = u8> + '_ {
+        self.heights.ray(tree, direction).map(|(_, &h)| h)
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let forest = Forest::new_from_str(input);
-    forest
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let forest = Forest::new_from_str(input)?;
+    Ok(forest
         .heights
         .iter()
-        .enumerate()
-        .filter(|&(i, &h)| {
-            DIRECTIONS
+        .filter(|&(p, &h)| {
+            Direction::ALL
                 .iter()
-                .any(|&d| forest.line_of_sight(i, d).all(|other| h > other))
+                .any(|&d| forest.line_of_sight(p, d).all(|other| h > other))
         })
-        .count()
+        .count())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let forest = Forest::new_from_str(input);
-    forest
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let forest = Forest::new_from_str(input)?;
+    Ok(forest
         .heights
         .iter()
-        .enumerate()
-        .map(|(i, &h)| {
-            DIRECTIONS
+        .map(|(p, &h)| {
+            Direction::ALL
                 .iter()
                 .map(|&d| {
                     let mut count = 0;
-                    for other in forest.line_of_sight(i, d) {
+                    for other in forest.line_of_sight(p, d) {
                         count += 1;
                         if other >= h {
                             break;
@@ -120,7 +59,22 @@ pub fn part_two(input: &str) -> usize {
                 .product()
         })
         .max()
-        .unwrap_or(0)
+        .unwrap_or(0))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
 }
 
 #[cfg(test)]
@@ -129,21 +83,21 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 21);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(21));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1805);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(1805));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 8);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(8));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 444528);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(444528));
     }
 }
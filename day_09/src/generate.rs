@@ -0,0 +1,81 @@
+//! Random series of motions with known answers, for testing.
+
+use std::collections::HashSet;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated series of motions is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub motions: usize,
+    pub max_steps: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            motions: 100,
+            max_steps: 12,
+        }
+    }
+}
+
+/// How many positions the tail of a rope of `knots` visits.
+fn tail_visits(motions: &[(char, usize)], knots: usize) -> usize {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for &(direction, steps) in motions {
+        for _ in 0..steps {
+            match direction {
+                'U' => rope[0].1 += 1,
+                'D' => rope[0].1 -= 1,
+                'L' => rope[0].0 -= 1,
+                _ => rope[0].0 += 1,
+            }
+            for i in 1..knots {
+                let (dx, dy) = (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1);
+                if dx.abs() > 1 || dy.abs() > 1 {
+                    rope[i].0 += dx.signum();
+                    rope[i].1 += dy.signum();
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len()
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let motions: Vec<(char, usize)> = (0..size.motions)
+        .map(|_| {
+            (
+                ['U', 'D', 'L', 'R'][rng.gen_range(0..4)],
+                rng.gen_range(1..=size.max_steps.max(1)),
+            )
+        })
+        .collect();
+    Generated {
+        input: motions
+            .iter()
+            .map(|(direction, steps)| format!("{direction} {steps}\n"))
+            .collect(),
+        part_one: Some(tail_visits(&motions, 2)),
+        part_two: Some(tail_visits(&motions, 10)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,13 +1,26 @@
-use std::{cmp::Ordering::*, collections::BTreeSet};
+use std::{
+    cmp::Ordering::*,
+    collections::BTreeSet,
+    fmt::{self, Display, Write},
+    io::BufRead,
+};
 
+use aoc_core::{
+    io::{with_lines, ReadError},
+    parse::{final_parse, lines, IResult},
+    Observer, ParseError, Solution, SolveError,
+};
 use nom::{
     branch::alt,
-    character::complete::{char, digit1, line_ending},
-    combinator::{iterator, map, map_res, value},
-    sequence::{terminated, tuple},
-    IResult,
+    character::complete::{char, digit1},
+    combinator::{map, map_res, value},
+    error::context,
+    sequence::tuple,
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
 #[derive(Debug, Clone, Copy)]
 enum Direction {
     Up,
@@ -36,12 +49,15 @@ impl Iterator for Motion {
 fn parse_motion(input: &str) -> IResult<&str, Motion> {
     map(
         tuple((
-            alt((
-                value(Up, char('U')),
-                value(Right, char('R')),
-                value(Down, char('D')),
-                value(Left, char('L')),
-            )),
+            context(
+                "a direction (U, R, D or L)",
+                alt((
+                    value(Up, char('U')),
+                    value(Right, char('R')),
+                    value(Down, char('D')),
+                    value(Left, char('L')),
+                )),
+            ),
             char(' '),
             map_res(digit1, str::parse),
         )),
@@ -83,63 +99,263 @@ impl Position {
     }
 }
 
-fn rope_simulation(input: &str, rope: &mut [Position]) -> usize {
-    let mut motions_iterator = iterator(input, terminated(parse_motion, line_ending));
+/// Moves the rope through every motion, calling `on_motion` with the rope and the positions its
+/// tail has visited after each one.
+fn rope_simulation(
+    motions: impl IntoIterator<Item = Motion>,
+    rope: &mut [Position],
+    mut on_motion: impl FnMut(&[Position], &BTreeSet<Position>),
+) -> usize {
     let mut visited = BTreeSet::new();
-    for direction in &mut motions_iterator.flatten() {
-        let mut previous_knot = None;
-        for knot in rope.iter_mut() {
-            match previous_knot {
-                None => knot.move_direction(direction),
-                Some(previous_knot) => {
-                    if !knot.is_touching(previous_knot) {
-                        knot.move_towards(previous_knot);
+    for motion in motions {
+        for direction in motion {
+            let mut previous_knot = None;
+            for knot in rope.iter_mut() {
+                match previous_knot {
+                    None => knot.move_direction(direction),
+                    Some(previous_knot) => {
+                        if !knot.is_touching(previous_knot) {
+                            knot.move_towards(previous_knot);
+                        }
                     }
-                }
-            };
-            previous_knot = Some(knot);
-        }
-        if let Some(last_knot) = previous_knot {
-            visited.insert(*last_knot);
+                };
+                previous_knot = Some(knot);
+            }
+            if let Some(last_knot) = previous_knot {
+                visited.insert(*last_knot);
+            }
         }
+        on_motion(rope, &visited);
     }
     visited.len()
 }
 
-pub fn part_one(input: &str) -> usize {
-    rope_simulation(input, &mut [Position::default(); 2])
+/// The corners of the smallest rectangle holding every position the head moves through. The
+/// rest of the rope follows the head, so it never leaves the rectangle either.
+fn head_bounds(motions: &[Motion]) -> (Position, Position) {
+    let mut head = Position::default();
+    let (mut min, mut max) = (head, head);
+    for motion in motions {
+        for _ in 0..motion.steps {
+            head.move_direction(motion.direction);
+        }
+        min = Position {
+            x: min.x.min(head.x),
+            y: min.y.min(head.y),
+        };
+        max = Position {
+            x: max.x.max(head.x),
+            y: max.y.max(head.y),
+        };
+    }
+    (min, max)
+}
+
+/// The rope within `min` and `max` as in the puzzle, with the head as `H`, the other knots
+/// numbered, the start as `s` and the tail's visited positions as `#`. It's only drawn when
+/// formatted.
+struct Drawing<'a> {
+    rope: &'a [Position],
+    visited: &'a BTreeSet<Position>,
+    min: Position,
+    max: Position,
 }
 
-pub fn part_two(input: &str) -> usize {
-    rope_simulation(input, &mut [Position::default(); 10])
+impl Display for Drawing<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in (self.min.y..=self.max.y).rev() {
+            for x in self.min.x..=self.max.x {
+                let position = Position { x, y };
+                f.write_char(match self.rope.iter().position(|&knot| knot == position) {
+                    Some(0) => 'H',
+                    Some(_) if self.rope.len() == 2 => 'T',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap_or('?'),
+                    None if position == Position::default() => 's',
+                    None if self.visited.contains(&position) => '#',
+                    None => '.',
+                })?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+fn observed_simulation(
+    input: &str,
+    knots: usize,
+    observer: &mut dyn Observer,
+) -> Result<usize, ParseError> {
+    let motions = final_parse(input, lines(parse_motion))?;
+    let (min, max) = head_bounds(&motions);
+    Ok(rope_simulation(
+        motions,
+        &mut vec![Position::default(); knots],
+        |rope, visited| {
+            observer.frame(&Drawing {
+                rope,
+                visited,
+                min,
+                max,
+            })
+        },
+    ))
+}
+
+/// Like [`part_one`], showing `observer` the rope after each motion.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
+    observed_simulation(input, 2, observer)
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let motions = final_parse(input, lines(parse_motion))?;
+    Ok(rope_simulation(
+        motions,
+        &mut [Position::default(); 2],
+        |_, _| {},
+    ))
+}
+
+/// Like [`part_two`], showing `observer` the rope after each motion.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
+    observed_simulation(input, 10, observer)
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let motions = final_parse(input, lines(parse_motion))?;
+    Ok(rope_simulation(
+        motions,
+        &mut [Position::default(); 10],
+        |_, _| {},
+    ))
+}
+
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    with_lines(reader, |lines| {
+        rope_simulation(
+            lines.parsed(parse_motion),
+            &mut [Position::default(); 2],
+            |_, _| {},
+        )
+    })
+}
+
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<usize, ReadError> {
+    with_lines(reader, |lines| {
+        rope_simulation(
+            lines.parsed(parse_motion),
+            &mut [Position::default(); 10],
+            |_, _| {},
+        )
+    })
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_one_observed(input, observer)?)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_two_observed(input, observer)?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::parse::Expected;
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example1.txt")), 13);
+        assert_eq!(part_one(include_str!("../example1.txt")), Ok(13));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 5735);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(5735));
     }
 
     #[test]
     fn example_one_part_two() {
-        assert_eq!(part_two(include_str!("../example1.txt")), 1);
+        assert_eq!(part_two(include_str!("../example1.txt")), Ok(1));
     }
 
     #[test]
     fn example_two_part_two() {
-        assert_eq!(part_two(include_str!("../example2.txt")), 36);
+        assert_eq!(part_two(include_str!("../example2.txt")), Ok(36));
+    }
+
+    #[test]
+    fn reports_unknown_direction() {
+        assert_eq!(
+            part_one("R 4\nU 4\nX 3\n"),
+            Err(ParseError {
+                line: 3,
+                column: 1,
+                expected: Expected::Described("a direction (U, R, D or L)"),
+                found: Some('X'),
+                within: None,
+            })
+        );
+    }
+
+    #[test]
+    fn examples_from_reader() {
+        assert_eq!(
+            part_one_from_reader(&include_bytes!("../example1.txt")[..]).unwrap(),
+            13
+        );
+        assert_eq!(
+            part_two_from_reader(&include_bytes!("../example2.txt")[..]).unwrap(),
+            36
+        );
+    }
+
+    #[test]
+    fn shows_the_rope_after_each_motion() {
+        struct Frames(Vec<String>);
+
+        impl Observer for Frames {
+            fn frame(&mut self, frame: &dyn std::fmt::Display) {
+                self.0.push(frame.to_string());
+            }
+        }
+
+        let mut frames = Frames(Vec::new());
+        assert_eq!(
+            part_one_observed(include_str!("../example1.txt"), &mut frames),
+            Ok(13)
+        );
+        assert_eq!(frames.0.len(), 8);
+        assert_eq!(
+            frames.0.last().unwrap(),
+            "..##..\n...##.\n.TH##.\n....#.\ns###..\n"
+        );
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2478);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(2478));
     }
 }
@@ -0,0 +1,83 @@
+//! Random programs with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How a generated program is made up. Programs always run for at least the 240 cycles the
+/// screen needs.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// The largest amount a single `addx` adds or takes away
+    pub max_add: i32,
+    /// How likely each instruction is to be `noop`, out of 1
+    pub noops: f64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            max_add: 8,
+            noops: 0.3,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<i32, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input = String::new();
+    // the value of X during each cycle, counting from 1
+    let mut during = vec![0];
+    let mut x = 1;
+    while during.len() <= 240 {
+        if rng.gen_bool(size.noops.clamp(0.0, 1.0)) {
+            input += "noop\n";
+            during.push(x);
+        } else {
+            // steer back towards the screen so that the picture isn't blank
+            let add = match rng.gen_range(-size.max_add..=size.max_add) {
+                add if x + add < -1 => -add,
+                add if x + add > 40 => -add,
+                add => add,
+            };
+            input += &format!("addx {add}\n");
+            during.extend([x, x]);
+            x += add;
+        }
+    }
+
+    let strength = [20, 60, 100, 140, 180, 220]
+        .iter()
+        .map(|&cycle| cycle as i32 * during[cycle])
+        .sum();
+    let mut screen = String::new();
+    for row in 0..6 {
+        for column in 0..40 {
+            let sprite = during[row * 40 + column + 1];
+            screen.push(if (sprite - column as i32).abs() <= 1 {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        screen.push('\n');
+    }
+    Generated {
+        input,
+        part_one: Some(strength),
+        part_two: Some(screen),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
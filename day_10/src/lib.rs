@@ -1,14 +1,71 @@
-use std::iter::{once, zip};
+use std::{
+    io::BufRead,
+    iter::{once, zip},
+};
 
+use aoc_core::{
+    io::{with_lines, ReadError},
+    parse::{final_parse, lines, tag, IResult},
+    parse_param, ParamError, Parameters, ParseError, Pixels, Solution, SolveError,
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
-    character::complete::line_ending,
-    combinator::{iterator, map, map_res, value},
-    sequence::{preceded, terminated},
-    IResult,
+    bytes::complete::take_while1,
+    combinator::{map, map_res, value},
+    error::context,
+    sequence::preceded,
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// The cycles and screen the puzzle is set with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The first cycle whose signal strength counts in part one
+    pub first_cycle: i32,
+    /// How many cycles apart the signal strengths that count are
+    pub cycle_interval: i32,
+    /// The last cycle whose signal strength might count
+    pub last_cycle: i32,
+    pub screen_width: usize,
+    pub screen_height: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            first_cycle: 20,
+            cycle_interval: 40,
+            last_cycle: 220,
+            screen_width: 40,
+            screen_height: 6,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "first_cycle" => self.first_cycle = parse_param(value, "a cycle")?,
+            "cycle_interval" => self.cycle_interval = parse_param(value, "a number of cycles")?,
+            "last_cycle" => self.last_cycle = parse_param(value, "a cycle")?,
+            "screen_width" => self.screen_width = parse_param(value, "a number of pixels")?,
+            "screen_height" => self.screen_height = parse_param(value, "a number of pixels")?,
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "first_cycle",
+                    "cycle_interval",
+                    "last_cycle",
+                    "screen_width",
+                    "screen_height",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 enum Instruction {
     Noop,
@@ -33,19 +90,22 @@ impl InternalInstruction {
 
 impl Instruction {
     fn nom_parse(input: &str) -> IResult<&str, Instruction> {
-        alt::<_, _, nom::error::Error<&str>, _>((
-            value(Noop, tag("noop")),
-            map(
-                preceded(
-                    tag("addx "),
-                    map_res(
-                        take_while1(|c| c == '-' || ('0'..='9').contains(&c)),
-                        str::parse,
+        context(
+            "an instruction (noop or addx)",
+            alt((
+                value(Noop, tag("noop")),
+                map(
+                    preceded(
+                        tag("addx "),
+                        map_res(
+                            take_while1(|c: char| c == '-' || c.is_ascii_digit()),
+                            str::parse,
+                        ),
                     ),
+                    Addx,
                 ),
-                Addx,
-            ),
-        ))(input)
+            )),
+        )(input)
     }
 
     fn internal_instruction_iter(self) -> impl Iterator<Item = InternalInstruction> {
@@ -57,21 +117,14 @@ impl Instruction {
     }
 }
 
-fn internal_instruction(input: &str) -> Vec<InternalInstruction> {
-    let mut instructions_iterator =
-        iterator(input, terminated(Instruction::nom_parse, line_ending));
-    (&mut instructions_iterator)
-        .flat_map(|i| i.internal_instruction_iter())
-        .collect()
-}
-
 fn register_x_values(
-    internal_instruction: &[InternalInstruction],
-) -> impl Iterator<Item = i32> + '_ {
+    instructions: impl IntoIterator<Item = Instruction>,
+) -> impl Iterator<Item = i32> {
     let mut register_x = 1;
     once(register_x).chain(
-        internal_instruction
-            .iter()
+        instructions
+            .into_iter()
+            .flat_map(|i| i.internal_instruction_iter())
             .map(move |internal_instruction| {
                 internal_instruction.run(&mut register_x);
                 register_x
@@ -79,31 +132,100 @@ fn register_x_values(
     )
 }
 
-pub fn part_one(input: &str) -> i32 {
-    zip(register_x_values(&internal_instruction(input)), 1..)
-        .filter_map(|(register_x, cycle)| match cycle {
-            20 | 60 | 100 | 140 | 180 | 220 => Some(cycle * register_x),
-            _ => None,
+fn signal_strength_sum(
+    instructions: impl IntoIterator<Item = Instruction>,
+    params: &Params,
+) -> i32 {
+    zip(register_x_values(instructions), 1..)
+        .take_while(|&(_, cycle)| cycle <= params.last_cycle)
+        .filter(|&(_, cycle)| {
+            cycle >= params.first_cycle
+                && (cycle - params.first_cycle) % params.cycle_interval.max(1) == 0
         })
+        .map(|(register_x, cycle)| cycle * register_x)
         .sum()
 }
 
-pub fn part_two(input: &str) -> String {
+fn render(instructions: impl IntoIterator<Item = Instruction>, params: &Params) -> String {
+    let width = params.screen_width.max(1);
     let mut output = String::new();
-    for (x, cycle) in zip(register_x_values(&internal_instruction(input)), 0..240) {
-        let i = cycle % 40;
+    for (x, cycle) in zip(
+        register_x_values(instructions),
+        0..width * params.screen_height,
+    ) {
+        let i = (cycle % width) as i32;
         output.push(if x - 1 == i || x == i || x + 1 == i {
             '#'
         } else {
             '.'
         });
-        if i == 39 {
+        if cycle % width == width - 1 {
             output.push('\n');
         }
     }
     output
 }
 
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    part_one_with(input, &Params::default())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<i32, ParseError> {
+    let instructions = final_parse(input, lines(Instruction::nom_parse))?;
+    Ok(signal_strength_sum(instructions, params))
+}
+
+pub fn part_two(input: &str) -> Result<String, ParseError> {
+    part_two_with(input, &Params::default())
+}
+
+pub fn part_two_with(input: &str, params: &Params) -> Result<String, ParseError> {
+    let instructions = final_parse(input, lines(Instruction::nom_parse))?;
+    Ok(render(instructions, params))
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(i32, String), ParseError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<i32, ReadError> {
+    with_lines(reader, |lines| {
+        signal_strength_sum(lines.parsed(Instruction::nom_parse), &Params::default())
+    })
+}
+
+/// Like [`part_two`], but stopping once the 240 pixels of the screen have been drawn.
+pub fn part_two_from_reader(reader: impl BufRead) -> Result<String, ReadError> {
+    with_lines(reader, |lines| {
+        render(lines.parsed(Instruction::nom_parse), &Params::default())
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct Day10 {
+    pub params: Params,
+}
+
+impl Solution for Day10 {
+    type PartOne = i32;
+    type PartTwo = Pixels;
+
+    fn part_one(&self, input: &str) -> Result<i32, SolveError> {
+        Ok(part_one_with(input, &self.params)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Pixels, SolveError> {
+        let screen = part_two_with(input, &self.params)?;
+        Ok(Pixels(
+            screen
+                .lines()
+                .map(|row| row.chars().map(|c| c == '#').collect())
+                .collect(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -112,25 +234,50 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 13140);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(13140));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 16880);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(16880));
     }
 
     #[test]
     fn example_part_two() {
         assert_eq!(
-            part_two(include_str!("../example.txt")),
+            part_two(include_str!("../example.txt")).as_deref(),
+            Ok(include_str!("../example_answer_part_2.txt"))
+        );
+    }
+
+    #[test]
+    fn example_on_a_smaller_screen() {
+        let params = Params {
+            first_cycle: 1,
+            cycle_interval: 1,
+            last_cycle: 3,
+            screen_width: 4,
+            screen_height: 2,
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((1 + 2 + 3 * 16, String::from("##..\n....\n")))
+        );
+    }
+
+    #[test]
+    fn example_from_reader() {
+        let example = include_bytes!("../example.txt");
+        assert_eq!(part_one_from_reader(&example[..]).unwrap(), 13140);
+        assert_eq!(
+            part_two_from_reader(&example[..]).unwrap(),
             include_str!("../example_answer_part_2.txt")
         );
     }
 
     #[test]
     fn challenge_part_two() {
-        let s = part_two(include_str!("../challenge.txt"));
+        let s = part_two(include_str!("../challenge.txt")).unwrap();
         let lines: Vec<&[u8]> = s.lines().map(|s| s.as_bytes()).collect();
         let img = ImageBuffer::from_fn(40, 6, |x, y| {
             if lines[y as usize][x as usize] == b'#' {
@@ -0,0 +1,170 @@
+//! Random troops of monkeys with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How big a generated troop is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// Between 2 and 9, as each monkey tests a different prime
+    pub monkeys: usize,
+    pub max_items: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            monkeys: 6,
+            max_items: 4,
+        }
+    }
+}
+
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Multiply(u64),
+    Square,
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// The product of the two highest inspection counts.
+fn business(mut inspections: Vec<usize>) -> usize {
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections.iter().take(2).product()
+}
+
+/// Part one, with worry levels kept exactly. Gives up if they grow too large for a `u64`.
+fn exact_business(troop: &[Monkey]) -> Option<usize> {
+    let mut items: Vec<Vec<u64>> = troop.iter().map(|m| m.items.clone()).collect();
+    let mut inspections = vec![0; troop.len()];
+    for _ in 0..20 {
+        for (i, monkey) in troop.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                inspections[i] += 1;
+                let worry = match monkey.operation {
+                    Operation::Add(n) => item.checked_add(n)?,
+                    Operation::Multiply(n) => item.checked_mul(n)?,
+                    Operation::Square => item.checked_mul(item)?,
+                } / 3;
+                let target = if worry % monkey.divisor == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[target].push(worry);
+            }
+        }
+    }
+    Some(business(inspections))
+}
+
+/// Part two, keeping each worry level as its remainder by every monkey's divisor.
+fn residue_business(troop: &[Monkey]) -> usize {
+    let residues = |worry: u64| -> Vec<u64> { troop.iter().map(|m| worry % m.divisor).collect() };
+    let mut items: Vec<Vec<Vec<u64>>> = troop
+        .iter()
+        .map(|m| m.items.iter().map(|&i| residues(i)).collect())
+        .collect();
+    let mut inspections = vec![0; troop.len()];
+    for _ in 0..10000 {
+        for (i, monkey) in troop.iter().enumerate() {
+            for mut item in std::mem::take(&mut items[i]) {
+                inspections[i] += 1;
+                for (r, other) in item.iter_mut().zip(troop) {
+                    *r = match monkey.operation {
+                        Operation::Add(n) => *r + n,
+                        Operation::Multiply(n) => *r * n,
+                        Operation::Square => *r * *r,
+                    } % other.divisor;
+                }
+                let target = if item[i] == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[target].push(item);
+            }
+        }
+    }
+    business(inspections)
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let count = size.monkeys.clamp(2, PRIMES.len());
+    let mut divisors = PRIMES;
+    divisors.shuffle(&mut rng);
+    let troop: Vec<Monkey> = (0..count)
+        .map(|i| {
+            let others: Vec<usize> = (0..count).filter(|&j| j != i).collect();
+            Monkey {
+                items: (0..rng.gen_range(1..=size.max_items.max(1)))
+                    .map(|_| rng.gen_range(50..=99))
+                    .collect(),
+                operation: match rng.gen_range(0..10) {
+                    0 => Operation::Square,
+                    1..=4 => Operation::Add(rng.gen_range(1..=8)),
+                    _ => Operation::Multiply(rng.gen_range(2..=19)),
+                },
+                divisor: divisors[i],
+                if_true: *others.choose(&mut rng).unwrap(),
+                if_false: *others.choose(&mut rng).unwrap(),
+            }
+        })
+        .collect();
+
+    let input = troop
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items: Vec<String> = m.items.iter().map(u64::to_string).collect();
+            let operation = match m.operation {
+                Operation::Add(n) => format!("+ {n}"),
+                Operation::Multiply(n) => format!("* {n}"),
+                Operation::Square => String::from("* old"),
+            };
+            format!(
+                "Monkey {i}:\n  Starting items: {}\n  Operation: new = old {operation}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n",
+                items.join(", "),
+                m.divisor,
+                m.if_true,
+                m.if_false
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated {
+        input,
+        part_one: exact_business(&troop),
+        part_two: Some(residue_business(&troop)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            if generated.part_one.is_some() {
+                assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            }
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
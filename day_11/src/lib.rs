@@ -1,15 +1,66 @@
 use std::{collections::VecDeque, mem::take};
 
+use aoc_core::{
+    parse::{final_parse, list, tag, IResult},
+    parse_param, Answer, Observer, ParamError, Parameters, Solution, SolveError, Step, Structured,
+};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{char, digit1, line_ending},
     combinator::{map, map_res, value},
     multi::separated_list1,
-    sequence::tuple,
-    IResult,
+    sequence::{preceded, tuple},
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// How long the monkeys play for and how much relief there is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The rounds watched in part one
+    pub rounds: u32,
+    /// What worry is divided by after each inspection in part one
+    pub relief: u64,
+    /// The rounds watched in part two, with no relief
+    pub rounds_without_relief: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            rounds: 20,
+            relief: 3,
+            rounds_without_relief: 10000,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "rounds" => self.rounds = parse_param(value, "a number of rounds")?,
+            "relief" => {
+                self.relief = parse_param(value, "a positive divisor")?;
+                if self.relief == 0 {
+                    return Err(ParamError::Invalid("a positive divisor"));
+                }
+            }
+            "rounds_without_relief" => {
+                self.rounds_without_relief = parse_param(value, "a number of rounds")?
+            }
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "rounds",
+                    "relief",
+                    "rounds_without_relief",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Operand {
     Constant(u64),
@@ -42,20 +93,20 @@ impl Monkey {
             tuple((
                 tag("Monkey "),
                 digit1,
-                tag(":\n  Starting items: "),
+                tuple((char(':'), line_ending, tag("  Starting items: "))),
                 separated_list1(tag(", "), map_res(digit1, str::parse)),
-                tag("\n  Operation: new = old "),
+                preceded(line_ending, tag("  Operation: new = old ")),
                 alt((value(Mul, char('*')), value(Add, char('+')))),
                 char(' '),
                 alt((
                     value(Old, tag("old")),
                     map(map_res(digit1, str::parse), Constant),
                 )),
-                tag("\n  Test: divisible by "),
+                preceded(line_ending, tag("  Test: divisible by ")),
                 map_res(digit1, str::parse),
-                tag("\n    If true: throw to monkey "),
+                preceded(line_ending, tag("    If true: throw to monkey ")),
                 map_res(digit1, str::parse),
-                tag("\n    If false: throw to monkey "),
+                preceded(line_ending, tag("    If false: throw to monkey ")),
                 map_res(digit1, str::parse),
             )),
             |(
@@ -93,17 +144,29 @@ struct Monkeys {
 }
 
 impl Monkeys {
-    fn parse_all(input: &str) -> Self {
-        let troop = separated_list1(tuple((line_ending, line_ending)), Monkey::parse_nom)(input)
-            .unwrap()
-            .1;
-        Self {
+    fn parse_all(input: &str) -> Result<Self, SolveError> {
+        let troop = final_parse(
+            input,
+            list(Monkey::parse_nom, tuple((line_ending, line_ending))),
+        )?;
+        if troop.iter().any(|m| {
+            m.test_divisor == 0
+                || m.test_true_monkey >= troop.len()
+                || m.test_false_monkey >= troop.len()
+        }) {
+            return Err(SolveError::Invalid(
+                "Monkeys must test a non-zero divisor and throw to monkeys in the troop",
+            ));
+        }
+        Ok(Self {
             common_divisor: troop.iter().map(|m| m.test_divisor).product(),
             troop,
-        }
+        })
     }
 
-    fn round(&mut self, divider: u64) {
+    /// Plays a round, noting each item thrown in `throws` as the monkey it came from, the
+    /// monkey it went to, and its worry level.
+    fn round(&mut self, divider: u64, throws: &mut Vec<(usize, usize, u64)>) {
         for i in 0..self.troop.len() {
             let monkey = &mut self.troop[i];
             let mut items = take(&mut monkey.items);
@@ -122,59 +185,284 @@ impl Monkeys {
                     (Mul, Old) => item * item,
                     (Add, Constant(c)) => item + c,
                     (Add, Old) => item + item,
-                } / divider
-                    % self.common_divisor;
-                self.troop[if item % test_divisor == 0 {
+                } / divider;
+                // the remainder by the common divisor passes every test the same way, but only
+                // while nothing is divided
+                if divider == 1 {
+                    item %= self.common_divisor;
+                }
+                let to = if item % test_divisor == 0 {
                     test_true_monkey
                 } else {
                     test_false_monkey
-                }]
-                .items
-                .push_back(item);
+                };
+                throws.push((i, to, item));
+                self.troop[to].items.push_back(item);
             }
         }
     }
 }
 
-fn monkey_business(input: &str, rounds: u16, divisor: u64) -> usize {
-    let mut monkeys = Monkeys::parse_all(input);
-    for _ in 0..rounds {
-        monkeys.round(divisor);
+/// Describes round number `round`, in which `throws` were made.
+fn explain_round(round: u32, throws: &[(usize, usize, u64)], monkeys: &Monkeys) -> Step {
+    let thrown: Vec<_> = throws
+        .iter()
+        .map(|(from, to, worry)| format!("{from} → {to} ({worry})"))
+        .collect();
+    let inspections: Vec<_> = monkeys.troop.iter().map(|m| m.inspections).collect();
+    Step::new(format!(
+        "Round {round}: {}, after which the monkeys have inspected {} items",
+        thrown.join(", "),
+        inspections
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .with("round", round)
+    .with_structured(
+        "throws",
+        Structured::List(
+            throws
+                .iter()
+                .map(|&(from, to, worry)| {
+                    Structured::List(vec![from.structured(), to.structured(), worry.structured()])
+                })
+                .collect(),
+        ),
+    )
+    .with_structured(
+        "inspections",
+        Structured::List(inspections.iter().map(|n| n.structured()).collect()),
+    )
+}
+
+/// Whether a round of part two is explained: like the puzzle, only the first, the 20th, every
+/// 1000th and the last are, rather than all 10 000.
+fn is_milestone(round: u32, rounds: u32) -> bool {
+    round == 1 || round == 20 || round.is_multiple_of(1000) || round == rounds
+}
+
+/// Plays `rounds` rounds, explaining those for which `explained` is true.
+fn monkey_business(
+    input: &str,
+    rounds: u32,
+    divisor: u64,
+    explained: impl Fn(u32) -> bool,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    let mut monkeys = Monkeys::parse_all(input)?;
+    let mut throws = Vec::new();
+    for round in 1..=rounds {
+        throws.clear();
+        monkeys.round(divisor, &mut throws);
+        if explained(round) {
+            observer.explain(&|| explain_round(round, &throws, &monkeys));
+        }
     }
     let mut inspections: Vec<_> = monkeys.troop.iter().map(|m| m.inspections).collect();
     inspections.sort();
-    inspections.iter().rev().take(2).product()
+    Ok(inspections.iter().rev().take(2).product())
+}
+
+pub fn part_one(input: &str) -> Result<usize, SolveError> {
+    part_one_with(input, &Params::default())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    part_one_observed_with(input, params, &mut ())
+}
+
+/// Like [`part_one`], explaining each round's throws to `observer`.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, SolveError> {
+    part_one_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_one_observed`], with the game set up by `params`.
+pub fn part_one_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    monkey_business(
+        input,
+        params.rounds,
+        params.relief.max(1),
+        |_| true,
+        observer,
+    )
+}
+
+pub fn part_two(input: &str) -> Result<usize, SolveError> {
+    part_two_with(input, &Params::default())
 }
 
-pub fn part_one(input: &str) -> usize {
-    monkey_business(input, 20, 3)
+pub fn part_two_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    part_two_observed_with(input, params, &mut ())
 }
 
-pub fn part_two(input: &str) -> usize {
-    monkey_business(input, 10000, 1)
+/// Like [`part_two`], explaining the throws of the rounds the puzzle shows to `observer`.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, SolveError> {
+    part_two_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_two_observed`], with the game set up by `params`.
+pub fn part_two_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    let rounds = params.rounds_without_relief;
+    monkey_business(
+        input,
+        rounds,
+        1,
+        |round| is_milestone(round, rounds),
+        observer,
+    )
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(usize, usize), SolveError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day11 {
+    pub params: Params,
+}
+
+impl Solution for Day11 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        part_one_with(input, &self.params)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        part_two_with(input, &self.params)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        part_one_observed_with(input, &self.params, observer)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        part_two_observed_with(input, &self.params, observer)
+    }
+
+    fn explains(&self, _part: u8) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::{parse::Expected, ParseError};
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 10605);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(10605));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 100345);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(100345));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 2713310158);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(2713310158));
+    }
+
+    #[test]
+    fn example_played_for_one_round() {
+        let params = Params {
+            rounds: 1,
+            rounds_without_relief: 1,
+            ..Params::default()
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((5 * 4, 4 * 6))
+        );
+    }
+
+    #[test]
+    fn explains_only_milestones_of_part_two() {
+        let mut explanation = aoc_core::Explanation::default();
+        part_two_observed(include_str!("../example.txt"), &mut explanation).unwrap();
+        let rounds: Vec<_> = explanation
+            .0
+            .iter()
+            .map(|step| step.description.split(':').next().unwrap())
+            .collect();
+        assert_eq!(rounds.len(), 12);
+        assert_eq!(rounds[..3], ["Round 1", "Round 20", "Round 1000"]);
+        assert_eq!(rounds[11], "Round 10000");
+    }
+
+    #[test]
+    fn explains_each_round() {
+        let mut explanation = aoc_core::Explanation::default();
+        part_one_observed(include_str!("../example.txt"), &mut explanation).unwrap();
+        assert_eq!(explanation.0.len(), 20);
+        let first = &explanation.0[0];
+        assert!(first
+            .description
+            .starts_with("Round 1: 0 → 3 (500), 0 → 3 (620), 1 → 0 (20), "));
+        assert!(first
+            .description
+            .ends_with("after which the monkeys have inspected 2, 4, 3, 5 items"));
+        let ("throws", Structured::List(throws)) = &first.values[1] else {
+            panic!("the second value should be the list of throws");
+        };
+        assert_eq!(throws.len(), 14);
+        assert_eq!(
+            throws[0],
+            Structured::List(vec![
+                Structured::Integer(0),
+                Structured::Integer(3),
+                Structured::Integer(500)
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_no_relief_divisor() {
+        assert_eq!(
+            Params::default().set("relief", "0"),
+            Err(ParamError::Invalid("a positive divisor"))
+        );
+    }
+
+    #[test]
+    fn reports_misspelt_line() {
+        let input = include_str!("../example.txt").replacen("If false", "If flase", 1);
+        assert_eq!(
+            part_one(&input),
+            Err(SolveError::Parse(ParseError {
+                line: 6,
+                column: 1,
+                expected: Expected::Tag("    If false: throw to monkey "),
+                found: Some(' '),
+                within: None,
+            }))
+        );
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 28537348205);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(28537348205));
     }
 }
@@ -0,0 +1,123 @@
+//! Random heightmaps with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated heightmap is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+    /// How far a square's height strays from a smooth slope, which makes cliffs more likely
+    pub roughness: i32,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            width: 20,
+            height: 10,
+            roughness: 2,
+        }
+    }
+}
+
+/// The fewest steps from every square to the end, found by relaxing every square's distance
+/// until none change. `None` marks squares that can't reach the end.
+fn steps_to_end(heights: &[Vec<u8>], end: (usize, usize)) -> Vec<Vec<Option<usize>>> {
+    let (width, height) = (heights[0].len(), heights.len());
+    let mut steps = vec![vec![None; width]; height];
+    steps[end.1][end.0] = Some(0);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for y in 0..height {
+            for x in 0..width {
+                let neighbours = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbours {
+                    let Some(&Some(next)) = steps.get(ny).and_then(|r: &Vec<_>| r.get(nx)) else {
+                        continue;
+                    };
+                    if heights[ny][nx] <= heights[y][x] + 1
+                        && steps[y][x].is_none_or(|s| next + 1 < s)
+                    {
+                        steps[y][x] = Some(next + 1);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+    steps
+}
+
+/// A heightmap sloping up from the start, in the top left quarter, to the end, in the bottom
+/// right quarter. Rough maps may have no way up at all.
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (width, height) = (size.width.max(2), size.height.max(2));
+    let heights: Vec<Vec<u8>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let slope = ((x + y) * 26 / (width + height)) as i32;
+                    let noise = rng.gen_range(-size.roughness..=size.roughness);
+                    (slope + noise).clamp(0, 25) as u8
+                })
+                .collect()
+        })
+        .collect();
+    let start = (rng.gen_range(0..width / 2), rng.gen_range(0..height / 2));
+    let end = (
+        rng.gen_range(width / 2..width),
+        rng.gen_range(height / 2..height),
+    );
+    let mut heights = heights;
+    heights[start.1][start.0] = 0;
+    heights[end.1][end.0] = 25;
+
+    let steps = steps_to_end(&heights, end);
+    let input = heights
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, &h)| match (x, y) {
+                    p if p == start => 'S',
+                    p if p == end => 'E',
+                    _ => char::from(b'a' + h),
+                })
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect();
+    Generated {
+        input,
+        part_one: steps[start.1][start.0],
+        part_two: (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| heights[y][x] == 0)
+            .filter_map(|(x, y)| steps[y][x])
+            .min(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,98 +1,82 @@
-use std::{collections::BTreeSet, mem::take};
+use aoc_core::{Solution, SolveError};
+use grid::{Grid, Point};
+
+#[cfg(feature = "generate")]
+pub mod generate;
 
 #[derive(Debug)]
 struct HeightMap {
-    heights: Vec<Vec<u8>>,
-    start: (usize, usize),
-    end: (usize, usize),
+    heights: Grid<u8>,
+    start: Point,
+    end: Point,
 }
 
 impl TryFrom<&str> for HeightMap {
-    type Error = &'static str;
+    type Error = SolveError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let mut start = None;
-        let mut end = None;
-        let heights = s
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.bytes()
-                    .enumerate()
-                    .map(|(x, b)| match b {
-                        b'S' => {
-                            start = Some((x, y));
-                            0
-                        }
-                        b'E' => {
-                            end = Some((x, y));
-                            25
-                        }
-                        _ => b - b'a',
-                    })
-                    .collect()
-            })
-            .collect();
+        let squares = Grid::parse(s, "a height (a to z), S or E", |c| {
+            matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+        })?;
         Ok(Self {
-            start: start.ok_or("Couldn't find Start")?,
-            end: end.ok_or("Couldn't find End")?,
-            heights,
+            start: squares
+                .position(|&c| c == 'S')
+                .ok_or("Couldn't find Start")?,
+            end: squares.position(|&c| c == 'E').ok_or("Couldn't find End")?,
+            heights: squares.map(|&c| match c {
+                'S' => 0,
+                'E' => 25,
+                _ => c as u8 - b'a',
+            }),
         })
     }
 }
 
 fn traverse_backwards(
     height_map: &HeightMap,
-    reached_start: impl Fn(usize, usize, u8) -> bool,
-) -> Result<usize, &'static str> {
-    let mut visited: BTreeSet<(usize, usize)> = [height_map.end].iter().cloned().collect();
-    let mut recently_visited: Vec<(usize, usize)> = visited.iter().cloned().collect();
-    let mut steps = 0;
-    loop {
-        steps += 1;
-        if recently_visited.is_empty() {
-            return Err("No where left to walk");
-        }
-        for &(x, y) in take(&mut recently_visited).iter() {
-            if let Some(&height) = height_map.heights.get(y).and_then(|row| row.get(x)) {
-                for &neighbour in [
-                    (x, y.wrapping_sub(1)),
-                    (x + 1, y),
-                    (x, y + 1),
-                    (x.wrapping_sub(1), y),
-                ]
-                .iter()
-                {
-                    if let Some(&neighbour_height) = height_map
-                        .heights
-                        .get(neighbour.1)
-                        .and_then(|row| row.get(neighbour.0))
-                    {
-                        if neighbour_height + 1 >= height && visited.insert(neighbour) {
-                            recently_visited.push(neighbour);
-                            if reached_start(neighbour.0, neighbour.1, neighbour_height) {
-                                return Ok(steps);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    reached_start: impl Fn(Point, u8) -> bool,
+) -> Result<usize, SolveError> {
+    search::bfs(
+        height_map.end,
+        |&square| {
+            let height = height_map.heights[square];
+            height_map
+                .heights
+                .neighbours_4(square)
+                .filter(move |&(_, &neighbour_height)| neighbour_height + 1 >= height)
+                .map(|(neighbour, _)| neighbour)
+        },
+        |&square| reached_start(square, height_map.heights[square]),
+    )
+    .map(|path| path.cost)
+    .ok_or(SolveError::NoAnswer("No where left to walk"))
 }
 
-pub fn part_one(input: &str) -> Result<usize, &'static str> {
+pub fn part_one(input: &str) -> Result<usize, SolveError> {
     let height_map = HeightMap::try_from(input)?;
 
-    traverse_backwards(&height_map, |x, y, _| {
-        x == height_map.start.0 && y == height_map.start.1
-    })
+    traverse_backwards(&height_map, |square, _| square == height_map.start)
 }
 
-pub fn part_two(input: &str) -> Result<usize, &'static str> {
+pub fn part_two(input: &str) -> Result<usize, SolveError> {
     let height_map = HeightMap::try_from(input)?;
 
-    traverse_backwards(&height_map, |_, _, height| height == 0)
+    traverse_backwards(&height_map, |_, height| height == 0)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
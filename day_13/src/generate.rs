@@ -0,0 +1,160 @@
+//! Random pairs of packets with known answers, for testing.
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated list of packets is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub pairs: usize,
+    /// How deeply lists can nest inside a packet
+    pub max_depth: usize,
+    pub max_length: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            pairs: 30,
+            max_depth: 4,
+            max_length: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+    Integer(u8),
+    List(Vec<Value>),
+}
+
+impl Value {
+    fn random(rng: &mut StdRng, depth: usize, size: &Size) -> Value {
+        if depth < size.max_depth && rng.gen_bool(0.4) {
+            Value::List(
+                (0..rng.gen_range(0..=size.max_length))
+                    .map(|_| Value::random(rng, depth + 1, size))
+                    .collect(),
+            )
+        } else {
+            Value::Integer(rng.gen_range(0..=10))
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            Value::Integer(n) => *out += &n.to_string(),
+            Value::List(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write(out);
+                }
+                out.push(']');
+            }
+        }
+    }
+}
+
+/// Compares packets by following the puzzle's rules one at a time: `Some(true)` if the left is
+/// in the right order, `Some(false)` if not, and `None` if the packets can't be told apart.
+fn in_order(left: &Value, right: &Value) -> Option<bool> {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => (l != r).then_some(l < r),
+        (Value::List(l), Value::List(r)) => {
+            for i in 0.. {
+                match (l.get(i), r.get(i)) {
+                    (None, None) => return None,
+                    (None, Some(_)) => return Some(true),
+                    (Some(_), None) => return Some(false),
+                    (Some(a), Some(b)) => {
+                        if let Some(ordered) = in_order(a, b) {
+                            return Some(ordered);
+                        }
+                    }
+                }
+            }
+            unreachable!()
+        }
+        (Value::Integer(_), Value::List(_)) => in_order(&Value::List(vec![left.clone()]), right),
+        (Value::List(_), Value::Integer(_)) => in_order(left, &Value::List(vec![right.clone()])),
+    }
+}
+
+fn ordering(left: &Value, right: &Value) -> Ordering {
+    match in_order(left, right) {
+        Some(true) => Ordering::Less,
+        Some(false) => Ordering::Greater,
+        None => Ordering::Equal,
+    }
+}
+
+/// Pairs of packets that are never equal to each other or to a divider packet, so that every
+/// packet has one place in the sorted list.
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let dividers = [2, 6].map(|n| Value::List(vec![Value::List(vec![Value::Integer(n)])]));
+    let mut packets: Vec<Value> = Vec::new();
+    while packets.len() < size.pairs * 2 {
+        let packet = Value::List(
+            (0..rng.gen_range(0..=size.max_length))
+                .map(|_| Value::random(&mut rng, 1, size))
+                .collect(),
+        );
+        let clashes = dividers.iter().any(|d| ordering(&packet, d).is_eq())
+            || (packets.len() % 2 == 1 && ordering(&packet, &packets[packets.len() - 1]).is_eq());
+        if !clashes {
+            packets.push(packet);
+        }
+    }
+
+    let mut input = String::new();
+    for (i, packet) in packets.iter().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            input.push('\n');
+        }
+        packet.write(&mut input);
+        input.push('\n');
+    }
+    let ordered_pairs = packets
+        .chunks(2)
+        .zip(1..)
+        .filter(|(pair, _)| in_order(&pair[0], &pair[1]) == Some(true))
+        .map(|(_, i)| i)
+        .sum();
+    let position = |divider: &Value| {
+        1 + packets
+            .iter()
+            .chain(&dividers)
+            .filter(|p| in_order(p, divider) == Some(true))
+            .count()
+    };
+    Generated {
+        input,
+        part_one: Some(ordered_pairs),
+        part_two: Some(position(&dividers[0]) * position(&dividers[1])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
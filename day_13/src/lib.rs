@@ -1,15 +1,27 @@
-use std::iter::zip;
+#![no_std]
 
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+use core::iter::zip;
+
+use aoc_core::{
+    parse::{final_parse, list, IResult},
+    ParseError, Solution, SolveError,
+};
+use core::cmp::Ordering::*;
 use nom::{
     branch::alt,
     bytes::complete::take_while_m_n,
     character::complete::{char, digit1, newline},
-    combinator::{iterator, map, map_res, ParserIterator},
+    combinator::{map, map_res},
+    error::context,
     multi::separated_list0,
-    sequence::{delimited, terminated, tuple},
-    IResult,
+    sequence::{delimited, tuple},
 };
-use std::cmp::Ordering::*;
+
+#[cfg(feature = "generate")]
+pub mod generate;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Packet {
@@ -34,31 +46,28 @@ impl Packet {
     }
 
     fn parse(input: &str) -> IResult<&str, Self> {
-        alt((Self::parse_integer, Self::parse_list))(input)
+        context(
+            "an integer or a list",
+            alt((Self::parse_integer, Self::parse_list)),
+        )(input)
     }
 
-    fn parse_all_iterator<'a>(
-        input: &'a str,
-    ) -> ParserIterator<
-        &'a str,
-        nom::error::Error<&'a str>,
-        impl FnMut(&'a str) -> IResult<&'a str, Self>,
-    > {
-        iterator(
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        final_parse(
             input,
-            terminated(Self::parse, take_while_m_n(1, 2, |c| c == '\n')),
+            list(Self::parse, take_while_m_n(1, 2, |c| c == '\n')),
         )
     }
 }
 
 impl PartialOrd for Packet {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for Packet {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         match (self, other) {
             (Self::Integer(s), Self::Integer(o)) => s.cmp(o),
             (Self::List(s), Self::List(o)) => zip(s.iter(), o.iter())
@@ -88,16 +97,10 @@ impl PacketPair {
         )(input)
     }
 
-    fn parser_iterator<'a>(
-        input: &'a str,
-    ) -> ParserIterator<
-        &'a str,
-        nom::error::Error<&'a str>,
-        impl FnMut(&'a str) -> IResult<&'a str, Self>,
-    > {
-        iterator(
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        final_parse(
             input,
-            terminated(Self::parse, take_while_m_n(1, 2, |c| c == '\n')),
+            list(Self::parse, take_while_m_n(1, 2, |c| c == '\n')),
         )
     }
 
@@ -106,29 +109,44 @@ impl PacketPair {
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    zip(1.., &mut PacketPair::parser_iterator(input))
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    Ok(zip(1.., PacketPair::parse_all(input)?)
         .filter_map(|(i, pair)| pair.is_ordered().then_some(i))
-        .sum()
+        .sum())
 }
 
-pub fn part_two(input: &str) -> usize {
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
     let divider_a = Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]);
     let divider_b = Packet::List(vec![Packet::List(vec![Packet::Integer(6)])]);
 
-    let mut packets: Vec<Packet> = Packet::parse_all_iterator(input).collect();
+    let mut packets = Packet::parse_all(input)?;
     packets.push(divider_a.clone());
     packets.push(divider_b.clone());
 
     packets.sort_unstable();
-    packets
+    Ok(packets
         .binary_search(&divider_a)
         .map(|i| i + 1)
         .unwrap_or(0)
         * packets
             .binary_search(&divider_b)
             .map(|i| i + 1)
-            .unwrap_or(0)
+            .unwrap_or(0))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
 }
 
 #[cfg(test)]
@@ -137,21 +155,21 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 13);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(13));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 5882);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(5882));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 140);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(140));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 24948);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(24948));
     }
 }
@@ -0,0 +1,136 @@
+//! Random scans of rock with known answers, for testing.
+
+use std::collections::HashSet;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated scan is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub paths: usize,
+    /// The most lines in a single path of rock
+    pub max_lines: usize,
+    /// How far below the source of the sand the rock reaches
+    pub depth: i64,
+    /// Whether paths can also run at 45 degrees, which real scans never do
+    pub diagonals: bool,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            paths: 8,
+            max_lines: 4,
+            depth: 20,
+            diagonals: false,
+        }
+    }
+}
+
+/// Part one, dropping each unit of sand through a set of blocked tiles until one falls past
+/// the lowest rock, or the sand is trapped up to the source.
+fn sand_before_abyss(rocks: &HashSet<(i64, i64)>) -> usize {
+    let lowest = rocks.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    let mut blocked = rocks.clone();
+    let mut count = 0;
+    loop {
+        let (mut x, mut y) = (500, 0);
+        loop {
+            if y > lowest {
+                return count;
+            }
+            match [x, x - 1, x + 1]
+                .into_iter()
+                .find(|&nx| !blocked.contains(&(nx, y + 1)))
+            {
+                Some(nx) => (x, y) = (nx, y + 1),
+                None => break,
+            }
+        }
+        blocked.insert((x, y));
+        count += 1;
+        if (x, y) == (500, 0) {
+            return count;
+        }
+    }
+}
+
+/// Part two, counting rather than dropping the sand: a tile above the floor fills if it isn't
+/// rock and sand can reach it from one of the three tiles above.
+fn sand_until_blocked(rocks: &HashSet<(i64, i64)>) -> usize {
+    let floor = rocks.iter().map(|&(_, y)| y).max().unwrap_or(0) + 2;
+    let mut row = HashSet::from([500]);
+    let mut count = 1;
+    for y in 1..floor {
+        row = row
+            .iter()
+            .flat_map(|&x| [x - 1, x, x + 1])
+            .filter(|&x| !rocks.contains(&(x, y)))
+            .collect();
+        count += row.len();
+    }
+    count
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let depth = size.depth.max(2);
+    let mut input = String::new();
+    let mut rocks = HashSet::new();
+    for _ in 0..size.paths.max(1) {
+        let mut corner = (
+            rng.gen_range(500 - depth..=500 + depth),
+            rng.gen_range(2..=depth),
+        );
+        let mut corners = vec![corner];
+        rocks.insert(corner);
+        for _ in 0..rng.gen_range(1..=size.max_lines.max(1)) {
+            let length = rng.gen_range(1..=depth / 2 + 1);
+            let sideways = length * if rng.gen_bool(0.5) { 1 } else { -1 };
+            // lines going down stop at the lowest rock
+            let down = length.min(depth - corner.1);
+            let (dx, dy) = match rng.gen_range(0..if size.diagonals { 3 } else { 2 }) {
+                0 => (sideways, 0),
+                1 => (0, down),
+                _ => (sideways.signum() * down, down),
+            };
+            let steps = dx.abs().max(dy);
+            if steps == 0 {
+                continue;
+            }
+            let next = (corner.0 + dx, corner.1 + dy);
+            for step in 0..=steps {
+                rocks.insert((corner.0 + dx.signum() * step, corner.1 + dy.signum() * step));
+            }
+            corner = next;
+            corners.push(corner);
+        }
+        let corners: Vec<String> = corners.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        input += &corners.join(" -> ");
+        input.push('\n');
+    }
+    Generated {
+        input,
+        part_one: Some(sand_before_abyss(&rocks)),
+        part_two: Some(sand_until_blocked(&rocks)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let size = Size {
+                diagonals: seed % 2 == 0,
+                ..Size::default()
+            };
+            let generated = generate(seed, &size);
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
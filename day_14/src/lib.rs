@@ -1,254 +1,535 @@
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    Observer, ParamError, Parameters, ParseError, Solution, SolveError, Stepper,
+};
+use grid::{Direction, Point, SparseGrid};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{char, digit1, newline},
-    combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    character::complete::{char, digit1},
+    combinator::{map, map_res, verify},
+    error::context,
+    sequence::{preceded, separated_pair},
 };
-use std::{cmp::Ordering::*, collections::BTreeSet};
+use std::{fmt::Display, iter::successors};
+
+#[cfg(feature = "generate")]
+pub mod generate;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Coordinate {
-    x: u16,
-    y: u16,
+fn parse_coordinate(input: &str) -> IResult<&str, Point> {
+    map(
+        separated_pair(
+            map_res(digit1, str::parse),
+            char(','),
+            map_res(digit1, str::parse),
+        ),
+        |(x, y)| Point { x, y },
+    )(input)
 }
 
-impl Coordinate {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            separated_pair(
-                map_res(digit1, str::parse),
-                char(','),
-                map_res(digit1, str::parse),
-            ),
-            |(x, y)| Self { x, y },
-        )(input)
-    }
-
-    fn iter_straight_line_to_other(self, other: Self) -> impl Iterator<Item = Self> {
-        (0..).map_while(
-            move |i| match (self.x.cmp(&other.x), self.y.cmp(&other.y)) {
-                (Less, Less) => None,
-                (Less, Equal) => {
-                    if self.x + i <= other.x {
-                        Some(Self {
-                            x: self.x + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Less, Greater) => None,
-                (Equal, Less) => {
-                    if self.y + i <= other.y {
-                        Some(Self {
-                            y: self.y + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Equal, Equal) => (i == 0).then_some(self),
-                (Equal, Greater) => {
-                    if other.y + i <= self.y {
-                        Some(Self {
-                            y: other.y + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Greater, Less) => None,
-                (Greater, Equal) => {
-                    if other.x + i <= self.x {
-                        Some(Self {
-                            x: other.x + i,
-                            ..self
-                        })
-                    } else {
-                        None
-                    }
-                }
-                (Greater, Greater) => None,
-            },
-        )
+/// Whether `start` and `end` are in a horizontal, vertical or 45 degree diagonal line.
+fn is_straight(start: Point, end: Point) -> bool {
+    let (dx, dy) = ((end.x - start.x).abs(), (end.y - start.y).abs());
+    dx == 0 || dy == 0 || dx == dy
+}
+
+/// The points in a line from `start` to `end` inclusive, which must be [straight](is_straight).
+fn straight_line(start: Point, end: Point) -> impl Iterator<Item = Point> {
+    let step = Point {
+        x: (end.x - start.x).signum(),
+        y: (end.y - start.y).signum(),
+    };
+    successors(Some(start), move |&p| (p != end).then_some(p + step))
+}
+
+/// Where the sand pours in from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub sand_source: Point,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            sand_source: Point { x: 500, y: 0 },
+        }
     }
 }
 
-const SAND_SOURCE: Coordinate = Coordinate { x: 500, y: 0 };
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "sand_source" => {
+                self.sand_source = final_parse(value.trim(), parse_coordinate)
+                    .map_err(|_| ParamError::Invalid("a point like 500,0"))?
+            }
+            _ => return Err(ParamError::Unknown(&["sand_source"])),
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug)]
 struct RockStructure {
-    coordinates: Vec<Coordinate>,
+    coordinates: Vec<Point>,
 }
 
 impl RockStructure {
     fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            separated_list1(tag(" -> "), Coordinate::parse),
-            |coordinates| Self { coordinates },
-        )(input)
+        let (mut input, first) = parse_coordinate(input)?;
+        let mut coordinates = vec![first];
+        loop {
+            let previous = coordinates[coordinates.len() - 1];
+            let mut next = preceded(
+                tag(" -> "),
+                context(
+                    "a point in a straight line from the last one",
+                    verify(parse_coordinate, |&c| is_straight(previous, c)),
+                ),
+            );
+            match next(input) {
+                Ok((rest, c)) => {
+                    coordinates.push(c);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) if !input.starts_with(" -> ") => {
+                    return Ok((input, Self { coordinates }))
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
-    fn iter_coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+    fn iter_coordinates(&self) -> impl Iterator<Item = Point> + '_ {
         self.coordinates
             .iter()
             .scan(None, |state, &coord| {
                 let previous_state = state.unwrap_or(coord);
                 *state = Some(coord);
-                Some(coord.iter_straight_line_to_other(previous_state))
+                Some(straight_line(previous_state, coord))
             })
             .flatten()
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Material {
+    Rock,
+    Sand,
+}
+
 #[derive(Debug)]
 struct Cave {
-    rocks: BTreeSet<Coordinate>,
-    sand: BTreeSet<Coordinate>,
-    max_y: u16,
-    min_x: u16,
-    max_x: u16,
+    materials: SparseGrid<Material>,
+    source: Point,
+    max_y: i64,
+    min_x: i64,
+    max_x: i64,
 }
 
 impl Cave {
-    fn new(rocks: BTreeSet<Coordinate>) -> Self {
+    fn new(materials: SparseGrid<Material>, source: Point) -> Self {
+        let (min, max) = materials.bounds().unwrap_or((source, source));
         Self {
-            max_y: rocks.iter().map(|c| c.y).max().unwrap_or(SAND_SOURCE.y),
-            min_x: rocks.iter().map(|c| c.x - 1).min().unwrap_or(SAND_SOURCE.x),
-            max_x: rocks.iter().map(|c| c.x + 1).max().unwrap_or(SAND_SOURCE.x),
-            rocks,
-            sand: BTreeSet::new(),
+            max_y: max.y,
+            min_x: min.x - 1,
+            max_x: max.x + 1,
+            materials,
+            source,
         }
     }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (rest, rock_structures) = separated_list1(newline, RockStructure::parse)(input)?;
+    fn parse(input: &str, source: Point) -> Result<Self, ParseError> {
+        let rock_structures = final_parse(input, lines(RockStructure::parse))?;
         let rocks = rock_structures
             .iter()
             .flat_map(|rs| rs.iter_coordinates())
+            .map(|c| (c, Material::Rock))
             .collect();
-        Ok((rest, Self::new(rocks)))
+        Ok(Self::new(rocks, source))
     }
 
-    fn is_blocked(&self, coord: Coordinate) -> bool {
-        coord.y >= self.max_y + 2 || self.rocks.contains(&coord) || self.sand.contains(&coord)
+    fn is_blocked(&self, coord: Point) -> bool {
+        coord.y >= self.max_y + 2 || self.materials.contains(coord)
     }
 
-    fn next_sand_position(&self, c: Coordinate) -> Option<Coordinate> {
-        let below = Coordinate { x: c.x, y: c.y + 1 };
-        if !self.is_blocked(below) {
-            return Some(below);
-        }
-        let below_left = Coordinate {
-            x: c.x - 1,
-            y: c.y + 1,
-        };
-        if !self.is_blocked(below_left) {
-            return Some(below_left);
-        }
-        let below_right = Coordinate {
-            x: c.x + 1,
-            y: c.y + 1,
+    fn next_sand_position(&self, c: Point) -> Option<Point> {
+        let below = c.step(Direction::South);
+        [
+            below,
+            below.step(Direction::West),
+            below.step(Direction::East),
+        ]
+        .into_iter()
+        .find(|&p| !self.is_blocked(p))
+    }
+}
+
+/// What is below the lowest rock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bottom {
+    /// Nothing, so sand falling past the lowest rock falls forever, as in part one.
+    Abyss,
+    /// A floor two below the lowest rock, as in part two.
+    Floor,
+}
+
+/// Sand pouring into the cave one move at a time.
+#[derive(Debug)]
+pub struct Pour {
+    cave: Cave,
+    bottom: Bottom,
+    /// Where the unit of sand that is falling is
+    falling: Point,
+    resting: usize,
+    ticks: u64,
+    finished: bool,
+    /// The corners of the drawing, which is big enough for all the sand that comes to rest.
+    min: Point,
+    max: Point,
+}
+
+impl Pour {
+    pub fn new(input: &str, params: &Params, bottom: Bottom) -> Result<Self, ParseError> {
+        let cave = Cave::parse(input, params.sand_source)?;
+        let (min, max) = match bottom {
+            Bottom::Abyss => (
+                Point {
+                    x: cave.min_x.min(cave.source.x),
+                    y: cave.source.y.min(0),
+                },
+                Point {
+                    x: cave.max_x,
+                    y: cave.max_y,
+                },
+            ),
+            Bottom::Floor => {
+                // the sand piles up into a triangle as wide as it is tall
+                let spread = (cave.max_y + 1 - cave.source.y).max(0);
+                (
+                    Point {
+                        x: cave.min_x.min(cave.source.x - spread),
+                        y: cave.source.y.min(0),
+                    },
+                    Point {
+                        x: cave.max_x.max(cave.source.x + spread),
+                        y: cave.max_y + 1,
+                    },
+                )
+            }
         };
-        if !self.is_blocked(below_right) {
-            return Some(below_right);
+        Ok(Self {
+            falling: cave.source,
+            finished: bottom == Bottom::Floor && cave.materials.contains(cave.source),
+            cave,
+            bottom,
+            resting: 0,
+            ticks: 0,
+            min,
+            max,
+        })
+    }
+
+    /// How many units of sand have come to rest.
+    pub fn resting(&self) -> usize {
+        self.resting
+    }
+
+    /// Moves the falling sand once, returning whether it came to rest.
+    fn fall(&mut self) -> bool {
+        if self.finished {
+            return false;
         }
-        None
-    }
-
-    fn draw(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
-        for y in 0..=self.max_y {
-            for x in self.min_x..=self.max_x {
-                let c = Coordinate { x, y };
-                write!(
-                    w,
-                    "{}",
-                    if self.rocks.contains(&c) {
-                        '#'
-                    } else if self.sand.contains(&c) {
-                        'o'
-                    } else if c == SAND_SOURCE {
-                        'p'
-                    } else {
-                        ' '
-                    }
-                )?;
+        self.ticks += 1;
+        match self.cave.next_sand_position(self.falling) {
+            Some(next) => {
+                self.falling = next;
+                if self.bottom == Bottom::Abyss && next.y > self.cave.max_y {
+                    self.finished = true;
+                }
+                false
+            }
+            None => {
+                self.cave.materials.insert(self.falling, Material::Sand);
+                self.resting += 1;
+                // sand trapped all the way up to the source stops any more pouring in
+                if self.falling == self.cave.source {
+                    self.finished = true;
+                } else {
+                    self.falling = self.cave.source;
+                }
+                true
             }
-            writeln!(w)?;
         }
-        Ok(())
     }
-}
 
-pub fn part_one(input: &str) -> usize {
-    let mut cave = Cave::parse(input).unwrap().1;
-    let mut counter = 0;
-    loop {
-        let mut c = SAND_SOURCE;
-        loop {
-            match cave.next_sand_position(c) {
-                Some(n) => c = n,
-                None => {
-                    cave.sand.insert(c);
-                    counter += 1;
-                    break;
-                }
-            }
-            if c.y > cave.max_y {
-                return counter;
+    /// Draws the cave, with the sand that is falling as `*`.
+    fn draw(&self) -> impl Display + '_ {
+        let falling = (self.falling != self.cave.source).then_some(self.falling);
+        self.cave
+            .materials
+            .render_within(self.min, self.max, move |c, material| match material {
+                Some(Material::Rock) => '#',
+                Some(Material::Sand) => 'o',
+                None if Some(c) == falling => '*',
+                None if c == self.cave.source => 'p',
+                None => ' ',
+            })
+    }
+
+    /// Pours until the end, showing `observer` the cave each time a unit of sand comes to rest.
+    fn run(mut self, observer: &mut dyn Observer) -> usize {
+        while !self.finished {
+            if self.fall() {
+                observer.frame(&self.draw());
             }
         }
+        self.resting
     }
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut cave = Cave::parse(input).unwrap().1;
-    let mut counter = 0;
-    loop {
-        let mut c = SAND_SOURCE;
-        loop {
-            if cave.sand.contains(&SAND_SOURCE) {
-                cave.draw(std::io::stdout()).unwrap();
-                return counter;
-            }
-            match cave.next_sand_position(c) {
-                Some(n) => c = n,
-                None => {
-                    cave.sand.insert(c);
-                    counter += 1;
-                    break;
-                }
-            }
+impl Stepper for Pour {
+    fn step(&mut self) -> bool {
+        if self.finished {
+            return false;
         }
+        self.fall();
+        true
+    }
+
+    fn tick(&self) -> u64 {
+        self.ticks
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn render(&self) -> String {
+        self.draw().to_string()
+    }
+
+    fn inspect(&self, column: usize, row: usize) -> Option<String> {
+        let c = self.min
+            + Point {
+                x: column as i64,
+                y: row as i64,
+            };
+        if c.x > self.max.x || c.y > self.max.y {
+            return None;
+        }
+        let what = match self.cave.materials.get(c) {
+            Some(Material::Rock) => "Rock",
+            Some(Material::Sand) => "Sand at rest",
+            None if c == self.falling && c != self.cave.source => "Falling sand",
+            None if c == self.cave.source => "The source of the sand",
+            None => "Air",
+        };
+        Some(format!("{what} at {},{}", c.x, c.y))
+    }
+
+    fn summary(&self) -> String {
+        if self.finished {
+            format!("Finished with {} units of sand at rest", self.resting)
+        } else {
+            format!("{} units of sand at rest", self.resting)
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    part_one_observed(input, &mut ())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<usize, ParseError> {
+    part_one_observed_with(input, params, &mut ())
+}
+
+/// Like [`part_one`], showing `observer` the cave each time a unit of sand comes to rest.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
+    part_one_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_one_observed`], with the sand poured in where `params` says.
+pub fn part_one_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<usize, ParseError> {
+    Ok(Pour::new(input, params, Bottom::Abyss)?.run(observer))
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    part_two_observed(input, &mut ())
+}
+
+pub fn part_two_with(input: &str, params: &Params) -> Result<usize, ParseError> {
+    part_two_observed_with(input, params, &mut ())
+}
+
+/// Like [`part_two`], showing `observer` the cave each time a unit of sand comes to rest.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
+    part_two_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_two_observed`], with the sand poured in where `params` says.
+pub fn part_two_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<usize, ParseError> {
+    Ok(Pour::new(input, params, Bottom::Floor)?.run(observer))
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(usize, usize), ParseError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day14 {
+    pub params: Params,
+}
+
+impl Solution for Day14 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one_with(input, &self.params)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two_with(input, &self.params)?)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_one_observed_with(input, &self.params, observer)?)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_two_observed_with(input, &self.params, observer)?)
+    }
+
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        let bottom = match part {
+            1 => Bottom::Abyss,
+            2 => Bottom::Floor,
+            _ => return None,
+        };
+        Some(
+            Pour::new(input, &self.params, bottom)
+                .map(|pour| Box::new(pour) as Box<dyn Stepper>)
+                .map_err(SolveError::from),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::parse::Expected;
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 24);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(24));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 737);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(737));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 93);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(93));
+    }
+
+    #[test]
+    fn rejects_paths_that_are_not_straight() {
+        let error = part_one("498,4 -> 500,9\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        assert_eq!(
+            error.expected,
+            Expected::Described("a point in a straight line from the last one")
+        );
+        assert_eq!(part_one("498,4 -> 500,6\n"), Ok(0));
+    }
+
+    #[test]
+    fn example_with_the_source_moved() {
+        let mut params = Params::default();
+        params.set("sand_source", "497,4").unwrap();
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((2, 18))
+        );
+    }
+
+    #[test]
+    fn shows_each_unit_of_sand() {
+        struct Frames(Vec<String>);
+
+        impl Observer for Frames {
+            fn frame(&mut self, frame: &dyn Display) {
+                self.0.push(frame.to_string());
+            }
+        }
+
+        let mut frames = Frames(Vec::new());
+        assert_eq!(
+            part_one_observed(include_str!("../example.txt"), &mut frames),
+            Ok(24)
+        );
+        assert_eq!(frames.0.len(), 24);
+        assert_eq!(
+            frames.0[0],
+            concat!(
+                "       p    \n",
+                "            \n",
+                "            \n",
+                "            \n",
+                "     #   ## \n",
+                "     #   #  \n",
+                "   ###   #  \n",
+                "         #  \n",
+                "       o #  \n",
+                " #########  \n",
+            )
+        );
+    }
+
+    #[test]
+    fn steps_one_move_at_a_time() {
+        let mut pour = Pour::new(
+            include_str!("../example.txt"),
+            &Params::default(),
+            Bottom::Abyss,
+        )
+        .unwrap();
+        pour.run_to(3);
+        assert_eq!(pour.tick(), 3);
+        assert_eq!(pour.inspect(7, 3).as_deref(), Some("Falling sand at 500,3"));
+        assert_eq!(pour.inspect(5, 4).as_deref(), Some("Rock at 498,4"));
+        assert_eq!(pour.render().matches('*').count(), 1);
+        // the first unit comes to rest on its ninth move
+        pour.run_to(9);
+        assert_eq!(pour.inspect(7, 8).as_deref(), Some("Sand at rest at 500,8"));
+        assert_eq!(pour.summary(), "1 units of sand at rest");
+        pour.run_to(u64::MAX);
+        assert!(pour.is_finished());
+        assert!(!pour.step());
+        assert_eq!(pour.resting(), 24);
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 28145);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(28145));
     }
 }
@@ -0,0 +1,112 @@
+//! Random sensor reports with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated report is, and where the parts look.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// Both coordinates of the distress beacon are between 0 and this
+    pub search_limit: i64,
+    /// The row checked in part one
+    pub row: i64,
+    /// The most sensors added while trying to leave just the distress beacon uncovered
+    pub max_sensors: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            search_limit: 20,
+            row: 10,
+            max_sensors: 100,
+        }
+    }
+}
+
+type Point = (i64, i64);
+
+fn distance(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+fn covered(sensors: &[(Point, Point)], point: Point) -> bool {
+    sensors
+        .iter()
+        .any(|&(sensor, beacon)| distance(sensor, point) <= distance(sensor, beacon))
+}
+
+/// Places sensors whose closest beacons are nearer than a hidden distress beacon, until that
+/// is the only position left uncovered in the search area or there are enough sensors.
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, i64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let limit = size.search_limit.max(1);
+    let distress = (rng.gen_range(0..=limit), rng.gen_range(0..=limit));
+    let area = || (0..=limit).flat_map(move |y| (0..=limit).map(move |x| (x, y)));
+    let mut sensors = Vec::new();
+    while sensors.len() < size.max_sensors.max(1) {
+        let sensor = (
+            rng.gen_range(-limit / 4..=limit + limit / 4),
+            rng.gen_range(-limit / 4..=limit + limit / 4),
+        );
+        let reach = distance(sensor, distress) - 1;
+        if reach < 1 {
+            continue;
+        }
+        let dx = rng.gen_range(-reach..=reach);
+        let dy = (reach - dx.abs()) * if rng.gen_bool(0.5) { 1 } else { -1 };
+        sensors.push((sensor, (sensor.0 + dx, sensor.1 + dy)));
+        if area().filter(|&p| !covered(&sensors, p)).count() == 1 {
+            break;
+        }
+    }
+
+    let input = sensors
+        .iter()
+        .map(|((sx, sy), (bx, by))| {
+            format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}\n")
+        })
+        .collect();
+    let furthest = sensors
+        .iter()
+        .map(|&(sensor, beacon)| sensor.0.abs() + distance(sensor, beacon))
+        .max()
+        .unwrap_or(0);
+    let part_one = (-furthest..=furthest)
+        .map(|x| (x, size.row))
+        .filter(|&p| covered(&sensors, p) && sensors.iter().all(|&(_, beacon)| beacon != p))
+        .count();
+    let mut uncovered = area().filter(|&p| !covered(&sensors, p));
+    let part_two = match (uncovered.next(), uncovered.next()) {
+        (Some((x, y)), None) => Some(x * 4000000 + y),
+        _ => None,
+    };
+    Generated {
+        input,
+        part_one: Some(part_one),
+        part_two,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        let size = Size::default();
+        for seed in 0..100 {
+            let generated = generate(seed, &size);
+            assert_eq!(
+                crate::part_one(&generated.input, size.row).ok(),
+                generated.part_one
+            );
+            if generated.part_two.is_some() {
+                assert_eq!(
+                    crate::part_two(&generated.input, size.search_limit).ok(),
+                    generated.part_two
+                );
+            }
+        }
+    }
+}
@@ -1,12 +1,58 @@
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    parse_param, ParamError, Parameters, ParseError, Solution, SolveError,
+};
 use nom::{
-    bytes::{complete::tag, streaming::take_while1},
-    character::complete::newline,
-    combinator::{iterator, map, map_res, ParserIterator},
-    sequence::{terminated, tuple},
-    IResult,
+    bytes::complete::take_while1,
+    combinator::{map, map_res},
+    sequence::tuple,
 };
 use rayon::prelude::*;
-use std::{collections::BTreeSet, ops::RangeInclusive};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// Where the puzzle looks for the distress beacon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The row checked in part one
+    pub row: i64,
+    /// Both coordinates of the distress beacon are between 0 and this
+    pub search_limit: i64,
+    /// What the distress beacon's x is multiplied by in its tuning frequency
+    pub tuning_multiplier: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            row: 2000000,
+            search_limit: 4000000,
+            tuning_multiplier: 4000000,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        let number = || parse_param(value, "a whole number");
+        match name {
+            "row" => self.row = number()?,
+            "search_limit" => self.search_limit = number()?,
+            "tuning_multiplier" => self.tuning_multiplier = number()?,
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "row",
+                    "search_limit",
+                    "tuning_multiplier",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 struct Location {
@@ -18,7 +64,7 @@ impl Location {
     fn parse_nom(input: &str) -> IResult<&str, Self> {
         fn parse_num(input: &str) -> IResult<&str, i64> {
             map_res(
-                take_while1(|c: char| c == '-' || ('0'..='9').contains(&c)),
+                take_while1(|c: char| c == '-' || c.is_ascii_digit()),
                 str::parse,
             )(input)
         }
@@ -59,14 +105,8 @@ impl Sensor {
         )(input)
     }
 
-    fn parse_all_iterator<'a>(
-        input: &'a str,
-    ) -> ParserIterator<
-        &'a str,
-        nom::error::Error<&'a str>,
-        impl FnMut(&'a str) -> IResult<&'a str, Self>,
-    > {
-        iterator(input, terminated(Self::parse_nom, newline))
+    fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        final_parse(input, lines(Self::parse_nom))
     }
 
     fn beacon_distance(&self) -> u64 {
@@ -95,10 +135,21 @@ fn normalize_ranges(mut ranges: Vec<RangeInclusive<i64>>) -> Vec<RangeInclusive<
     normalized
 }
 
-pub fn part_one(input: &str, row: i64) -> usize {
+pub fn part_one(input: &str, row: i64) -> Result<usize, ParseError> {
+    part_one_with(
+        input,
+        &Params {
+            row,
+            ..Params::default()
+        },
+    )
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let row = params.row;
     let mut covered_ranges = Vec::new();
     let mut beacons_in_row = BTreeSet::new();
-    for sensor in &mut Sensor::parse_all_iterator(input) {
+    for sensor in Sensor::parse_all(input)? {
         covered_ranges.push(
             sensor
                 .location
@@ -110,28 +161,71 @@ pub fn part_one(input: &str, row: i64) -> usize {
     }
     let covered_ranges = normalize_ranges(covered_ranges);
 
-    covered_ranges
+    Ok(covered_ranges
         .iter()
         .map(|r| r.clone().count())
         .sum::<usize>()
-        - beacons_in_row.len()
+        - beacons_in_row.len())
+}
+
+pub fn part_two(input: &str, search_limit: i64) -> Result<i64, ParseError> {
+    part_two_with(
+        input,
+        &Params {
+            search_limit,
+            ..Params::default()
+        },
+    )
 }
 
-pub fn part_two(input: &str, search_limit: i64) -> i64 {
-    let sensors: Vec<Sensor> = Sensor::parse_all_iterator(input).collect();
-    (0..=search_limit)
+pub fn part_two_with(input: &str, params: &Params) -> Result<i64, ParseError> {
+    let Params {
+        search_limit,
+        tuning_multiplier,
+        ..
+    } = *params;
+    let sensors = Sensor::parse_all(input)?;
+    Ok((0..=search_limit)
         .into_par_iter()
         .find_map_first(|row| {
             let covered_ranges = sensors
                 .iter()
                 .map(|s| s.location.manhatten_points_at_row(s.beacon_distance(), row))
                 .collect();
-            let covered_ranges = normalize_ranges(covered_ranges);
-            covered_ranges.iter().find_map(|r| {
-                ((-1..search_limit).contains(r.end())).then(|| (*r.end() + 1) * 4000000 + row)
-            })
+            // the first gap in the row, which may come before all of the ranges or after them
+            let mut x = 0;
+            for r in normalize_ranges(covered_ranges) {
+                if *r.start() > x || x > search_limit {
+                    break;
+                }
+                x = x.max(*r.end() + 1);
+            }
+            (x <= search_limit).then_some(x * tuning_multiplier + row)
         })
-        .unwrap_or(0)
+        .unwrap_or(0))
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(usize, i64), ParseError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day15 {
+    pub params: Params,
+}
+
+impl Solution for Day15 {
+    type PartOne = usize;
+    type PartTwo = i64;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one_with(input, &self.params)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<i64, SolveError> {
+        Ok(part_two_with(input, &self.params)?)
+    }
 }
 
 #[cfg(test)]
@@ -140,24 +234,40 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt"), 10), 26);
+        assert_eq!(part_one(include_str!("../example.txt"), 10), Ok(26));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt"), 2000000), 4793062);
+        assert_eq!(
+            part_one(include_str!("../challenge.txt"), 2000000),
+            Ok(4793062)
+        );
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt"), 20), 56000011);
+        assert_eq!(part_two(include_str!("../example.txt"), 20), Ok(56000011));
+    }
+
+    #[test]
+    fn example_with_its_own_params() {
+        let params = Params {
+            row: 11,
+            search_limit: 20,
+            tuning_multiplier: 100,
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((28, 1411))
+        );
     }
 
     #[test]
     fn challenge_part_two() {
         assert_eq!(
             part_two(include_str!("../challenge.txt"), 4000000),
-            10826395253551
+            Ok(10826395253551)
         );
     }
 }
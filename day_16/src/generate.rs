@@ -0,0 +1,116 @@
+//! Random valve scans with known answers, for testing.
+
+use std::collections::BTreeSet;
+
+use aoc_core::Generated;
+
+use crate::reference;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How big a generated scan is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub valves: usize,
+    /// Tunnels added on top of the ones needed to connect every valve
+    pub extra_tunnels: usize,
+    /// How likely each valve other than AA is to have a working flow rate
+    pub working: f64,
+    pub max_flow_rate: u16,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            valves: 10,
+            extra_tunnels: 4,
+            working: 0.5,
+            max_flow_rate: 25,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<u16, u16> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let count = size.valves.clamp(2, 26 * 26);
+    let mut names = vec![String::from("AA")];
+    let mut letters: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|name| name != "AA")
+        .collect();
+    letters.shuffle(&mut rng);
+    names.extend(letters.into_iter().take(count - 1));
+
+    // a random tree, so every valve can be reached, and then some shortcuts
+    let mut tunnels = BTreeSet::new();
+    for valve in 1..count {
+        let other = rng.gen_range(0..valve);
+        tunnels.insert((other, valve));
+    }
+    for _ in 0..size.extra_tunnels {
+        let (a, b) = (rng.gen_range(0..count), rng.gen_range(0..count));
+        if a != b {
+            tunnels.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut order: Vec<usize> = (0..count).collect();
+    order.shuffle(&mut rng);
+    let mut input = String::new();
+    for valve in order {
+        let flow_rate = if valve > 0 && rng.gen_bool(size.working) {
+            rng.gen_range(1..=size.max_flow_rate.max(1))
+        } else {
+            0
+        };
+        let mut neighbours: Vec<&str> = tunnels
+            .iter()
+            .filter_map(|&(a, b)| match (a == valve, b == valve) {
+                (true, _) => Some(names[b].as_str()),
+                (_, true) => Some(names[a].as_str()),
+                _ => None,
+            })
+            .collect();
+        neighbours.shuffle(&mut rng);
+        let tunnels = match neighbours.as_slice() {
+            [only] => format!("tunnel leads to valve {only}"),
+            _ => format!("tunnels lead to valves {}", neighbours.join(", ")),
+        };
+        input += &format!(
+            "Valve {} has flow rate={flow_rate}; {tunnels}\n",
+            names[valve]
+        );
+    }
+    Generated {
+        part_one: reference::part_one(&input),
+        part_two: reference::part_two(&input),
+        input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::smallest_disagreement;
+
+    #[test]
+    fn matches_the_reference() {
+        let cases = (2..12).flat_map(|valves| {
+            (0..20).map(move |seed| {
+                let size = Size {
+                    valves,
+                    ..Size::default()
+                };
+                generate(seed, &size)
+            })
+        });
+        let (part_one, part_two): (Vec<_>, Vec<_>) = cases
+            .map(|g| ((g.input.clone(), g.part_one), (g.input, g.part_two)))
+            .unzip();
+        if let Some(disagreement) = smallest_disagreement(part_one, |i| crate::part_one(i).ok()) {
+            panic!("part one {disagreement}");
+        }
+        if let Some(disagreement) = smallest_disagreement(part_two, |i| crate::part_two(i).ok()) {
+            panic!("part two {disagreement}");
+        }
+    }
+}
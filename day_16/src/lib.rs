@@ -1,20 +1,64 @@
+use std::{
+    collections::{hash_map, HashMap},
+    iter::once,
+};
+
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    parse_param, ParamError, Parameters, Solution, SolveError,
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while},
-    character::complete::{digit1, newline},
+    bytes::complete::take_while,
+    character::complete::digit1,
     combinator::{map, map_res},
     multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
 
-use std::{
-    collections::{hash_map, HashMap, HashSet},
-    iter::once,
-    mem::take,
-};
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod reference;
+
+/// Where you start and how long you have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub start_valve: String,
+    /// The time you have on your own in part one
+    pub minutes: u16,
+    /// The time left once the elephant has been taught in part two
+    pub minutes_with_elephant: u16,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            start_valve: String::from("AA"),
+            minutes: 30,
+            minutes_with_elephant: 26,
+        }
+    }
+}
 
-const START_VALVE: &str = "AA";
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "start_valve" => self.start_valve = parse_param(value, "a valve name")?,
+            "minutes" => self.minutes = parse_param(value, "a number of minutes")?,
+            "minutes_with_elephant" => {
+                self.minutes_with_elephant = parse_param(value, "a number of minutes")?
+            }
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "start_valve",
+                    "minutes",
+                    "minutes_with_elephant",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug)]
 struct ValveData<'a> {
@@ -25,7 +69,7 @@ struct ValveData<'a> {
 
 impl<'a> ValveData<'a> {
     fn parse_nom(input: &'a str) -> IResult<&'a str, ValveData<'a>> {
-        let is_valve_name_char: fn(char) -> bool = |c| ('A'..='Z').contains(&c);
+        let is_valve_name_char: fn(char) -> bool = |c| c.is_ascii_uppercase();
 
         map(
             tuple((
@@ -54,46 +98,44 @@ struct Valve<'a> {
     neighbour_distance: HashMap<&'a str, u16>,
 }
 
+/// The valves worth opening and the one you start at, with the walking times between them.
 #[derive(Debug)]
-struct World<'a> {
+pub struct World<'a> {
     valves: HashMap<&'a str, Valve<'a>>,
+    start: &'a str,
 }
 
 impl<'a> World<'a> {
-    fn prepare(input: &'a str) -> Self {
-        let valve_datas = separated_list1(newline, ValveData::parse_nom)(input)
-            .unwrap()
-            .1;
+    /// Parses the scan and works out how far apart the valves are, starting from `start_valve`.
+    pub fn prepare(input: &'a str, start_valve: &str) -> Result<Self, SolveError> {
+        let valve_datas = final_parse(input, lines(ValveData::parse_nom))?;
+        let start = valve_datas
+            .iter()
+            .find(|vd| vd.name == start_valve)
+            .ok_or(SolveError::Invalid("There is no start valve to start from"))?
+            .name;
         let mut valves = HashMap::new();
 
         for vd in valve_datas
             .iter()
-            .filter(|&vd| vd.name == START_VALVE || vd.flow_rate > 0)
+            .filter(|&vd| vd.name == start || vd.flow_rate > 0)
         {
-            let mut neighbour_distance = HashMap::new();
-            let mut visited = HashSet::new();
-            visited.insert(vd.name);
-            let mut recently_visited = vec![vd.name];
-            for distance in 1.. {
-                if recently_visited.is_empty() {
-                    break;
-                }
-                for &v in take(&mut recently_visited).iter() {
-                    if let Some(vd) = valve_datas.iter().find(|vd| vd.name == v) {
-                        for &neighbour in vd.neighbours.iter() {
-                            if visited.insert(neighbour) {
-                                recently_visited.push(neighbour);
-                                if valve_datas
-                                    .iter()
-                                    .any(|vd| vd.name == neighbour && vd.flow_rate > 0)
-                                {
-                                    neighbour_distance.insert(neighbour, distance);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            let neighbour_distance = search::bfs_all(vd.name, |&v| {
+                valve_datas
+                    .iter()
+                    .find(|vd| vd.name == v)
+                    .into_iter()
+                    .flat_map(|vd| vd.neighbours.iter().copied())
+            })
+            .iter()
+            .filter(|&(&neighbour, distance)| {
+                distance > 0
+                    && valve_datas
+                        .iter()
+                        .any(|vd| vd.name == neighbour && vd.flow_rate > 0)
+            })
+            .map(|(&neighbour, distance)| (neighbour, distance as u16))
+            .collect();
             valves.insert(
                 vd.name,
                 Valve {
@@ -103,7 +145,27 @@ impl<'a> World<'a> {
             );
         }
 
-        World { valves }
+        Ok(World { valves, start })
+    }
+
+    pub fn start(&self) -> &'a str {
+        self.start
+    }
+
+    /// The flow rate of each valve kept, which are the start and those worth opening.
+    pub fn flow_rates(&self) -> impl Iterator<Item = (&'a str, u16)> + '_ {
+        self.valves
+            .iter()
+            .map(|(&name, valve)| (name, valve.flow_rate))
+    }
+
+    /// How many minutes it takes to walk from `valve` to each other valve worth opening.
+    pub fn distances_from(&self, valve: &str) -> impl Iterator<Item = (&'a str, u16)> + '_ {
+        self.valves
+            .get(valve)
+            .into_iter()
+            .flat_map(|valve| valve.neighbour_distance.iter())
+            .map(|(&name, &distance)| (name, distance))
     }
 }
 
@@ -117,10 +179,10 @@ struct StackItem<'a> {
 fn best_one_person(world: &World, time_remaining: u16, valves_to_avoid: &[&str]) -> u16 {
     let mut best = 0;
     let mut stack = vec![StackItem {
-        name: START_VALVE,
+        name: world.start,
         neighbour_iter: world
             .valves
-            .get(START_VALVE)
+            .get(world.start)
             .unwrap()
             .neighbour_distance
             .iter(),
@@ -162,25 +224,34 @@ fn best_one_person(world: &World, time_remaining: u16, valves_to_avoid: &[&str])
     best
 }
 
-pub fn part_one(input: &str) -> u16 {
-    let world = World::prepare(input);
-    best_one_person(&world, 30, &[])
+pub fn part_one(input: &str) -> Result<u16, SolveError> {
+    part_one_with(input, &Params::default())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<u16, SolveError> {
+    let world = World::prepare(input, &params.start_valve)?;
+    Ok(best_one_person(&world, params.minutes, &[]))
+}
+
+pub fn part_two(input: &str) -> Result<u16, SolveError> {
+    part_two_with(input, &Params::default())
 }
 
-pub fn part_two(input: &str) -> u16 {
-    let world = World::prepare(input);
+pub fn part_two_with(input: &str, params: &Params) -> Result<u16, SolveError> {
+    let world = World::prepare(input, &params.start_valve)?;
+    let minutes = params.minutes_with_elephant;
 
     let mut best = 0;
     let mut stack = vec![StackItem {
-        name: START_VALVE,
+        name: world.start,
         neighbour_iter: world
             .valves
-            .get(START_VALVE)
+            .get(world.start)
             .unwrap()
             .neighbour_distance
             .iter(),
         acc_flow: 0,
-        time_remaining: 26,
+        time_remaining: minutes,
     }];
 
     // an elephant always remembers
@@ -217,13 +288,36 @@ pub fn part_two(input: &str) -> u16 {
             visited.sort_unstable();
             let elephant = *elephant_memoization
                 .entry(visited.clone())
-                .or_insert_with(|| best_one_person(&world, 26, &visited));
+                .or_insert_with(|| best_one_person(&world, minutes, &visited));
             best = u16::max(best, new_stack_item.acc_flow + elephant);
             stack.push(new_stack_item);
         }
     }
 
-    best
+    Ok(best)
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(u16, u16), SolveError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day16 {
+    pub params: Params,
+}
+
+impl Solution for Day16 {
+    type PartOne = u16;
+    type PartTwo = u16;
+
+    fn part_one(&self, input: &str) -> Result<u16, SolveError> {
+        part_one_with(input, &self.params)
+    }
+
+    fn part_two(&self, input: &str) -> Result<u16, SolveError> {
+        part_two_with(input, &self.params)
+    }
 }
 
 #[cfg(test)]
@@ -232,21 +326,65 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 1651);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(1651));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1789);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(1789));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 1707);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(1707));
+    }
+
+    #[test]
+    fn example_distances() {
+        let world = World::prepare(include_str!("../example.txt"), "AA").unwrap();
+        assert_eq!(world.start(), "AA");
+        assert_eq!(world.flow_rates().count(), 7);
+        let mut from_start: Vec<_> = world.distances_from("AA").collect();
+        from_start.sort();
+        assert_eq!(
+            from_start,
+            [
+                ("BB", 1),
+                ("CC", 2),
+                ("DD", 1),
+                ("EE", 2),
+                ("HH", 5),
+                ("JJ", 2)
+            ]
+        );
+        assert_eq!(world.distances_from("GG").count(), 0);
+    }
+
+    #[test]
+    fn example_with_less_time_from_another_valve() {
+        let params = Params {
+            start_valve: String::from("II"),
+            minutes: 10,
+            minutes_with_elephant: 5,
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((21 * 8 + 20 * 4 + 13, 21 * 3 + 20 * 2))
+        );
+        assert_eq!(
+            part_one_with(
+                include_str!("../example.txt"),
+                &Params {
+                    start_valve: String::from("ZZ"),
+                    ..params
+                }
+            ),
+            Err(SolveError::Invalid("There is no start valve to start from"))
+        );
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2496);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(2496));
     }
 }
@@ -0,0 +1,126 @@
+//! A slow but simple solution for small scans, to check the fast one against.
+//!
+//! It tries every order of opening the working valves, and for part two every way of sharing
+//! them with the elephant, so it only finishes for a handful of working valves.
+
+use std::collections::{HashMap, VecDeque};
+
+struct Scan {
+    flow_rates: Vec<u16>,
+    /// The minutes to walk between each pair of valves
+    distances: Vec<Vec<u16>>,
+    start: usize,
+}
+
+impl Scan {
+    fn parse(input: &str) -> Option<Scan> {
+        let mut names = HashMap::new();
+        let mut valves = Vec::new();
+        for line in input.lines() {
+            let (valve, tunnels) = line.split_once("; ")?;
+            let (name, flow_rate) = valve
+                .strip_prefix("Valve ")?
+                .split_once(" has flow rate=")?;
+            let tunnels = tunnels
+                .trim_start_matches(|c: char| !c.is_ascii_uppercase())
+                .split(", ");
+            names.insert(name, valves.len());
+            valves.push((flow_rate.parse().ok()?, tunnels));
+        }
+
+        let mut flow_rates = Vec::new();
+        let mut neighbours = Vec::new();
+        for (flow_rate, tunnels) in valves {
+            flow_rates.push(flow_rate);
+            neighbours.push(
+                tunnels
+                    .map(|name| names.get(name).copied())
+                    .collect::<Option<Vec<usize>>>()?,
+            );
+        }
+        let distances = (0..flow_rates.len())
+            .map(|from| {
+                let mut distance = vec![u16::MAX; flow_rates.len()];
+                distance[from] = 0;
+                let mut queue = VecDeque::from([from]);
+                while let Some(valve) = queue.pop_front() {
+                    for &next in &neighbours[valve] {
+                        if distance[next] == u16::MAX {
+                            distance[next] = distance[valve] + 1;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                distance
+            })
+            .collect();
+        Some(Scan {
+            flow_rates,
+            distances,
+            start: *names.get("AA")?,
+        })
+    }
+
+    /// The most pressure released by opening valves in `closed` within `time` minutes,
+    /// standing at `at`.
+    fn best(&self, at: usize, time: u16, closed: &mut Vec<usize>) -> u16 {
+        let mut best = 0;
+        for i in 0..closed.len() {
+            let valve = closed[i];
+            let Some(time) = time.checked_sub(self.distances[at][valve].saturating_add(1)) else {
+                continue;
+            };
+            closed.swap_remove(i);
+            best = best.max(time * self.flow_rates[valve] + self.best(valve, time, closed));
+            closed.push(valve);
+            let last = closed.len() - 1;
+            closed.swap(i, last);
+        }
+        best
+    }
+
+    fn working(&self) -> Vec<usize> {
+        (0..self.flow_rates.len())
+            .filter(|&valve| self.flow_rates[valve] > 0)
+            .collect()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u16> {
+    let scan = Scan::parse(input)?;
+    Some(scan.best(scan.start, 30, &mut scan.working()))
+}
+
+pub fn part_two(input: &str) -> Option<u16> {
+    let scan = Scan::parse(input)?;
+    let working = scan.working();
+    (0..1usize << working.len())
+        .map(|mine| {
+            let (mut my_valves, mut elephant_valves) = (Vec::new(), Vec::new());
+            for (i, &valve) in working.iter().enumerate() {
+                if mine & 1 << i != 0 {
+                    my_valves.push(valve);
+                } else {
+                    elephant_valves.push(valve);
+                }
+            }
+            scan.best(scan.start, 26, &mut my_valves)
+                + scan.best(scan.start, 26, &mut elephant_valves)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(part_one(include_str!("../example.txt")), Some(1651));
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(part_two(include_str!("../example.txt")), Some(1707));
+    }
+}
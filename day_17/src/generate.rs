@@ -0,0 +1,68 @@
+//! Random jet patterns with known answers, for testing.
+
+use aoc_core::Generated;
+
+use crate::reference;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated jet pattern is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub jets: usize,
+    /// How likely each jet is to push left
+    pub left: f64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            jets: 40,
+            left: 0.5,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input: String = (0..size.jets.max(1))
+        .map(|_| if rng.gen_bool(size.left) { '<' } else { '>' })
+        .collect();
+    input.push('\n');
+    Generated {
+        part_one: reference::part_one(&input),
+        part_two: reference::part_two(&input),
+        input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::smallest_disagreement;
+
+    #[test]
+    fn matches_the_reference() {
+        let cases = (1..40).flat_map(|jets| {
+            (0..5).map(move |seed| {
+                generate(
+                    seed,
+                    &Size {
+                        jets,
+                        ..Size::default()
+                    },
+                )
+            })
+        });
+        let (part_one, part_two): (Vec<_>, Vec<_>) = cases
+            .map(|g| ((g.input.clone(), g.part_one), (g.input, g.part_two)))
+            .unzip();
+        // some patterns never repeat in a way the reference can spot
+        let part_two = part_two.into_iter().filter(|(_, answer)| answer.is_some());
+        if let Some(disagreement) = smallest_disagreement(part_one, |i| crate::part_one(i).ok()) {
+            panic!("part one {disagreement}");
+        }
+        if let Some(disagreement) = smallest_disagreement(part_two, |i| crate::part_two(i).ok()) {
+            panic!("part two {disagreement}");
+        }
+    }
+}
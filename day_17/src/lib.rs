@@ -1,29 +1,73 @@
-use std::collections::{hash_map, HashMap};
+use std::{
+    collections::{hash_map, HashMap},
+    fmt::Write,
+};
 
-#[derive(Clone, Copy)]
-struct RP {
-    x: usize,
-    y: usize,
+use aoc_core::{
+    parse::Expected, parse_param, Observer, ParamError, Parameters, ParseError, Solution,
+    SolveError, Stepper,
+};
+use grid::{Direction, Grid, Point};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod reference;
+
+/// The chamber and how many rocks fall into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// How many units wide the chamber is, which has to leave room for the widest rock two units
+    /// from the left wall
+    pub width: usize,
+    /// The rocks dropped in part one
+    pub rocks: u64,
+    /// The rocks dropped in part two
+    pub rocks_part_two: u64,
 }
 
-enum Direction {
-    Left,
-    Right,
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            width: 7,
+            rocks: 2022,
+            rocks_part_two: 1000000000000,
+        }
+    }
+}
+
+const MIN_WIDTH: usize = 6;
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "width" => {
+                self.width = parse_param(value, "a width of at least 6")?;
+                if self.width < MIN_WIDTH {
+                    return Err(ParamError::Invalid("a width of at least 6"));
+                }
+            }
+            "rocks" => self.rocks = parse_param(value, "a number of rocks")?,
+            "rocks_part_two" => self.rocks_part_two = parse_param(value, "a number of rocks")?,
+            _ => return Err(ParamError::Unknown(&["width", "rocks", "rocks_part_two"])),
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy)]
 struct Rock<'a> {
-    shape: &'a [RP],
+    /// Offsets from the rock's bottom left corner, with `y` increasing upwards.
+    shape: &'a [Point],
 }
 
 const ROCKS: &[Rock] = &[
     // ####
     Rock {
         shape: &[
-            RP { x: 0, y: 0 },
-            RP { x: 1, y: 0 },
-            RP { x: 2, y: 0 },
-            RP { x: 3, y: 0 },
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(3, 0),
         ],
     },
     // .#.
@@ -31,11 +75,11 @@ const ROCKS: &[Rock] = &[
     // .#.
     Rock {
         shape: &[
-            RP { x: 1, y: 2 },
-            RP { x: 0, y: 1 },
-            RP { x: 1, y: 1 },
-            RP { x: 2, y: 1 },
-            RP { x: 1, y: 0 },
+            Point::new(1, 2),
+            Point::new(0, 1),
+            Point::new(1, 1),
+            Point::new(2, 1),
+            Point::new(1, 0),
         ],
     },
     // ..#
@@ -43,11 +87,11 @@ const ROCKS: &[Rock] = &[
     // ###
     Rock {
         shape: &[
-            RP { x: 2, y: 2 },
-            RP { x: 2, y: 1 },
-            RP { x: 0, y: 0 },
-            RP { x: 1, y: 0 },
-            RP { x: 2, y: 0 },
+            Point::new(2, 2),
+            Point::new(2, 1),
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
         ],
     },
     // #
@@ -56,172 +100,387 @@ const ROCKS: &[Rock] = &[
     // #
     Rock {
         shape: &[
-            RP { x: 0, y: 3 },
-            RP { x: 0, y: 2 },
-            RP { x: 0, y: 1 },
-            RP { x: 0, y: 0 },
+            Point::new(0, 3),
+            Point::new(0, 2),
+            Point::new(0, 1),
+            Point::new(0, 0),
         ],
     },
     // ##
     // ##
     Rock {
         shape: &[
-            RP { x: 0, y: 1 },
-            RP { x: 1, y: 1 },
-            RP { x: 0, y: 0 },
-            RP { x: 1, y: 0 },
+            Point::new(0, 1),
+            Point::new(1, 1),
+            Point::new(0, 0),
+            Point::new(1, 0),
         ],
     },
 ];
 
 struct Simulation {
-    stopped_rocks: Vec<[bool; 7]>,
+    /// Row `0` is the floor of the chamber.
+    stopped_rocks: Grid<bool>,
     rock_cycle: usize,
     wind_cycle: usize,
     wind_directions: Vec<Direction>,
+    /// Where the bottom left corner of the rock that is falling is, if one is
+    falling: Option<Point>,
+}
+
+/// The jets of gas, which have to be a line of at least one `<` or `>`.
+fn parse_jets(input: &str) -> Result<Vec<Direction>, SolveError> {
+    let jets = input
+        .trim_end()
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Direction::West),
+            '>' => Ok(Direction::East),
+            _ => Err(ParseError::new(
+                input,
+                &input[i..],
+                Expected::Described("a jet of gas (< or >)"),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if jets.is_empty() {
+        return Err(SolveError::Invalid("There are no jets of gas"));
+    }
+    Ok(jets)
 }
 
 impl Simulation {
-    fn new(input: &str) -> Self {
-        Self {
-            stopped_rocks: Vec::new(),
+    fn new(input: &str, width: usize) -> Result<Self, SolveError> {
+        Ok(Self {
+            stopped_rocks: Grid::new(width, 0, false),
             rock_cycle: 0,
             wind_cycle: 0,
-            wind_directions: input
-                .chars()
-                .map_while(|c| match c {
-                    '<' => Some(Direction::Left),
-                    '>' => Some(Direction::Right),
-                    _ => None,
-                })
-                .collect(),
-        }
+            wind_directions: parse_jets(input)?,
+            falling: None,
+        })
     }
 
-    fn valid_position(
-        &self,
-        rock: &Rock,
-        x_offset: Option<usize>,
-        y_offset: Option<usize>,
-    ) -> bool {
-        let x_offset = match x_offset {
-            None => {
-                return false;
-            }
-            Some(x_offset) => x_offset,
-        };
-        let y_offset = match y_offset {
-            None => {
-                return false;
+    fn valid_position(&self, rock: &Rock, offset: Point) -> bool {
+        rock.shape.iter().all(|&s| {
+            let p = s + offset;
+            (0..self.stopped_rocks.width() as i64).contains(&p.x)
+                && p.y >= 0
+                && self.stopped_rocks.get(p) != Some(&true)
+        })
+    }
+
+    fn solidify_rock(&mut self, rock: &Rock, offset: Point) {
+        for &s in rock.shape.iter() {
+            let p = s + offset;
+            while !self.stopped_rocks.contains(p) {
+                let width = self.stopped_rocks.width();
+                self.stopped_rocks.push_row(vec![false; width]);
             }
-            Some(y_offset) => y_offset,
-        };
-        for s in rock.shape.iter() {
-            let x = s.x + x_offset;
-            let y = s.y + y_offset;
-            if x >= 7 {
-                return false;
+            self.stopped_rocks[p] = true;
+        }
+    }
+
+    /// Pushes the falling rock with the next jet of gas and then lets it fall one unit, first
+    /// bringing in the next rock if none is falling. Returns whether the rock came to rest.
+    fn push_and_fall(&mut self) -> bool {
+        let rock = ROCKS[self.rock_cycle];
+        let mut offset = self
+            .falling
+            .unwrap_or_else(|| Point::new(2, 3 + self.stopped_rocks.height() as i64));
+        let blown = offset.step(self.wind_directions[self.wind_cycle]);
+        if self.valid_position(&rock, blown) {
+            offset = blown;
+        }
+        self.wind_cycle = (self.wind_cycle + 1) % self.wind_directions.len();
+        // the grid's rows count up from the floor, so falling is a step north
+        let fallen = offset.step(Direction::North);
+        if self.valid_position(&rock, fallen) {
+            self.falling = Some(fallen);
+            false
+        } else {
+            self.solidify_rock(&rock, offset);
+            self.falling = None;
+            self.rock_cycle = (self.rock_cycle + 1) % ROCKS.len();
+            true
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        while !self.push_and_fall() {}
+    }
+
+    /// The cells of the rock that is falling.
+    fn falling_rock(&self) -> impl Iterator<Item = Point> + '_ {
+        self.falling.into_iter().flat_map(|offset| {
+            ROCKS[self.rock_cycle]
+                .shape
+                .iter()
+                .map(move |&s| s + offset)
+        })
+    }
+
+    /// The row above the top row that [`Simulation::render`] draws.
+    fn render_top(&self, rows: usize) -> usize {
+        self.falling_rock()
+            .map(|p| p.y as usize + 1)
+            .fold(self.stopped_rocks.height(), usize::max)
+            .max(rows)
+    }
+
+    /// Draws the top `rows` rows of the tower, highest first, padded with air when it is lower.
+    /// A rock that is still falling is drawn with `@`, and the rows are raised to fit it in.
+    fn render(&self, rows: usize) -> String {
+        let mut drawing = String::new();
+        let falling: Vec<_> = self.falling_rock().collect();
+        let top = self.render_top(rows);
+        for y in (top - rows..top).rev() {
+            drawing.push('|');
+            for x in 0..self.stopped_rocks.width() {
+                let p = Point::new(x as i64, y as i64);
+                drawing.push(if falling.contains(&p) {
+                    '@'
+                } else if self.stopped_rocks.get(p) == Some(&true) {
+                    '#'
+                } else {
+                    '.'
+                });
             }
-            match self.stopped_rocks.get(y) {
-                None => {}
-                Some(row) => {
-                    if row.get(x) != Some(&false) {
-                        return false;
-                    }
-                }
-            };
+            let _ = writeln!(drawing, "|");
         }
+        drawing
+    }
+}
+
+/// How many rows of the tower a [`RockFall`] draws.
+const DRAWN_ROWS: usize = 40;
+
+/// Rocks falling into the chamber one push and one fall at a time, as in part one.
+pub struct RockFall {
+    simulation: Simulation,
+    rocks: u64,
+    stopped: u64,
+    ticks: u64,
+}
+
+impl RockFall {
+    pub fn new(input: &str, params: &Params) -> Result<Self, SolveError> {
+        check_width(params)?;
+        Ok(Self {
+            simulation: Simulation::new(input, params.width)?,
+            rocks: params.rocks,
+            stopped: 0,
+            ticks: 0,
+        })
+    }
 
+    /// How tall the tower of rocks at rest is.
+    pub fn height(&self) -> usize {
+        self.simulation.stopped_rocks.height()
+    }
+}
+
+impl Stepper for RockFall {
+    fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.ticks += 1;
+        if self.simulation.push_and_fall() {
+            self.stopped += 1;
+        }
         true
     }
 
-    fn solidify_rock(&mut self, rock: &Rock, x_offset: usize, y_offset: usize) {
-        for s in rock.shape.iter() {
-            let x = s.x + x_offset;
-            let y = s.y + y_offset;
-            loop {
-                match self.stopped_rocks.get_mut(y) {
-                    None => self.stopped_rocks.push([false; 7]),
-                    Some(row) => {
-                        row[x] = true;
-                        break;
-                    }
-                }
-            }
+    fn tick(&self) -> u64 {
+        self.ticks
+    }
+
+    fn is_finished(&self) -> bool {
+        self.stopped >= self.rocks
+    }
+
+    fn render(&self) -> String {
+        self.simulation.render(DRAWN_ROWS)
+    }
+
+    fn inspect(&self, column: usize, row: usize) -> Option<String> {
+        let width = self.simulation.stopped_rocks.width();
+        if column > width + 1 || row >= DRAWN_ROWS {
+            return None;
+        }
+        if column == 0 || column == width + 1 {
+            return Some("The chamber's wall".to_owned());
         }
+        let y = self.simulation.render_top(DRAWN_ROWS) - 1 - row;
+        let p = Point::new(column as i64 - 1, y as i64);
+        let what = if self.simulation.falling_rock().any(|r| r == p) {
+            "A falling rock"
+        } else if self.simulation.stopped_rocks.get(p) == Some(&true) {
+            "A rock at rest"
+        } else {
+            "Air"
+        };
+        Some(format!(
+            "{what}, {} from the left wall and {y} above the floor",
+            p.x
+        ))
     }
 
-    fn drop_rock(&mut self) {
-        let rock = ROCKS[self.rock_cycle];
-        let mut x_offset: usize = 2;
-        let mut y_offset: usize = 3 + self.stopped_rocks.len();
-        loop {
-            let new_x_offset = match self.wind_directions[self.wind_cycle] {
-                Direction::Left => x_offset.checked_sub(1),
-                Direction::Right => x_offset.checked_add(1),
-            };
-            if self.valid_position(&rock, new_x_offset, Some(y_offset)) {
-                x_offset = new_x_offset.unwrap();
-            }
-            self.wind_cycle = (self.wind_cycle + 1) % self.wind_directions.len();
-            if self.valid_position(&rock, Some(x_offset), y_offset.checked_sub(1)) {
-                y_offset -= 1;
-            } else {
-                self.solidify_rock(&rock, x_offset, y_offset);
-                break;
-            }
+    fn summary(&self) -> String {
+        if self.is_finished() {
+            format!(
+                "Finished with the tower {} tall after {} rocks",
+                self.height(),
+                self.stopped
+            )
+        } else {
+            format!(
+                "{} of {} rocks at rest, with the tower {} tall",
+                self.stopped,
+                self.rocks,
+                self.height()
+            )
         }
-        self.rock_cycle = (self.rock_cycle + 1) % ROCKS.len();
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut simulation = Simulation::new(input);
-    for _ in 0..2022 {
+fn check_width(params: &Params) -> Result<(), SolveError> {
+    if params.width < MIN_WIDTH {
+        return Err(SolveError::Invalid(
+            "The chamber is too narrow for the rocks to appear in",
+        ));
+    }
+    Ok(())
+}
+
+pub fn part_one(input: &str) -> Result<usize, SolveError> {
+    part_one_observed(input, &mut ())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    part_one_observed_with(input, params, &mut ())
+}
+
+/// Like [`part_one`], showing `observer` the top of the tower after each rock comes to rest.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, SolveError> {
+    height_after(input, 7, 2022, observer)
+}
+
+/// Like [`part_one_observed`], with the chamber and rocks set up by `params`.
+pub fn part_one_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    check_width(params)?;
+    height_after(input, params.width, params.rocks, observer)
+}
+
+fn height_after(
+    input: &str,
+    width: usize,
+    rocks: u64,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    let mut simulation = Simulation::new(input, width)?;
+    for _ in 0..rocks {
         simulation.drop_rock();
+        observer.frame(&simulation.render(40));
     }
-    simulation.stopped_rocks.len()
+    Ok(simulation.stopped_rocks.height())
+}
+
+pub fn part_two(input: &str) -> Result<usize, SolveError> {
+    cycling_height_after(input, 7, 1000000000000)
 }
 
-pub fn part_two(input: &str) -> usize {
-    let mut simulation = Simulation::new(input);
-    let mut cycle_detector = HashMap::<(Vec<[bool; 7]>, usize, usize), (usize, u64)>::new();
+pub fn part_two_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    check_width(params)?;
+    cycling_height_after(input, params.width, params.rocks_part_two)
+}
+
+/// The height after `goal` rocks, found by skipping ahead once the tower starts repeating.
+fn cycling_height_after(input: &str, width: usize, goal: u64) -> Result<usize, SolveError> {
+    let mut simulation = Simulation::new(input, width)?;
+    // the height and rock count when each state was last seen, and how much they had grown by
+    // since the time before
+    let mut cycle_detector =
+        HashMap::<(Vec<bool>, usize, usize), (usize, u64, Option<(usize, u64)>)>::new();
     let mut counter: u64 = 0;
     let mut cave_height_increase = 0;
-    let goal: u64 = 1000000000000;
     let mut jumped = false;
-    loop {
+    while counter < goal {
         simulation.drop_rock();
         counter += 1;
-        if !jumped {
-            if let Some(last_rows) = simulation
+        if !jumped && simulation.stopped_rocks.height() >= 9 {
+            let last_rows = simulation
                 .stopped_rocks
-                .len()
-                .checked_sub(9)
-                .and_then(|s| simulation.stopped_rocks.get(s..))
-                .map(|s| s.to_vec())
-            {
-                match cycle_detector.entry((
-                    last_rows,
-                    simulation.rock_cycle,
-                    simulation.wind_cycle,
-                )) {
-                    hash_map::Entry::Occupied(o) => {
-                        let repeats = (goal - counter) / (counter - o.get().1);
-                        cave_height_increase +=
-                            repeats as usize * (simulation.stopped_rocks.len() - o.get().0);
-                        counter += repeats * (counter - o.get().1);
+                .rows()
+                .rev()
+                .take(9)
+                .flatten()
+                .copied()
+                .collect();
+            let height = simulation.stopped_rocks.height();
+            match cycle_detector.entry((last_rows, simulation.rock_cycle, simulation.wind_cycle)) {
+                hash_map::Entry::Occupied(mut o) => {
+                    let (last_height, last_counter, last_growth) = *o.get();
+                    let growth = (height - last_height, counter - last_counter);
+                    // the top rows can match before the tower below them has settled into its
+                    // cycle, so only trust a cycle once it has repeated the same way twice
+                    if last_growth == Some(growth) {
+                        let repeats = (goal - counter) / growth.1;
+                        cave_height_increase += repeats as usize * growth.0;
+                        counter += repeats * growth.1;
                         jumped = true;
+                    } else {
+                        o.insert((height, counter, Some(growth)));
                     }
-                    hash_map::Entry::Vacant(v) => {
-                        v.insert((simulation.stopped_rocks.len(), counter));
-                    }
+                }
+                hash_map::Entry::Vacant(v) => {
+                    v.insert((height, counter, None));
                 }
             }
         }
-        if counter == goal {
-            return cave_height_increase + simulation.stopped_rocks.len();
-        }
+    }
+    Ok(cave_height_increase + simulation.stopped_rocks.height())
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(usize, usize), SolveError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day17 {
+    pub params: Params,
+}
+
+impl Solution for Day17 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        part_one_with(input, &self.params)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        part_two_with(input, &self.params)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        part_one_observed_with(input, &self.params, observer)
+    }
+
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        // part two skips ahead once the tower repeats, so there's nothing to step through
+        (part == 1).then(|| {
+            RockFall::new(input, &self.params).map(|fall| Box::new(fall) as Box<dyn Stepper>)
+        })
     }
 }
 
@@ -231,21 +490,96 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 3068);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(3068));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 3048);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(3048));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 1514285714288);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(1514285714288));
+    }
+
+    #[test]
+    fn example_in_a_wider_chamber() {
+        let example = include_str!("../example.txt");
+        let params = Params {
+            width: 9,
+            rocks: 100,
+            rocks_part_two: 10000,
+        };
+        let (height, many_height) = solve_with(example, &params).unwrap();
+        assert_eq!(height, height_after(example, 9, 100, &mut ()).unwrap());
+        // skipping ahead has to land on the same height as dropping every rock
+        assert_eq!(
+            many_height,
+            height_after(example, 9, 10000, &mut ()).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_a_narrow_chamber() {
+        let params = Params {
+            width: 5,
+            ..Params::default()
+        };
+        assert_eq!(
+            part_one_with(include_str!("../example.txt"), &params),
+            Err(SolveError::Invalid(
+                "The chamber is too narrow for the rocks to appear in"
+            ))
+        );
+        assert_eq!(
+            Params::default().set("width", "3"),
+            Err(ParamError::Invalid("a width of at least 6"))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_or_unknown_jets() {
+        assert_eq!(
+            part_one("\n"),
+            Err(SolveError::Invalid("There are no jets of gas"))
+        );
+        let Err(SolveError::Parse(error)) = part_two_with("<<x>\n", &Params::default()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((error.line, error.column, error.found), (1, 3, Some('x')));
+        assert!(RockFall::new("", &Params::default()).is_err());
+    }
+
+    #[test]
+    fn steps_one_push_and_fall_at_a_time() {
+        let mut fall = RockFall::new(include_str!("../example.txt"), &Params::default()).unwrap();
+        fall.step();
+        let drawing = fall.render();
+        let bottom: Vec<_> = drawing.lines().skip(DRAWN_ROWS - 3).collect();
+        assert_eq!(bottom, ["|...@@@@|", "|.......|", "|.......|"]);
+        assert_eq!(
+            fall.inspect(4, DRAWN_ROWS - 3).as_deref(),
+            Some("A falling rock, 3 from the left wall and 2 above the floor")
+        );
+        assert_eq!(fall.inspect(0, 0).as_deref(), Some("The chamber's wall"));
+        // the first rock comes to rest after being pushed four times
+        fall.run_to(4);
+        assert_eq!(fall.height(), 1);
+        assert_eq!(
+            fall.summary(),
+            "1 of 2022 rocks at rest, with the tower 1 tall"
+        );
+        fall.run_to(u64::MAX);
+        assert!(fall.is_finished());
+        assert_eq!(fall.height(), 3068);
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 1504093567249);
+        assert_eq!(
+            part_two(include_str!("../challenge.txt")),
+            Ok(1504093567249)
+        );
     }
 }
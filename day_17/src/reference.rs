@@ -0,0 +1,148 @@
+//! A slow but simple solution, to check the fast one against.
+//!
+//! The tower still has to be skipped ahead to reach a trillion rocks, but only once the whole
+//! state has repeated: which rock and jet come next, and every tile a falling rock could still
+//! reach from above the tower. Some jet patterns leave a column open all the way down, so that
+//! the state never repeats, and then there is no answer.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// How many open tiles there can be before giving up on the tower repeating.
+const MAX_OPEN_TILES: usize = 1000;
+
+/// The most rocks that are simply dropped rather than looking for the tower to repeat.
+const MAX_DROPPED: u64 = 100000;
+
+/// The rocks in the order they fall, as offsets from their bottom left corner.
+const ROCKS: [&[(i64, i64)]; 5] = [
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+struct Chamber {
+    jets: Vec<i64>,
+    rocks: HashSet<(i64, i64)>,
+    height: i64,
+    next_rock: usize,
+    next_jet: usize,
+}
+
+impl Chamber {
+    fn free(&self, (x, y): (i64, i64)) -> bool {
+        (0..7).contains(&x) && y >= 0 && !self.rocks.contains(&(x, y))
+    }
+
+    fn drop_rock(&mut self) {
+        let shape = ROCKS[self.next_rock];
+        self.next_rock = (self.next_rock + 1) % ROCKS.len();
+        let fits = |chamber: &Chamber, (x, y): (i64, i64)| {
+            shape.iter().all(|&(dx, dy)| chamber.free((x + dx, y + dy)))
+        };
+        let mut corner = (2, self.height + 3);
+        loop {
+            let pushed = (corner.0 + self.jets[self.next_jet], corner.1);
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            if fits(self, pushed) {
+                corner = pushed;
+            }
+            let fallen = (corner.0, corner.1 - 1);
+            if !fits(self, fallen) {
+                break;
+            }
+            corner = fallen;
+        }
+        for &(dx, dy) in shape {
+            self.rocks.insert((corner.0 + dx, corner.1 + dy));
+            self.height = self.height.max(corner.1 + dy + 1);
+        }
+    }
+
+    /// Every empty tile that can be reached from above the tower, measured down from its top,
+    /// unless there are too many.
+    fn open_tiles(&self) -> Option<BTreeSet<(i64, i64)>> {
+        let mut open = BTreeSet::from([(0, 0)]);
+        let mut stack = vec![(0, self.height)];
+        while let Some((x, y)) = stack.pop() {
+            for next in [(x - 1, y), (x + 1, y), (x, y - 1)] {
+                if next.1 <= self.height
+                    && self.free(next)
+                    && open.insert((next.0, self.height - next.1))
+                {
+                    stack.push(next);
+                }
+            }
+            if open.len() > MAX_OPEN_TILES {
+                return None;
+            }
+        }
+        Some(open)
+    }
+}
+
+fn height_after(input: &str, rocks: u64) -> Option<u64> {
+    let jets = input
+        .trim_end()
+        .chars()
+        .map(|c| match c {
+            '<' => Some(-1),
+            '>' => Some(1),
+            _ => None,
+        })
+        .collect::<Option<Vec<i64>>>()?;
+    if jets.is_empty() {
+        return None;
+    }
+    let mut chamber = Chamber {
+        jets,
+        rocks: HashSet::new(),
+        height: 0,
+        next_rock: 0,
+        next_jet: 0,
+    };
+    let mut seen = HashMap::new();
+    let mut skipped = 0;
+    let mut dropped = 0;
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+        if skipped > 0 || rocks <= MAX_DROPPED {
+            continue;
+        }
+        let state = (chamber.next_rock, chamber.next_jet, chamber.open_tiles()?);
+        if let Some((before, height)) = seen.insert(state, (dropped, chamber.height)) {
+            let cycles = (rocks - dropped) / (dropped - before);
+            skipped = cycles * (chamber.height - height) as u64;
+            dropped += cycles * (dropped - before);
+        }
+    }
+    Some(chamber.height as u64 + skipped)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    height_after(input, 2022).map(|height| height as usize)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    height_after(input, 1000000000000).map(|height| height as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(part_one(include_str!("../example.txt")), Some(3068));
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(
+            part_two(include_str!("../example.txt")),
+            Some(1514285714288)
+        );
+    }
+}
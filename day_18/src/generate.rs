@@ -0,0 +1,118 @@
+//! Random droplet scans with known answers, for testing.
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated droplet is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// Every coordinate is less than this
+    pub side: usize,
+    /// How likely each position is to hold a cube of lava
+    pub density: f64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            side: 8,
+            density: 0.5,
+        }
+    }
+}
+
+const FACES: [(i64, i64, i64); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// Counts the faces of lava next to tiles for which `open` holds, with the droplet held in
+/// a box a cube larger than it on every side.
+fn faces(lava: &[Vec<Vec<bool>>], open: impl Fn(usize, usize, usize) -> bool) -> usize {
+    let side = lava.len() as i64;
+    let mut count = 0;
+    for x in 1..side - 1 {
+        for y in 1..side - 1 {
+            for z in 1..side - 1 {
+                if lava[x as usize][y as usize][z as usize] {
+                    count += FACES
+                        .iter()
+                        .filter(|(dx, dy, dz)| {
+                            open((x + dx) as usize, (y + dy) as usize, (z + dz) as usize)
+                        })
+                        .count();
+                }
+            }
+        }
+    }
+    count
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let side = size.side.max(1);
+    let mut input = String::new();
+    let mut lava = vec![vec![vec![false; side + 2]; side + 2]; side + 2];
+    for x in 0..side {
+        for y in 0..side {
+            for z in 0..side {
+                if rng.gen_bool(size.density) {
+                    input += &format!("{x},{y},{z}\n");
+                    lava[x + 1][y + 1][z + 1] = true;
+                }
+            }
+        }
+    }
+    if input.is_empty() {
+        input += "0,0,0\n";
+        lava[1][1][1] = true;
+    }
+
+    // fill the air from a corner of the box, which is outside the droplet
+    let mut outside = vec![vec![vec![false; side + 2]; side + 2]; side + 2];
+    let mut stack = vec![(0, 0, 0)];
+    outside[0][0][0] = true;
+    while let Some((x, y, z)) = stack.pop() {
+        for (dx, dy, dz) in FACES {
+            let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+            if [nx, ny, nz]
+                .iter()
+                .all(|&c| (0..side as i64 + 2).contains(&c))
+            {
+                let (ux, uy, uz) = (nx as usize, ny as usize, nz as usize);
+                if !lava[ux][uy][uz] && !outside[ux][uy][uz] {
+                    outside[ux][uy][uz] = true;
+                    stack.push((nx, ny, nz));
+                }
+            }
+        }
+    }
+
+    Generated {
+        part_one: Some(faces(&lava, |x, y, z| !lava[x][y][z])),
+        part_two: Some(faces(&lava, |x, y, z| outside[x][y][z])),
+        input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let size = Size {
+                density: seed as f64 / 100.0,
+                ..Size::default()
+            };
+            let generated = generate(seed, &size);
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,17 +1,23 @@
-use std::{collections::BTreeSet, mem::take};
+use std::collections::BTreeSet;
 
+use aoc_core::{
+    parse::{final_parse, lines, IResult},
+    ParseError, Solution, SolveError,
+};
 use nom::{
-    character::complete::{char, digit1, newline},
-    combinator::{iterator, map_res},
-    sequence::{terminated, tuple},
-    IResult,
+    character::complete::{char, digit1},
+    combinator::map_res,
+    sequence::tuple,
 };
 
-#[derive(Debug, Default)]
+#[cfg(feature = "generate")]
+pub mod generate;
+
+#[derive(Debug)]
 struct Scan(BTreeSet<(i8, i8, i8)>);
 
 impl Scan {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         fn parse_rock(input: &str) -> IResult<&str, (i8, char, i8, char, i8)> {
             tuple((
                 map_res(digit1, str::parse),
@@ -21,11 +27,11 @@ impl Scan {
                 map_res(digit1, str::parse),
             ))(input)
         }
-        let mut rocks = BTreeSet::new();
-        for (x, _, y, _, z) in &mut iterator(input, terminated(parse_rock, newline)) {
-            rocks.insert((x, y, z));
-        }
-        Self(rocks)
+        let rocks = final_parse(input, lines(parse_rock))?
+            .into_iter()
+            .map(|(x, _, y, _, z)| (x, y, z))
+            .collect();
+        Ok(Self(rocks))
     }
 
     fn surface_area(&self) -> usize {
@@ -43,12 +49,12 @@ impl Scan {
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    Scan::new(input).surface_area()
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    Ok(Scan::new(input)?.surface_area())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let rock_scan = Scan::new(input).0;
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let rock_scan = Scan::new(input)?.0;
     let x_min = rock_scan
         .iter()
         .map(|&(x, _, _)| x)
@@ -85,43 +91,49 @@ pub fn part_two(input: &str) -> usize {
         .max()
         .unwrap_or_default()
         + 1;
-    let mut external_scan = Scan::default();
-    external_scan.0.insert((x_min, y_min, z_min));
-    let mut recent = vec![(x_min, y_min, z_min)];
-    let mut visit = |(x, y, z): (i8, i8, i8), recent: &mut Vec<(i8, i8, i8)>| {
-        if !rock_scan.contains(&(x, y, z)) && external_scan.0.insert((x, y, z)) {
-            recent.push((x, y, z));
-        }
-    };
-
-    while !recent.is_empty() {
-        for &(x, y, z) in take(&mut recent).iter() {
-            if x > x_min {
-                visit((x - 1, y, z), &mut recent);
-            }
-            if x < x_max {
-                visit((x + 1, y, z), &mut recent);
-            }
-            if y > y_min {
-                visit((x, y - 1, z), &mut recent);
-            }
-            if y < y_max {
-                visit((x, y + 1, z), &mut recent);
-            }
-            if z > z_min {
-                visit((x, y, z - 1), &mut recent);
-            }
-            if z < z_max {
-                visit((x, y, z + 1), &mut recent);
-            }
-        }
-    }
+    let external_scan = Scan(
+        search::bfs_all((x_min, y_min, z_min), |&(x, y, z)| {
+            [
+                (x - 1, y, z),
+                (x + 1, y, z),
+                (x, y - 1, z),
+                (x, y + 1, z),
+                (x, y, z - 1),
+                (x, y, z + 1),
+            ]
+            .into_iter()
+            .filter(|&(x, y, z)| {
+                (x_min..=x_max).contains(&x)
+                    && (y_min..=y_max).contains(&y)
+                    && (z_min..=z_max).contains(&z)
+                    && !rock_scan.contains(&(x, y, z))
+            })
+        })
+        .iter()
+        .map(|(&cube, _)| cube)
+        .collect(),
+    );
 
     let width = (x_max + 1 - x_min) as usize;
     let height = (y_max + 1 - y_min) as usize;
     let depth = (z_max + 1 - z_min) as usize;
 
-    external_scan.surface_area() - width * height * 2 - width * depth * 2 - height * depth * 2
+    Ok(external_scan.surface_area() - width * height * 2 - width * depth * 2 - height * depth * 2)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
 }
 
 #[cfg(test)]
@@ -130,21 +142,21 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 64);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(64));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 3650);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(3650));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 58);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(58));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 2118);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(2118));
     }
 }
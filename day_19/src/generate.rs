@@ -0,0 +1,80 @@
+//! Random blueprints with known answers, for testing.
+
+use aoc_core::Generated;
+
+use crate::reference;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated list of blueprints is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub blueprints: usize,
+    /// The most ore any robot costs
+    pub max_ore: u32,
+    /// The most clay an obsidian robot costs
+    pub max_clay: u32,
+    /// The most obsidian a geode robot costs
+    pub max_obsidian: u32,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            blueprints: 3,
+            max_ore: 4,
+            max_clay: 20,
+            max_obsidian: 20,
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<u32, u32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ore = 2..=size.max_ore.max(2);
+    let mut input = String::new();
+    for id in 1..=size.blueprints.max(1) {
+        input += &format!(
+            "Blueprint {id}: \
+             Each ore robot costs {} ore. \
+             Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.\n",
+            rng.gen_range(ore.clone()),
+            rng.gen_range(ore.clone()),
+            rng.gen_range(ore.clone()),
+            rng.gen_range(2..=size.max_clay.max(2)),
+            rng.gen_range(ore.clone()),
+            rng.gen_range(2..=size.max_obsidian.max(2)),
+        );
+    }
+    Generated {
+        part_one: reference::part_one(&input),
+        part_two: reference::part_two(&input),
+        input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::smallest_disagreement;
+
+    #[test]
+    fn matches_the_reference() {
+        let size = Size {
+            blueprints: 1,
+            ..Size::default()
+        };
+        // some blueprints take the solution seconds in 32 minutes, so only a few are tried
+        let (part_one, part_two): (Vec<_>, Vec<_>) = (0..4)
+            .map(|seed| generate(seed, &size))
+            .map(|g| ((g.input.clone(), g.part_one), (g.input, g.part_two)))
+            .unzip();
+        if let Some(disagreement) = smallest_disagreement(part_one, |i| crate::part_one(i).ok()) {
+            panic!("part one {disagreement}");
+        }
+        if let Some(disagreement) = smallest_disagreement(part_two, |i| crate::part_two(i).ok()) {
+            panic!("part two {disagreement}");
+        }
+    }
+}
@@ -1,15 +1,65 @@
 use std::{collections::HashSet, iter::zip, mem::take};
+#[cfg(not(target_family = "wasm"))]
+use std::{sync::mpsc, thread};
 
+use aoc_core::{
+    parse::{final_parse, list, tag, IResult},
+    parse_param, Observer, ParamError, Parameters, ParseError, Solution, SolveError,
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::{char, digit1, multispace1},
     combinator::{map, map_res},
-    multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
 use rayon::prelude::*;
 
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod reference;
+
+/// How long the robots have and how many blueprints survive the elephants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The minutes each blueprint is tried for in part one
+    pub minutes: u8,
+    /// The minutes each blueprint is tried for in part two
+    pub minutes_part_two: u8,
+    /// How many of the first blueprints are left in part two
+    pub blueprints_part_two: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            minutes: 24,
+            minutes_part_two: 32,
+            blueprints_part_two: 3,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "minutes" => self.minutes = parse_param(value, "a number of minutes up to 255")?,
+            "minutes_part_two" => {
+                self.minutes_part_two = parse_param(value, "a number of minutes up to 255")?
+            }
+            "blueprints_part_two" => {
+                self.blueprints_part_two = parse_param(value, "a number of blueprints")?
+            }
+            _ => {
+                return Err(ParamError::Unknown(&[
+                    "minutes",
+                    "minutes_part_two",
+                    "blueprints_part_two",
+                ]))
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Hash, Eq, PartialEq)]
 struct Blueprint {
     id: u32,
@@ -90,26 +140,20 @@ impl Blueprint {
         )(input)
     }
 
-    fn geode_count(&self, rounds: u8) -> u32 {
+    /// The most geodes that can be opened in `rounds` minutes, calling `round_done` after each.
+    fn geode_count(&self, rounds: u8, round_done: &(dyn Fn() + Sync)) -> u32 {
         let start = World {
             robot_counts: [1, 0, 0, 0],
             resource_counts: [0, 0, 0, 0],
             blueprint: self,
         };
         let mut next_possibilities = vec![start];
+        // in the last two minutes only geode robots are worth building
+        let endgame = rounds.saturating_sub(2);
         for i in 0..rounds {
-            #[cfg(feature = "show-progress")]
-            if i >= 23 {
-                println!(
-                    "{} beginning round {}, {}",
-                    self.id,
-                    i,
-                    next_possibilities.len()
-                );
-            }
             let mut possibilities = HashSet::<World>::new();
 
-            if i <= rounds - 2 {
+            if i <= endgame {
                 next_possibilities.sort_by_cached_key(|p| {
                     p.robot_counts.iter().sum::<u32>() + p.resource_counts.iter().sum::<u32>()
                 });
@@ -128,17 +172,9 @@ impl Blueprint {
                     .then_some(*p)
                 })
                 .collect();
-                #[cfg(feature = "show-progress")]
-                if i >= 23 {
-                    println!(
-                        "{} second part of round {}",
-                        self.id,
-                        next_possibilities.len()
-                    );
-                }
             }
             for possibility in take(&mut next_possibilities).iter() {
-                if i >= rounds - 2 {
+                if i >= endgame {
                     if let Some(possibility) = possibility.build_robot_and_collect(3) {
                         next_possibilities.push(possibility);
                     } else {
@@ -167,6 +203,7 @@ impl Blueprint {
                     }
                 }
             }
+            round_done();
         }
         next_possibilities
             .iter()
@@ -217,27 +254,154 @@ impl<'a> World<'a> {
     }
 }
 
-fn blueprints(input: &str) -> Vec<Blueprint> {
-    separated_list1(multispace1, Blueprint::parse_nom)(input)
-        .unwrap()
-        .1
+fn blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    final_parse(input, list(Blueprint::parse_nom, multispace1))
+}
+
+/// Runs `work` on another thread, passing `observer` the progress through `total` rounds as
+/// `work` reports each one finished.
+///
+/// The observer stays on this thread, so it doesn't need to be shared with rayon's workers.
+#[cfg(not(target_family = "wasm"))]
+fn with_progress<T: Send>(
+    total: u64,
+    observer: &mut dyn Observer,
+    work: impl FnOnce(&(dyn Fn() + Sync)) -> T + Send,
+) -> T {
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        let worker = scope.spawn(move || {
+            work(&|| {
+                // the receiver only goes away if the observer panicked
+                let _ = sender.send(());
+            })
+        });
+        for (done, ()) in (1..).zip(receiver) {
+            observer.progress(done, total);
+        }
+        worker
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+/// Runs `work` here, as there are no threads in the browser, so the progress is only reported
+/// once it's all done.
+#[cfg(target_family = "wasm")]
+fn with_progress<T: Send>(
+    total: u64,
+    observer: &mut dyn Observer,
+    work: impl FnOnce(&(dyn Fn() + Sync)) -> T + Send,
+) -> T {
+    let result = work(&|| {});
+    observer.progress(total, total);
+    result
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    part_one_observed(input, &mut ())
+}
+
+pub fn part_one_with(input: &str, params: &Params) -> Result<u32, ParseError> {
+    part_one_observed_with(input, params, &mut ())
+}
+
+/// Like [`part_one`], reporting progress through the blueprints' rounds to `observer`.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<u32, ParseError> {
+    part_one_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_one_observed`], with the time set by `params`.
+pub fn part_one_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<u32, ParseError> {
+    let blueprints = blueprints(input)?;
+    let minutes = params.minutes;
+    Ok(with_progress(
+        blueprints.len() as u64 * minutes as u64,
+        observer,
+        |round_done| {
+            blueprints
+                .par_iter()
+                .map(|bp| bp.id * bp.geode_count(minutes, round_done))
+                .sum()
+        },
+    ))
+}
+
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    part_two_observed(input, &mut ())
 }
 
-pub fn part_one(input: &str) -> u32 {
-    let blueprints = blueprints(input);
-    blueprints
-        .par_iter()
-        .map(|bp| bp.id * bp.geode_count(24))
-        .sum()
+pub fn part_two_with(input: &str, params: &Params) -> Result<u32, ParseError> {
+    part_two_observed_with(input, params, &mut ())
 }
 
-pub fn part_two(input: &str) -> u32 {
-    let blueprints = blueprints(input);
-    blueprints
-        .par_iter()
-        .take(3)
-        .map(|bp| bp.geode_count(32))
-        .product()
+/// Like [`part_two`], reporting progress through the blueprints' rounds to `observer`.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<u32, ParseError> {
+    part_two_observed_with(input, &Params::default(), observer)
+}
+
+/// Like [`part_two_observed`], with the time and blueprints set by `params`.
+pub fn part_two_observed_with(
+    input: &str,
+    params: &Params,
+    observer: &mut dyn Observer,
+) -> Result<u32, ParseError> {
+    let blueprints = blueprints(input)?;
+    let (minutes, kept) = (params.minutes_part_two, params.blueprints_part_two);
+    Ok(with_progress(
+        blueprints.len().min(kept) as u64 * minutes as u64,
+        observer,
+        |round_done| {
+            blueprints
+                .par_iter()
+                .take(kept)
+                .map(|bp| bp.geode_count(minutes, round_done))
+                .product()
+        },
+    ))
+}
+
+/// Both parts, with the puzzle set up by `params`.
+pub fn solve_with(input: &str, params: &Params) -> Result<(u32, u32), ParseError> {
+    Ok((part_one_with(input, params)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day19 {
+    pub params: Params,
+}
+
+impl Solution for Day19 {
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn part_one(&self, input: &str) -> Result<u32, SolveError> {
+        Ok(part_one_with(input, &self.params)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<u32, SolveError> {
+        Ok(part_two_with(input, &self.params)?)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u32, SolveError> {
+        Ok(part_one_observed_with(input, &self.params, observer)?)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<u32, SolveError> {
+        Ok(part_two_observed_with(input, &self.params, observer)?)
+    }
 }
 
 #[cfg(test)]
@@ -246,21 +410,66 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 33);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(33));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 1962);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(1962));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 56 * 62);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(56 * 62));
+    }
+
+    #[test]
+    fn example_with_part_one_time_for_both_blueprints() {
+        let params = Params {
+            minutes: 24,
+            minutes_part_two: 24,
+            blueprints_part_two: 2,
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((33, 9 * 12))
+        );
+    }
+
+    #[test]
+    fn opens_no_geodes_in_under_two_minutes() {
+        for minutes in ["0", "1"] {
+            let mut params = Params::default();
+            params.set("minutes", minutes).unwrap();
+            params.set("minutes_part_two", minutes).unwrap();
+            assert_eq!(
+                solve_with(include_str!("../example.txt"), &params),
+                Ok((0, 0))
+            );
+        }
+    }
+
+    #[test]
+    fn reports_progress_from_other_threads() {
+        struct Progress(Vec<(u64, u64)>);
+
+        impl Observer for Progress {
+            fn progress(&mut self, done: u64, total: u64) {
+                self.0.push((done, total));
+            }
+        }
+
+        let mut progress = Progress(Vec::new());
+        let answer = with_progress(3, &mut progress, |round_done| {
+            (0..3).into_par_iter().for_each(|_| round_done());
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(progress.0, [(1, 3), (2, 3), (3, 3)]);
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 88160);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(88160));
     }
 }
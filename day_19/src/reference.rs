@@ -0,0 +1,122 @@
+//! A slow but simple solution, to check the fast one against.
+//!
+//! It tries every order of building robots, waiting as long as needed for each, and only cuts
+//! off orders that provably can't do better: building more of a robot than could be spent in a
+//! minute, or carrying on when even a new geode robot every minute wouldn't beat the best so far.
+
+/// Ore, clay, obsidian and geode.
+type Amounts = [u32; 4];
+
+struct Blueprint {
+    id: u32,
+    /// What each robot costs, in the same order as the amounts
+    costs: [Amounts; 4],
+}
+
+impl Blueprint {
+    fn parse(text: &str) -> Option<Blueprint> {
+        let numbers: Vec<u32> = text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        let &[id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+            numbers.as_slice()
+        else {
+            return None;
+        };
+        Some(Blueprint {
+            id,
+            costs: [
+                [ore, 0, 0, 0],
+                [clay, 0, 0, 0],
+                [obsidian_ore, obsidian_clay, 0, 0],
+                [geode_ore, 0, geode_obsidian, 0],
+            ],
+        })
+    }
+
+    fn most_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+        self.search(minutes, [1, 0, 0, 0], [0; 4], &mut best);
+        best
+    }
+
+    fn search(&self, minutes: u32, robots: Amounts, resources: Amounts, best: &mut u32) {
+        let idle = resources[3] + robots[3] * minutes;
+        *best = (*best).max(idle);
+        if idle + minutes * minutes.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+        for (robot, cost) in self.costs.iter().enumerate() {
+            let most_spent = self.costs.iter().map(|c| c[robot]).max().unwrap();
+            if robot < 3 && robots[robot] >= most_spent {
+                continue;
+            }
+            // the minutes spent gathering for the robot, if it can be afforded at all
+            let Some(waiting) = (0..3)
+                .map(
+                    |r| match (cost[r].saturating_sub(resources[r]), robots[r]) {
+                        (0, _) => Some(0),
+                        (_, 0) => None,
+                        (needed, robots) => Some(needed.div_ceil(robots)),
+                    },
+                )
+                .try_fold(0, |most, wait| Some(most.max(wait?)))
+            else {
+                continue;
+            };
+            if waiting + 1 >= minutes {
+                continue;
+            }
+            let elapsed = waiting + 1;
+            let mut next_robots = robots;
+            next_robots[robot] += 1;
+            let next_resources = [0, 1, 2, 3].map(|r| resources[r] + robots[r] * elapsed - cost[r]);
+            self.search(minutes - elapsed, next_robots, next_resources, best);
+        }
+    }
+}
+
+fn blueprints(input: &str) -> Option<Vec<Blueprint>> {
+    input
+        .split("Blueprint")
+        .filter(|text| !text.trim().is_empty())
+        .map(Blueprint::parse)
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(
+        blueprints(input)?
+            .iter()
+            .map(|blueprint| blueprint.id * blueprint.most_geodes(24))
+            .sum(),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(
+        blueprints(input)?
+            .iter()
+            .take(3)
+            .map(|blueprint| blueprint.most_geodes(32))
+            .product(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(part_one(include_str!("../example.txt")), Some(33));
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(part_two(include_str!("../example.txt")), Some(56 * 62));
+    }
+}
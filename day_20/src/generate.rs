@@ -0,0 +1,95 @@
+//! Random encrypted files with known answers, for testing.
+
+use alloc::{format, vec::Vec};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated file is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub numbers: usize,
+    /// The largest magnitude of any number, which is kept small so that numbers repeat
+    pub max_magnitude: i64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            numbers: 50,
+            max_magnitude: 20,
+        }
+    }
+}
+
+/// Mixes by moving each number one place at a time around the circle, returning the numbers
+/// in the order they end up in starting from anywhere.
+fn mix_slowly(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let len = numbers.len();
+    let mut circle: Vec<usize> = (0..len).collect();
+    for _ in 0..rounds {
+        for (original, &number) in numbers.iter().enumerate() {
+            let mut position = circle.iter().position(|&i| i == original).unwrap();
+            // moving all the way round past the other numbers leaves the order unchanged
+            let moves = number.unsigned_abs() % (len as u64 - 1);
+            for _ in 0..moves {
+                let next = if number > 0 {
+                    (position + 1) % len
+                } else {
+                    (position + len - 1) % len
+                };
+                circle.swap(position, next);
+                position = next;
+            }
+        }
+    }
+    circle.into_iter().map(|i| numbers[i]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed.iter().position(|&n| n == 0).unwrap();
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<i64, i64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let magnitude = size.max_magnitude.max(1);
+    let mut numbers: Vec<i64> = (0..size.numbers.max(2))
+        .map(|_| match rng.gen_range(-magnitude..magnitude) {
+            0 => magnitude,
+            n => n,
+        })
+        .collect();
+    // there is exactly one zero
+    let zero = rng.gen_range(0..numbers.len());
+    numbers[zero] = 0;
+
+    let input = numbers.iter().map(|n| format!("{n}\n")).collect();
+    let decrypted: Vec<i64> = numbers.iter().map(|n| n * 811589153).collect();
+    Generated {
+        input,
+        part_one: Some(grove_coordinates(&mix_slowly(&numbers, 1))),
+        part_two: Some(grove_coordinates(&mix_slowly(&decrypted, 10))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let size = Size {
+                numbers: 2 + seed as usize,
+                ..Size::default()
+            };
+            let generated = generate(seed, &size);
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,13 +1,58 @@
-use std::iter::zip;
+#![no_std]
 
-fn parse(input: &str) -> Vec<i64> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::iter::zip;
+
+use aoc_core::{
+    parse::{final_parse, lines},
+    parse_param, ParamError, Parameters, ParseError, Solution, SolveError,
+};
+use nom::character::complete::i64 as number;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// How part two decrypts the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// What every number is multiplied by before mixing in part two
+    pub decryption_key: i64,
+    /// How many times the numbers are mixed in part two
+    pub rounds: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            decryption_key: 811589153,
+            rounds: 10,
+        }
+    }
+}
+
+impl Parameters for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "decryption_key" => self.decryption_key = parse_param(value, "a whole number")?,
+            "rounds" => self.rounds = parse_param(value, "a number of rounds")?,
+            _ => return Err(ParamError::Unknown(&["decryption_key", "rounds"])),
+        }
+        Ok(())
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    final_parse(input, lines(number))
 }
 
 fn mix(input: &[i64], order: &mut Vec<i64>) -> Vec<i64> {
-    for (i, &num) in zip(0.., input.iter()) {
+    // a number moving all the way round the others ends up where it started
+    let others = input.len() as i64 - 1;
+    for (i, &num) in zip(0.., input.iter()).filter(|_| others > 0) {
         let current_position = order.iter().position(|&o| o == i).unwrap() as i64;
-        let new_position = (current_position + num).rem_euclid(input.len() as i64 - 1);
+        let new_position = (current_position + num.rem_euclid(others)).rem_euclid(others);
         if current_position != new_position {
             order.remove(current_position as usize);
             order.insert(new_position as usize, i);
@@ -17,53 +62,137 @@ fn mix(input: &[i64], order: &mut Vec<i64>) -> Vec<i64> {
     order.iter().map(|&i| input[i as usize]).collect()
 }
 
-pub fn part_one(input: &str) -> i64 {
-    let nums = parse(input);
+/// The sum of the three numbers 1000, 2000 and 3000 places after the 0 in the mixed file.
+fn grove_coordinates(mixed: &[i64]) -> Result<i64, SolveError> {
+    let p = mixed
+        .iter()
+        .position(|&n| n == 0)
+        .ok_or(SolveError::NoAnswer("The file has no 0"))?;
+    [1000, 2000, 3000]
+        .iter()
+        .try_fold(0i64, |sum, offset| {
+            sum.checked_add(mixed[(p + offset) % mixed.len()])
+        })
+        .ok_or(SolveError::NoAnswer(
+            "The grove coordinates are too big to add up",
+        ))
+}
+
+pub fn part_one(input: &str) -> Result<i64, SolveError> {
+    let nums = parse(input)?;
     let mut order = (0..nums.len() as i64).collect::<Vec<_>>();
-    let mixed = mix(&nums, &mut order);
+    grove_coordinates(&mix(&nums, &mut order))
+}
 
-    let p = mixed.iter().position(|&n| n == 0).unwrap();
-    mixed[(p + 1000) % mixed.len()]
-        + mixed[(p + 2000) % mixed.len()]
-        + mixed[(p + 3000) % mixed.len()]
+pub fn part_two(input: &str) -> Result<i64, SolveError> {
+    part_two_with(input, &Params::default())
 }
 
-pub fn part_two(input: &str) -> i64 {
-    let nums: Vec<i64> = parse(input).iter().map(|n| n * 811589153).collect();
+pub fn part_two_with(input: &str, params: &Params) -> Result<i64, SolveError> {
+    let nums = parse(input)?
+        .iter()
+        .map(|n| n.checked_mul(params.decryption_key))
+        .collect::<Option<Vec<i64>>>()
+        .ok_or(SolveError::Invalid(
+            "The decryption key makes a number too big to mix",
+        ))?;
     let mut order = (0..nums.len() as i64).collect::<Vec<_>>();
-    let mut mixed = Vec::new();
-    for _ in 0..10 {
+    let mut mixed = nums.clone();
+    for _ in 0..params.rounds {
         mixed = mix(&nums, &mut order);
     }
+    grove_coordinates(&mixed)
+}
 
-    let p = mixed.iter().position(|&n| n == 0).unwrap();
-    mixed[(p + 1000) % mixed.len()]
-        + mixed[(p + 2000) % mixed.len()]
-        + mixed[(p + 3000) % mixed.len()]
+/// Both parts, with the puzzle set up by `params`. Only part two has anything to set.
+pub fn solve_with(input: &str, params: &Params) -> Result<(i64, i64), SolveError> {
+    Ok((part_one(input)?, part_two_with(input, params)?))
+}
+
+#[derive(Debug, Default)]
+pub struct Day20 {
+    pub params: Params,
+}
+
+impl Solution for Day20 {
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn part_one(&self, input: &str) -> Result<i64, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<i64, SolveError> {
+        part_two_with(input, &self.params)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use aoc_core::parse::Expected;
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 3);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(3));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 8028);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(8028));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 1623178306);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(1623178306));
+    }
+
+    #[test]
+    fn example_mixed_once_without_a_key() {
+        let params = Params {
+            decryption_key: 1,
+            rounds: 1,
+        };
+        assert_eq!(
+            solve_with(include_str!("../example.txt"), &params),
+            Ok((3, 3))
+        );
+    }
+
+    #[test]
+    fn rejects_keys_that_overflow() {
+        let params = Params {
+            decryption_key: i64::MAX / 2,
+            rounds: 1,
+        };
+        assert_eq!(
+            part_two_with(include_str!("../example.txt"), &params),
+            Err(SolveError::Invalid(
+                "The decryption key makes a number too big to mix"
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_bad_numbers() {
+        assert_eq!(
+            part_one("1\n2\nx\n"),
+            Err(SolveError::Parse(ParseError {
+                line: 3,
+                column: 1,
+                expected: Expected::Described("a digit"),
+                found: Some('x'),
+                within: None,
+            }))
+        );
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 8798438007673);
+        assert_eq!(
+            part_two(include_str!("../challenge.txt")),
+            Ok(8798438007673)
+        );
     }
 }
@@ -0,0 +1,187 @@
+//! Random monkey riddles with known answers, for testing.
+
+use std::collections::HashSet;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How big a generated riddle is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// How many jobs there are between `humn` and `root`
+    pub depth: usize,
+    /// How deep the jobs shouting numbers that don't depend on `humn` go
+    pub branch_depth: usize,
+    pub max_number: i64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            depth: 10,
+            branch_depth: 2,
+            max_number: 20,
+        }
+    }
+}
+
+struct Riddle<'a> {
+    rng: StdRng,
+    size: &'a Size,
+    names: HashSet<String>,
+    jobs: Vec<String>,
+}
+
+impl Riddle<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| char::from(self.rng.gen_range(b'a'..=b'z')))
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// A monkey that shouts `value`, whatever number the human shouts.
+    fn number(&mut self, value: i64) -> String {
+        let name = self.name();
+        if value >= 0 {
+            self.jobs.push(format!("{name}: {value}"));
+        } else {
+            let zero = self.number(0);
+            let magnitude = self.number(-value);
+            self.jobs.push(format!("{name}: {zero} - {magnitude}"));
+        }
+        name
+    }
+
+    /// A monkey that doesn't depend on the human, with the number it shouts.
+    fn branch(&mut self, depth: usize) -> (String, i64) {
+        if depth == 0 || self.rng.gen_bool(0.3) {
+            let value = self.rng.gen_range(1..=self.size.max_number.max(1));
+            return (self.number(value), value);
+        }
+        let (a, a_value) = self.branch(depth - 1);
+        let (b, b_value) = self.branch(depth - 1);
+        let name = self.name();
+        let (op, value) = match self.rng.gen_range(0..4) {
+            0 => ('-', a_value - b_value),
+            1 => ('*', a_value * b_value),
+            2 if b_value != 0 && a_value % b_value == 0 => ('/', a_value / b_value),
+            _ => ('+', a_value + b_value),
+        };
+        self.jobs.push(format!("{name}: {a} {op} {b}"));
+        (name, value)
+    }
+}
+
+/// Builds the jobs up from `humn`, working out what each monkey shouts both for the number the
+/// human is given and for the number that passes root's equality test, only dividing where
+/// that second number divides exactly.
+pub fn generate(seed: u64, size: &Size) -> Generated<i64, i64> {
+    let mut riddle = Riddle {
+        rng: StdRng::seed_from_u64(seed),
+        size,
+        names: HashSet::new(),
+        jobs: Vec::new(),
+    };
+    let given: i64 = riddle.rng.gen_range(1..=1000);
+    let wanted: i64 = riddle.rng.gen_range(1..=1000);
+    riddle.jobs.push(format!("humn: {given}"));
+    let (mut path, mut given_value, mut wanted_value) = (String::from("humn"), given, wanted);
+    for _ in 0..size.depth {
+        let (other, other_value) = riddle.branch(size.branch_depth);
+        let name = riddle.name();
+        let human_first = riddle.rng.gen_bool(0.5);
+        let options: [(char, bool, Option<i64>, Option<i64>); 4] = [
+            (
+                '+',
+                human_first,
+                given_value.checked_add(other_value),
+                wanted_value.checked_add(other_value),
+            ),
+            (
+                '-',
+                human_first,
+                if human_first {
+                    given_value.checked_sub(other_value)
+                } else {
+                    other_value.checked_sub(given_value)
+                },
+                if human_first {
+                    wanted_value.checked_sub(other_value)
+                } else {
+                    other_value.checked_sub(wanted_value)
+                },
+            ),
+            (
+                '*',
+                human_first,
+                given_value.checked_mul(other_value),
+                // multiplying by zero would lose the human's number
+                wanted_value
+                    .checked_mul(other_value)
+                    .filter(|_| other_value != 0),
+            ),
+            (
+                '/',
+                true,
+                given_value.checked_div(other_value),
+                wanted_value
+                    .checked_rem(other_value)
+                    .filter(|&r| r == 0)
+                    .and_then(|_| wanted_value.checked_div(other_value)),
+            ),
+        ];
+        let choice = *options.choose(&mut riddle.rng).unwrap();
+        let (op, human_first, Some(next_given), Some(next_wanted)) = choice else {
+            // the operation would overflow or divide unevenly, so add instead
+            riddle.jobs.push(format!("{name}: {path} + {other}"));
+            (path, given_value, wanted_value) =
+                (name, given_value + other_value, wanted_value + other_value);
+            continue;
+        };
+        riddle.jobs.push(if human_first {
+            format!("{name}: {path} {op} {other}")
+        } else {
+            format!("{name}: {other} {op} {path}")
+        });
+        (path, given_value, wanted_value) = (name, next_given, next_wanted);
+    }
+
+    // the other side of root shouts what the human's side does when the human gets it right
+    let (other, other_value) = riddle.branch(size.branch_depth);
+    let difference = wanted_value - other_value;
+    let matching = riddle.number(difference);
+    let side = riddle.name();
+    riddle.jobs.push(format!("{side}: {other} + {matching}"));
+    riddle.jobs.push(if riddle.rng.gen_bool(0.5) {
+        format!("root: {path} + {side}")
+    } else {
+        format!("root: {side} + {path}")
+    });
+
+    let mut jobs = riddle.jobs;
+    jobs.shuffle(&mut riddle.rng);
+    Generated {
+        input: jobs.iter().map(|job| format!("{job}\n")).collect(),
+        part_one: Some(given_value + wanted_value),
+        part_two: Some(wanted),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,26 +1,35 @@
 use std::collections::HashMap;
 
+use aoc_core::{
+    parse::{final_parse, lines, tag, IResult},
+    Observer, ParseError, Solution, SolveError, Step,
+};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
-    character::complete::{digit1, newline},
-    combinator::{iterator, map, map_res, value},
-    sequence::{terminated, tuple},
-    IResult,
+    bytes::complete::take_while1,
+    character::complete::digit1,
+    combinator::{map, map_res, value},
+    sequence::tuple,
 };
 
-#[derive(Clone, Copy, Debug)]
-enum Operation {
+#[cfg(feature = "generate")]
+pub mod generate;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
     Plus,
     Minus,
     Mul,
     Div,
+    /// What `root` really does in part two. It never appears in the input.
     Eq,
 }
 
-#[derive(Clone, Debug)]
-enum Job<'a> {
+/// What a monkey yells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Job<'a> {
     Number(i64),
+    /// The result of an operation on what monkeys `a` and `b` yell.
     Dependent {
         a: &'a str,
         op: Operation,
@@ -28,6 +37,18 @@ enum Job<'a> {
     },
 }
 
+impl Operation {
+    fn symbol(self) -> &'static str {
+        match self {
+            Operation::Plus => "+",
+            Operation::Minus => "-",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Eq => "=",
+        }
+    }
+}
+
 impl<'a> Job<'a> {
     fn get_value(&self, troop: &Troop) -> Option<i64> {
         match self {
@@ -79,7 +100,7 @@ struct MonkeyJob<'a> {
 
 impl<'a> MonkeyJob<'a> {
     fn parse_nom(input: &'a str) -> IResult<&'a str, Self> {
-        let name_cond = |c: char| ('a'..='z').contains(&c);
+        let name_cond = |c: char| c.is_ascii_lowercase();
         map(
             tuple((
                 take_while1(name_cond),
@@ -106,51 +127,142 @@ impl<'a> MonkeyJob<'a> {
     }
 }
 
+/// Every monkey's job, by the monkey's name.
 #[derive(Clone, Debug)]
-struct Troop<'a> {
+pub struct Troop<'a> {
     monkeys: HashMap<&'a str, Job<'a>>,
 }
 
 impl<'a> Troop<'a> {
-    fn new(input: &'a str) -> Self {
-        let mut monkeys = HashMap::new();
-        for monkey_job in &mut iterator(input, terminated(MonkeyJob::parse_nom, newline)) {
-            monkeys.insert(monkey_job.name, monkey_job.job);
+    pub fn new(input: &'a str) -> Result<Self, ParseError> {
+        let monkeys = final_parse(input, lines(MonkeyJob::parse_nom))?
+            .into_iter()
+            .map(|monkey_job| (monkey_job.name, monkey_job.job))
+            .collect();
+        Ok(Self { monkeys })
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = (&'a str, &Job<'a>)> + '_ {
+        self.monkeys.iter().map(|(&name, job)| (name, job))
+    }
+
+    /// What `root` yells, if every monkey it depends on is there.
+    pub fn root_value(&self) -> Option<i64> {
+        self.monkeys.get("root")?.get_value(self)
+    }
+
+    /// What `humn` has to yell for both sides of `root` to be equal.
+    pub fn humn_value(self) -> Option<i64> {
+        self.humn_value_observed(&mut ())
+    }
+
+    /// Like [`Troop::humn_value`], explaining to `observer` each operation that is undone on the
+    /// way down from `root` to `humn`.
+    pub fn humn_value_observed(mut self, observer: &mut dyn Observer) -> Option<i64> {
+        self.monkeys.remove("humn");
+        let troop = &self;
+        if let &Job::Dependent { a, b, .. } = troop.monkeys.get("root")? {
+            let a_val = troop.monkeys.get(a).and_then(|j| j.get_value(troop));
+            let b_val = troop.monkeys.get(b).and_then(|j| j.get_value(troop));
+            let mut job = &Job::Dependent {
+                a,
+                op: Operation::Eq,
+                b,
+            };
+            let mut value = a_val.or(b_val)?;
+            let mut name = "root";
+            loop {
+                let value_monkey = job.missing_value(troop, value)?;
+                observer.explain(&|| troop.explain_inversion(name, job, value, value_monkey));
+                value = value_monkey.0;
+                let monkey = value_monkey.1;
+                if monkey == "humn" {
+                    break Some(value);
+                } else {
+                    job = troop.monkeys.get(monkey)?;
+                    name = monkey;
+                }
+            }
+        } else {
+            None
         }
-        Self { monkeys }
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let troop = Troop::new(input);
-    let root = troop.monkeys.get("root")?;
-    root.get_value(&troop)
+impl Troop<'_> {
+    /// Describes how monkey `name`, whose `job` has to come to `value`, needs the monkey it
+    /// depends on that isn't yet known to yell `missing`.
+    fn explain_inversion(&self, name: &str, job: &Job, value: i64, missing: (i64, &str)) -> Step {
+        let (missing_value, unknown) = missing;
+        let &Job::Dependent { a, op, b } = job else {
+            return Step::new(format!("{name} yells a number"));
+        };
+        let known = if unknown == a { b } else { a };
+        let known_value = self.monkeys.get(known).and_then(|j| j.get_value(self));
+        let known_value = known_value.unwrap_or(value);
+        let description = if op == Operation::Eq {
+            format!(
+                "{name} checks that {a} and {b} are equal; {known} yells {known_value}, so \
+                 {unknown} must yell {missing_value}"
+            )
+        } else {
+            format!(
+                "{name} yells {a} {} {b}, which must be {value}; {known} yells {known_value}, \
+                 so {unknown} must yell {missing_value}",
+                op.symbol()
+            )
+        };
+        Step::new(description)
+            .with("monkey", name)
+            .with("must_yell", value)
+            .with("known", known)
+            .with("known_value", known_value)
+            .with("unknown", unknown)
+            .with("unknown_value", missing_value)
+    }
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let mut troop = Troop::new(input);
-    troop.monkeys.remove("humn");
-    if let &Job::Dependent { a, b, .. } = troop.monkeys.get("root")? {
-        let a_val = troop.monkeys.get(a).and_then(|j| j.get_value(&troop));
-        let b_val = troop.monkeys.get(b).and_then(|j| j.get_value(&troop));
-        let mut job = &Job::Dependent {
-            a,
-            op: Operation::Eq,
-            b,
-        };
-        let mut value = a_val.or(b_val)?;
-        loop {
-            let value_monkey = job.missing_value(&troop, value)?;
-            value = value_monkey.0;
-            let monkey = value_monkey.1;
-            if monkey == "humn" {
-                break Some(value);
-            } else {
-                job = troop.monkeys.get(monkey)?;
-            }
-        }
-    } else {
-        None
+pub fn part_one(input: &str) -> Result<i64, SolveError> {
+    Troop::new(input)?
+        .root_value()
+        .ok_or(SolveError::NoAnswer("Couldn't work out root's number"))
+}
+
+pub fn part_two(input: &str) -> Result<i64, SolveError> {
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], explaining to `observer` each operation undone to find humn's number.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<i64, SolveError> {
+    Troop::new(input)?
+        .humn_value_observed(observer)
+        .ok_or(SolveError::NoAnswer("Couldn't work out humn's number"))
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn part_one(&self, input: &str) -> Result<i64, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<i64, SolveError> {
+        part_two(input)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<i64, SolveError> {
+        part_two_observed(input, observer)
+    }
+
+    fn explains(&self, part: u8) -> bool {
+        part == 2
     }
 }
 
@@ -160,27 +272,60 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), Some(152));
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(152));
+    }
+
+    #[test]
+    fn example_jobs() {
+        let troop = Troop::new(include_str!("../example.txt")).unwrap();
+        assert_eq!(troop.jobs().count(), 15);
+        assert_eq!(
+            troop.jobs().find(|&(name, _)| name == "root").unwrap().1,
+            &Job::Dependent {
+                a: "pppw",
+                op: Operation::Plus,
+                b: "sjmn"
+            }
+        );
+        assert_eq!(troop.root_value(), Some(152));
+        assert_eq!(troop.humn_value(), Some(301));
     }
 
     #[test]
     fn challenge_part_one() {
         assert_eq!(
             part_one(include_str!("../challenge.txt")),
-            Some(49288254556480)
+            Ok(49288254556480)
         );
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), Some(301));
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(301));
+    }
+
+    #[test]
+    fn explains_the_inverted_operations() {
+        let mut explanation = aoc_core::Explanation::default();
+        part_two_observed(include_str!("../example.txt"), &mut explanation).unwrap();
+        let steps: Vec<_> = explanation.0.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            steps,
+            [
+                "root checks that pppw and sjmn are equal; sjmn yells 150, so pppw must yell 150",
+                "pppw yells cczh / lfqf, which must be 150; lfqf yells 4, so cczh must yell 600",
+                "cczh yells sllz + lgvd, which must be 600; sllz yells 4, so lgvd must yell 596",
+                "lgvd yells ljgn * ptdq, which must be 596; ljgn yells 2, so ptdq must yell 298",
+                "ptdq yells humn - dvpt, which must be 298; dvpt yells 3, so humn must yell 301",
+            ]
+        );
     }
 
     #[test]
     fn challenge_part_two() {
         assert_eq!(
             part_two(include_str!("../challenge.txt")),
-            Some(3558714869436)
+            Ok(3558714869436)
         );
     }
 }
@@ -0,0 +1,306 @@
+//! Random notes on the board shape shared by the challenge inputs, with known answers, for
+//! testing.
+//!
+//! The answers come from walking the board without the crate's simulation or
+//! [`cube_wrapping`](crate::cube_wrapping): for part one by searching back along the row or
+//! column for the far edge, and for part two by folding the board into an actual cube.
+
+use std::collections::HashMap;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated set of notes is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    /// How likely each tile is to be a wall
+    pub walls: f64,
+    /// How many times the path says to move forward
+    pub moves: usize,
+    pub max_forward: usize,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            walls: 0.1,
+            moves: 100,
+            max_forward: 60,
+        }
+    }
+}
+
+const FACE: usize = 50;
+
+/// Which blocks of the board, by row and column, are faces of the cube.
+const FACES: [(usize, usize); 6] = [(0, 1), (0, 2), (1, 1), (2, 0), (2, 1), (3, 0)];
+
+#[derive(Clone, Copy)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// Right, down, left and up, as in the password.
+const FACINGS: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+fn password(row: i64, column: i64, facing: usize) -> usize {
+    (row as usize + 1) * 1000 + (column as usize + 1) * 4 + facing
+}
+
+fn walk_flat(board: &[Vec<Option<bool>>], path: &[(usize, Option<Turn>)]) -> usize {
+    let tile = |column: i64, row: i64| -> Option<bool> {
+        *board
+            .get(usize::try_from(row).ok()?)?
+            .get(usize::try_from(column).ok()?)?
+    };
+    let (mut column, mut row) = (FACE as i64, 0);
+    let mut facing = 0;
+    for &(forward, turn) in path {
+        let (dx, dy) = FACINGS[facing];
+        for _ in 0..forward {
+            let (mut x, mut y) = (column + dx, row + dy);
+            if tile(x, y).is_none() {
+                // back to the far edge of the board
+                (x, y) = (column, row);
+                while tile(x - dx, y - dy).is_some() {
+                    (x, y) = (x - dx, y - dy);
+                }
+            }
+            if tile(x, y) == Some(true) {
+                break;
+            }
+            (column, row) = (x, y);
+        }
+        facing = match turn {
+            Some(Turn::Right) => (facing + 1) % 4,
+            Some(Turn::Left) => (facing + 3) % 4,
+            None => facing,
+        };
+    }
+    password(row, column, facing)
+}
+
+type Vector = [i64; 3];
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vector, n: i64) -> Vector {
+    [a[0] * n, a[1] * n, a[2] * n]
+}
+
+/// How one face lies on the cube: which ways its columns and rows run, and which way is out.
+#[derive(Clone, Copy)]
+struct Orientation {
+    right: Vector,
+    down: Vector,
+    out: Vector,
+}
+
+impl Orientation {
+    fn facing(&self, facing: usize) -> Vector {
+        [
+            self.right,
+            self.down,
+            scale(self.right, -1),
+            scale(self.down, -1),
+        ][facing]
+    }
+}
+
+/// Walks a cube with sides twice as long as a face, on which each tile's centre has odd
+/// coordinates except for a zero or twice the face size along the face's outward direction.
+fn walk_cube(board: &[Vec<Option<bool>>], path: &[(usize, Option<Turn>)]) -> usize {
+    let n = FACE as i64;
+
+    // fold the faces up from the first one, each neighbour on the board tipping over an edge
+    let mut orientations: HashMap<(usize, usize), Orientation> = HashMap::new();
+    let first = FACES[0];
+    orientations.insert(
+        first,
+        Orientation {
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+            out: [0, 0, -1],
+        },
+    );
+    let mut stack = vec![first];
+    while let Some((block_row, block_column)) = stack.pop() {
+        let o = orientations[&(block_row, block_column)];
+        let neighbours = [
+            (
+                (block_row, block_column + 1),
+                Orientation {
+                    right: scale(o.out, -1),
+                    out: o.right,
+                    ..o
+                },
+            ),
+            (
+                (block_row + 1, block_column),
+                Orientation {
+                    down: scale(o.out, -1),
+                    out: o.down,
+                    ..o
+                },
+            ),
+            (
+                (block_row, block_column.wrapping_sub(1)),
+                Orientation {
+                    right: o.out,
+                    out: scale(o.right, -1),
+                    ..o
+                },
+            ),
+            (
+                (block_row.wrapping_sub(1), block_column),
+                Orientation {
+                    down: o.out,
+                    out: scale(o.down, -1),
+                    ..o
+                },
+            ),
+        ];
+        for (block, orientation) in neighbours {
+            if FACES.contains(&block) && !orientations.contains_key(&block) {
+                orientations.insert(block, orientation);
+                stack.push(block);
+            }
+        }
+    }
+
+    // where each tile of the board ends up
+    let centre = [n, n, n];
+    let mut tiles: HashMap<Vector, (i64, i64, Orientation)> = HashMap::new();
+    for (&(block_row, block_column), &o) in &orientations {
+        for y in 0..n {
+            for x in 0..n {
+                let position = add(
+                    add(centre, scale(o.out, n)),
+                    add(scale(o.right, 2 * x + 1 - n), scale(o.down, 2 * y + 1 - n)),
+                );
+                let (row, column) = (block_row as i64 * n + y, block_column as i64 * n + x);
+                tiles.insert(position, (row, column, o));
+            }
+        }
+    }
+    let wall = |position: &Vector| {
+        let (row, column, _) = tiles[position];
+        board[row as usize][column as usize] == Some(true)
+    };
+    let on_cube = |position: &Vector| position.iter().all(|c| (0..=2 * n).contains(c));
+
+    let mut position = add(
+        add(centre, scale(orientations[&first].out, n)),
+        add(
+            scale(orientations[&first].right, 1 - n),
+            scale(orientations[&first].down, 1 - n),
+        ),
+    );
+    let mut direction = orientations[&first].right;
+    for &(forward, turn) in path {
+        for _ in 0..forward {
+            let out = tiles[&position].2.out;
+            let mut next = (add(position, scale(direction, 2)), direction);
+            if !on_cube(&next.0) {
+                // over the edge and down the next face
+                next = (
+                    add(add(position, direction), scale(out, -1)),
+                    scale(out, -1),
+                );
+            }
+            if wall(&next.0) {
+                break;
+            }
+            (position, direction) = next;
+        }
+        if let Some(turn) = turn {
+            let o = tiles[&position].2;
+            let facing = (0..4).find(|&f| o.facing(f) == direction).unwrap();
+            direction = o.facing(match turn {
+                Turn::Right => (facing + 1) % 4,
+                Turn::Left => (facing + 3) % 4,
+            });
+        }
+    }
+    let (row, column, o) = tiles[&position];
+    password(
+        row,
+        column,
+        (0..4).find(|&f| o.facing(f) == direction).unwrap(),
+    )
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut board = vec![vec![None; 3 * FACE]; 4 * FACE];
+    for &(block_row, block_column) in &FACES {
+        for row in &mut board[block_row * FACE..(block_row + 1) * FACE] {
+            for tile in &mut row[block_column * FACE..(block_column + 1) * FACE] {
+                *tile = Some(rng.gen_bool(size.walls));
+            }
+        }
+    }
+    // somewhere to start
+    board[0][FACE] = Some(false);
+
+    let path: Vec<(usize, Option<Turn>)> = (0..size.moves.max(1))
+        .map(|i| {
+            let forward = rng.gen_range(1..=size.max_forward.max(1));
+            let turn = match (i + 1 < size.moves, rng.gen_bool(0.5)) {
+                (false, _) => None,
+                (true, true) => Some(Turn::Right),
+                (true, false) => Some(Turn::Left),
+            };
+            (forward, turn)
+        })
+        .collect();
+
+    let mut input = String::new();
+    for row in &board {
+        let line: String = row
+            .iter()
+            .map(|tile| match tile {
+                None => ' ',
+                Some(false) => '.',
+                Some(true) => '#',
+            })
+            .collect();
+        input += line.trim_end();
+        input.push('\n');
+    }
+    input.push('\n');
+    for &(forward, turn) in &path {
+        input += &forward.to_string();
+        match turn {
+            Some(Turn::Right) => input.push('R'),
+            Some(Turn::Left) => input.push('L'),
+            None => {}
+        }
+    }
+    input.push('\n');
+
+    Generated {
+        input,
+        part_one: Some(walk_flat(&board, &path)),
+        part_two: Some(walk_cube(&board, &path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day22;
+    use aoc_core::Solution;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(Day22.part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(Day22.part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,14 +1,21 @@
-use std::slice;
+use std::{fmt::Display, slice};
 
+use aoc_core::{
+    parse::{final_parse, IResult},
+    Observer, Solution, SolveError,
+};
+use grid::{Grid, Point};
 use nom::{
     branch::alt,
     character::complete::{char, digit1, newline},
     combinator::{map, map_res, value},
     multi::{many1, separated_list1},
     sequence::tuple,
-    IResult,
 };
 
+#[cfg(feature = "generate")]
+pub mod generate;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Tile {
     Open,
@@ -24,11 +31,32 @@ enum Direction {
 
 #[derive(Debug)]
 struct Notes {
-    map: Vec<Vec<Option<Tile>>>,
+    map: Grid<Option<Tile>>,
     path: Vec<Direction>,
 }
 
 impl Notes {
+    /// The side of the board's square faces, and which blocks of the board are faces, by row
+    /// and column in reading order, or `None` if the board isn't six such faces.
+    fn net(&self) -> Option<(usize, Vec<(usize, usize)>)> {
+        let tiles = self.map.iter().filter(|(_, tile)| tile.is_some()).count();
+        let side = (1..).find(|side| 6 * side * side >= tiles)?;
+        if 6 * side * side != tiles {
+            return None;
+        }
+        let on_board = |x: usize, y: usize| {
+            matches!(self.map.get(Point::new(x as i64, y as i64)), Some(Some(_)))
+        };
+        let blocks: Vec<_> = (0..self.map.height() / side)
+            .flat_map(|row| (0..self.map.width() / side).map(move |column| (row, column)))
+            .filter(|&(row, column)| on_board(column * side, row * side))
+            .collect();
+        let whole = blocks.iter().all(|&(row, column)| {
+            (0..side).all(|y| (0..side).all(|x| on_board(column * side + x, row * side + y)))
+        });
+        (blocks.len() == 6 && whole).then_some((side, blocks))
+    }
+
     fn parse_nom(input: &str) -> IResult<&str, Notes> {
         map(
             tuple((
@@ -48,7 +76,10 @@ impl Notes {
                     map_res(digit1, |d: &str| d.parse().map(Direction::Forward)),
                 ))),
             )),
-            |(map, _, _, path)| Self { map, path },
+            |(rows, _, _, path)| Self {
+                map: Grid::from_rows(rows, None),
+                path,
+            },
         )(input)
     }
 }
@@ -64,69 +95,106 @@ struct Simulation<'a> {
     notes: &'a Notes,
     path_iter: slice::Iter<'a, Direction>,
     position: Position,
-    warping_rules: &'a fn(Position) -> Option<Position>,
+    warping_rules: Option<fn(Position) -> Option<Position>>,
+    /// The way the walker last faced on each tile it has stood on
+    trail: Grid<Option<usize>>,
 }
 
 impl<'a> Simulation<'a> {
-    fn new(notes: &'a Notes, warping_rules: &'a fn(Position) -> Option<Position>) -> Self {
-        Simulation {
+    fn new(
+        notes: &'a Notes,
+        warping_rules: Option<fn(Position) -> Option<Position>>,
+    ) -> Option<Self> {
+        let mut simulation = Simulation {
             notes,
             path_iter: notes.path.iter(),
             position: Position {
                 row: 1,
                 column: notes
                     .map
-                    .get(0)
-                    .unwrap()
+                    .row(0)?
                     .iter()
-                    .position(|&t| t == Some(Tile::Open))
-                    .unwrap()
+                    .position(|&t| t == Some(Tile::Open))?
                     + 1,
                 facing: 0,
             },
             warping_rules,
-        }
+            trail: notes.map.map(|_| None),
+        };
+        simulation.mark_trail();
+        Some(simulation)
+    }
+
+    fn point(position: Position) -> Point {
+        Point::new(position.column as i64 - 1, position.row as i64 - 1)
+    }
+
+    fn mark_trail(&mut self) {
+        self.trail[Self::point(self.position)] = Some(self.position.facing);
     }
 
+    /// Draws the map as in the puzzle, with an arrow on each tile the walker has stood on.
+    fn render(&self) -> impl Display + '_ {
+        self.notes
+            .map
+            .render(|p, tile| match (self.trail[p], tile) {
+                (Some(facing), _) => ['>', 'v', '<', '^'][facing % 4],
+                (None, Some(Tile::Open)) => '.',
+                (None, Some(Tile::Wall)) => '#',
+                (None, None) => ' ',
+            })
+    }
+
+    fn on_board(&self, position: Position) -> bool {
+        matches!(self.notes.map.get(Self::point(position)), Some(Some(_)))
+    }
+
+    /// Where a step forward from `old` leads. Steps off the board that `warping_rules` don't
+    /// cover wrap around to the far side of the same row or column, as in part one.
     fn new_coords(&self, old: Position) -> Position {
-        (self.warping_rules)(old).unwrap_or(match old.facing {
+        if let Some(warped) = self.warping_rules.and_then(|rules| rules(old)) {
+            return warped;
+        }
+        let step = |p: Position, forward: bool| match (p.facing, forward) {
             // right (column +)
-            0 => Position {
-                column: old.column + 1,
-                ..old
+            (0, true) | (2, false) => Position {
+                column: p.column + 1,
+                ..p
             },
             // down (row +)
-            1 => Position {
-                row: old.row + 1,
-                ..old
+            (1, true) | (3, false) => Position {
+                row: p.row + 1,
+                ..p
             },
             // left (column -)
-            2 => Position {
-                column: old.column - 1,
-                ..old
+            (2, true) | (0, false) => Position {
+                column: p.column - 1,
+                ..p
             },
             // up (row -)
-            3 => Position {
-                row: old.row - 1,
-                ..old
+            _ => Position {
+                row: p.row - 1,
+                ..p
             },
-            _ => old,
-        })
+        };
+        let next = step(old, true);
+        if self.on_board(next) {
+            return next;
+        }
+        let mut far_edge = old;
+        while self.on_board(step(far_edge, false)) {
+            far_edge = step(far_edge, false);
+        }
+        far_edge
     }
 
     fn step_forward(&mut self) -> bool {
         let position = self.new_coords(self.position);
-        match self
-            .notes
-            .map
-            .get(position.row - 1)
-            .and_then(|row| row.get(position.column - 1))
-            .and_then(Option::as_ref)
-            .unwrap()
-        {
+        match self.notes.map[Self::point(position)].as_ref().unwrap() {
             Tile::Wall => false,
             Tile::Open => {
                 self.position = position;
+                self.mark_trail();
                 true
             }
         }
@@ -140,9 +208,11 @@ impl<'a> Iterator for Simulation<'a> {
         match self.path_iter.next()? {
             Direction::Left => {
                 self.position.facing = (self.position.facing + 4 - 1) % 4;
+                self.mark_trail();
             }
             Direction::Right => {
                 self.position.facing = (self.position.facing + 1) % 4;
+                self.mark_trail();
             }
             Direction::Forward(c) => {
                 for _ in 0..*c {
@@ -157,10 +227,234 @@ impl<'a> Iterator for Simulation<'a> {
     }
 }
 
-pub fn secret(input: &str, warping_rules: fn(Position) -> Option<Position>) -> Option<usize> {
-    let notes = Notes::parse_nom(input).ok()?.1;
-    let simulation = Simulation::new(&notes, &warping_rules);
-    simulation.last()
+pub fn part_one(input: &str) -> Result<usize, SolveError> {
+    part_one_observed(input, &mut ())
+}
+
+/// Like [`part_one`], showing `observer` the map and the walker's trail after each step of the
+/// path.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, SolveError> {
+    walk(&final_parse(input, Notes::parse_nom)?, None, observer)
+}
+
+pub fn secret(
+    input: &str,
+    warping_rules: fn(Position) -> Option<Position>,
+) -> Result<usize, SolveError> {
+    secret_observed(input, warping_rules, &mut ())
+}
+
+/// Like [`secret`], showing `observer` the map and the walker's trail after each step of the path.
+pub fn secret_observed(
+    input: &str,
+    warping_rules: fn(Position) -> Option<Position>,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    walk(
+        &final_parse(input, Notes::parse_nom)?,
+        Some(warping_rules),
+        observer,
+    )
+}
+
+fn walk(
+    notes: &Notes,
+    warping_rules: Option<fn(Position) -> Option<Position>>,
+    observer: &mut dyn Observer,
+) -> Result<usize, SolveError> {
+    let mut simulation = Simulation::new(notes, warping_rules).ok_or(SolveError::Invalid(
+        "The top row has no open tile to start on",
+    ))?;
+    let mut password = None;
+    while let Some(p) = simulation.next() {
+        observer.frame(&simulation.render());
+        password = Some(p);
+    }
+    password.ok_or(SolveError::NoAnswer("The path has no steps"))
+}
+
+/// Like [`secret_observed`] with [`cube_wrapping`], which is only right for the board shape
+/// shared by the challenge inputs, so any other shape is refused.
+pub fn cube_secret_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, SolveError> {
+    let notes = final_parse(input, Notes::parse_nom)?;
+    if notes.net() != Some((CHALLENGE_FACE, CHALLENGE_NET.to_vec())) {
+        return Err(SolveError::Invalid(
+            "Only boards shaped like the challenge inputs can be folded into a cube",
+        ));
+    }
+    walk(&notes, Some(cube_wrapping), observer)
+}
+
+/// The side of each face of the challenge inputs' cube.
+const CHALLENGE_FACE: usize = 50;
+
+/// Which blocks of the challenge inputs' board, by row and column, are faces of the cube.
+const CHALLENGE_NET: [(usize, usize); 6] = [(0, 1), (0, 2), (1, 1), (2, 0), (2, 1), (3, 0)];
+
+/// Warping rules for part two on the board shape shared by the challenge inputs, where the
+/// board is folded into a cube. Other shapes fold differently, which these rules don't know.
+pub fn cube_wrapping(p: Position) -> Option<Position> {
+    match p {
+        Position {
+            row: 1,
+            column: 51..=100,
+            facing: 3,
+        } => Some(Position {
+            row: p.column - 51 + 151,
+            column: 1,
+            facing: 0,
+        }),
+        Position {
+            row: 1,
+            column: 101..=150,
+            facing: 3,
+        } => Some(Position {
+            row: 200,
+            column: p.column - 100,
+            facing: 3,
+        }),
+        Position {
+            row: 1..=50,
+            column: 51,
+            facing: 2,
+        } => Some(Position {
+            row: 50 - p.row + 101,
+            column: 1,
+            facing: 0,
+        }),
+        Position {
+            row: 1..=50,
+            column: 150,
+            facing: 0,
+        } => Some(Position {
+            row: 50 - p.row + 101,
+            column: 100,
+            facing: 2,
+        }),
+        Position {
+            row: 50,
+            column: 101..=150,
+            facing: 1,
+        } => Some(Position {
+            row: p.column - 50,
+            column: 100,
+            facing: 2,
+        }),
+        Position {
+            row: 51..=100,
+            column: 51,
+            facing: 2,
+        } => Some(Position {
+            row: 101,
+            column: p.row - 50,
+            facing: 1,
+        }),
+        Position {
+            row: 51..=100,
+            column: 100,
+            facing: 0,
+        } => Some(Position {
+            row: 50,
+            column: p.row + 50,
+            facing: 3,
+        }),
+        Position {
+            row: 101,
+            column: 1..=50,
+            facing: 3,
+        } => Some(Position {
+            row: p.column + 50,
+            column: 51,
+            facing: 0,
+        }),
+        Position {
+            row: 101..=150,
+            column: 1,
+            facing: 2,
+        } => Some(Position {
+            row: 150 - p.row + 1,
+            column: 51,
+            facing: 0,
+        }),
+        Position {
+            row: 101..=150,
+            column: 100,
+            facing: 0,
+        } => Some(Position {
+            row: 150 - p.row + 1,
+            column: 150,
+            facing: 2,
+        }),
+        Position {
+            row: 150,
+            column: 51..=100,
+            facing: 1,
+        } => Some(Position {
+            row: 100 + p.column,
+            column: 50,
+            facing: 2,
+        }),
+        Position {
+            row: 151..=200,
+            column: 1,
+            facing: 2,
+        } => Some(Position {
+            row: 1,
+            column: p.row - 100,
+            facing: 1,
+        }),
+        Position {
+            row: 151..=200,
+            column: 50,
+            facing: 0,
+        } => Some(Position {
+            row: 150,
+            column: p.row - 100,
+            facing: 3,
+        }),
+        Position {
+            row: 200,
+            column: 1..=50,
+            facing: 1,
+        } => Some(Position {
+            row: 1,
+            column: p.column + 100,
+            facing: 1,
+        }),
+
+        _ => None,
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        cube_secret_observed(input, &mut ())
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        part_one_observed(input, observer)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        cube_secret_observed(input, observer)
+    }
 }
 
 #[cfg(test)]
@@ -209,91 +503,32 @@ mod tests {
 
                 _ => None,
             }),
-            Some(6032)
+            Ok(6032)
         );
     }
 
     #[test]
-    fn challenge_part_one() {
+    fn wraps_any_board_flat_but_folds_only_the_challenge_shape() {
+        let example = include_str!("../example.txt");
+        assert_eq!(Day22.part_one(example), Ok(6032));
         assert_eq!(
-            secret(include_str!("../challenge.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 51..=100,
-                    facing: 3,
-                } => Some(Position { row: 150, ..p }),
-                Position {
-                    row: 1,
-                    column: 101..=150,
-                    facing: 3,
-                } => Some(Position { row: 50, ..p }),
-                Position {
-                    row: 51..=100,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position { column: 100, ..p }),
-                Position {
-                    row: 1..=50,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position { column: 150, ..p }),
-                Position {
-                    row: 1..=50,
-                    column: 150,
-                    facing: 0,
-                } => Some(Position { column: 51, ..p }),
-                Position {
-                    row: 50,
-                    column: 101..=150,
-                    facing: 1,
-                } => Some(Position { row: 1, ..p }),
-                Position {
-                    row: 51..=100,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position { column: 51, ..p }),
-                Position {
-                    row: 101,
-                    column: 1..=50,
-                    facing: 3,
-                } => Some(Position { row: 200, ..p }),
-                Position {
-                    row: 101..=150,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position { column: 100, ..p }),
-                Position {
-                    row: 101..=150,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position { column: 1, ..p }),
-                Position {
-                    row: 150,
-                    column: 51..=100,
-                    facing: 1,
-                } => Some(Position { row: 1, ..p }),
-                Position {
-                    row: 151..=200,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position { column: 50, ..p }),
-                Position {
-                    row: 151..=200,
-                    column: 50,
-                    facing: 0,
-                } => Some(Position { column: 1, ..p }),
-                Position {
-                    row: 200,
-                    column: 1..=50,
-                    facing: 1,
-                } => Some(Position { row: 101, ..p }),
-
-                _ => None,
-            }),
-            Some(149138)
+            Day22.part_two(example),
+            Err(SolveError::Invalid(
+                "Only boards shaped like the challenge inputs can be folded into a cube"
+            ))
+        );
+        let notes = final_parse(example, Notes::parse_nom).unwrap();
+        assert_eq!(
+            notes.net(),
+            Some((4, vec![(0, 2), (1, 0), (1, 1), (1, 2), (2, 2), (2, 3)]))
         );
     }
 
+    #[test]
+    fn challenge_part_one() {
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(149138));
+    }
+
     #[test]
     fn example_part_two() {
         assert_eq!(
@@ -426,144 +661,15 @@ mod tests {
                 }),
                 _ => None,
             }),
-            Some(5031)
+            Ok(5031)
         );
     }
 
     #[test]
     fn challenge_part_two() {
         assert_eq!(
-            secret(include_str!("../challenge.txt"), |p| match p {
-                Position {
-                    row: 1,
-                    column: 51..=100,
-                    facing: 3,
-                } => Some(Position {
-                    row: p.column - 51 + 151,
-                    column: 1,
-                    facing: 0
-                }),
-                Position {
-                    row: 1,
-                    column: 101..=150,
-                    facing: 3,
-                } => Some(Position {
-                    row: 200,
-                    column: p.column - 100,
-                    facing: 3
-                }),
-                Position {
-                    row: 1..=50,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position {
-                    row: 50 - p.row + 101,
-                    column: 1,
-                    facing: 0
-                }),
-                Position {
-                    row: 1..=50,
-                    column: 150,
-                    facing: 0,
-                } => Some(Position {
-                    row: 50 - p.row + 101,
-                    column: 100,
-                    facing: 2
-                }),
-                Position {
-                    row: 50,
-                    column: 101..=150,
-                    facing: 1,
-                } => Some(Position {
-                    row: p.column - 50,
-                    column: 100,
-                    facing: 2
-                }),
-                Position {
-                    row: 51..=100,
-                    column: 51,
-                    facing: 2,
-                } => Some(Position {
-                    row: 101,
-                    column: p.row - 50,
-                    facing: 1
-                }),
-                Position {
-                    row: 51..=100,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position {
-                    row: 50,
-                    column: p.row + 50,
-                    facing: 3
-                }),
-                Position {
-                    row: 101,
-                    column: 1..=50,
-                    facing: 3,
-                } => Some(Position {
-                    row: p.column + 50,
-                    column: 51,
-                    facing: 0
-                }),
-                Position {
-                    row: 101..=150,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position {
-                    row: 150 - p.row + 1,
-                    column: 51,
-                    facing: 0
-                }),
-                Position {
-                    row: 101..=150,
-                    column: 100,
-                    facing: 0,
-                } => Some(Position {
-                    row: 150 - p.row + 1,
-                    column: 150,
-                    facing: 2
-                }),
-                Position {
-                    row: 150,
-                    column: 51..=100,
-                    facing: 1,
-                } => Some(Position {
-                    row: 100 + p.column,
-                    column: 50,
-                    facing: 2
-                }),
-                Position {
-                    row: 151..=200,
-                    column: 1,
-                    facing: 2,
-                } => Some(Position {
-                    row: 1,
-                    column: p.row - 100,
-                    facing: 1
-                }),
-                Position {
-                    row: 151..=200,
-                    column: 50,
-                    facing: 0,
-                } => Some(Position {
-                    row: 150,
-                    column: p.row - 100,
-                    facing: 3
-                }),
-                Position {
-                    row: 200,
-                    column: 1..=50,
-                    facing: 1,
-                } => Some(Position {
-                    row: 1,
-                    column: p.column + 100,
-                    facing: 1
-                }),
-
-                _ => None,
-            }),
-            Some(153203)
+            secret(include_str!("../challenge.txt"), cube_wrapping),
+            Ok(153203)
         );
     }
 }
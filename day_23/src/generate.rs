@@ -0,0 +1,124 @@
+//! Random scans of elves with known answers, for testing.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated scan is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+    /// How likely each tile is to have an elf on it
+    pub density: f64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            width: 12,
+            height: 12,
+            density: 0.4,
+        }
+    }
+}
+
+type Elf = (i64, i64);
+
+/// North, south, west and east, each with the tiles that have to be clear to move that way.
+const CHECKS: [[(i64, i64); 3]; 4] = [
+    [(0, -1), (-1, -1), (1, -1)],
+    [(0, 1), (-1, 1), (1, 1)],
+    [(-1, 0), (-1, -1), (-1, 1)],
+    [(1, 0), (1, -1), (1, 1)],
+];
+
+/// Spreads the elves out for one round, returning whether any moved.
+fn spread(elves: &mut HashSet<Elf>, round: usize) -> bool {
+    let occupied =
+        |elves: &HashSet<Elf>, (x, y): Elf, (dx, dy): (i64, i64)| elves.contains(&(x + dx, y + dy));
+    let mut proposals = HashMap::new();
+    for &elf in elves.iter() {
+        let alone = CHECKS.iter().flatten().all(|&d| !occupied(elves, elf, d));
+        if alone {
+            continue;
+        }
+        if let Some(check) = (0..4)
+            .map(|i| CHECKS[(round + i) % 4])
+            .find(|check| check.iter().all(|&d| !occupied(elves, elf, d)))
+        {
+            proposals.insert(elf, (elf.0 + check[0].0, elf.1 + check[0].1));
+        }
+    }
+    let mut moved = false;
+    let mut next = HashSet::new();
+    for &elf in elves.iter() {
+        match proposals.get(&elf) {
+            Some(&to) if proposals.values().filter(|&&p| p == to).count() == 1 => {
+                next.insert(to);
+                moved = true;
+            }
+            _ => {
+                next.insert(elf);
+            }
+        }
+    }
+    *elves = next;
+    moved
+}
+
+fn empty_ground(elves: &HashSet<Elf>) -> usize {
+    let xs = elves.iter().map(|&(x, _)| x);
+    let ys = elves.iter().map(|&(_, y)| y);
+    let width = xs.clone().max().unwrap() - xs.min().unwrap() + 1;
+    let height = ys.clone().max().unwrap() - ys.min().unwrap() + 1;
+    (width * height) as usize - elves.len()
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<usize, usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (width, height) = (size.width.max(1), size.height.max(1));
+    let mut elves = HashSet::new();
+    let mut input = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            // there is always at least one elf
+            if rng.gen_bool(size.density) || (x, y) == (0, 0) {
+                elves.insert((x as i64, y as i64));
+                input.push('#');
+            } else {
+                input.push('.');
+            }
+        }
+        input.push('\n');
+    }
+
+    let mut round = 0;
+    let mut part_one = None;
+    while spread(&mut elves, round) {
+        round += 1;
+        if round == 10 {
+            part_one = Some(empty_ground(&elves));
+        }
+    }
+    Generated {
+        input,
+        part_one: part_one.or_else(|| Some(empty_ground(&elves))),
+        part_two: Some(round + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
@@ -1,116 +1,74 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    iter::zip,
-};
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-struct Position {
-    x: i16,
-    y: i16,
-}
+use std::{collections::BTreeMap, fmt::Display};
 
-impl Position {
-    fn proposed_move(&self, grove: &Grove) -> Position {
-        let mut free = [[true; 3]; 3];
-        for (y, row) in zip(-1.., free.iter_mut()) {
-            for (x, tile) in zip(-1.., row.iter_mut()) {
-                if x != 0 || y != 0 {
-                    *tile = !grove.elves.contains(&Position {
-                        x: self.x + x,
-                        y: self.y + y,
-                    })
-                }
-            }
-        }
-        if free.iter().all(|row| row.iter().all(|&tile| tile)) {
-            return *self;
-        }
-        for i in 0..4 {
-            match (grove.cycle + i) % 4 {
-                0 => {
-                    // North
-                    if free[0][0] && free[0][1] && free[0][2] {
-                        return Position {
-                            x: self.x,
-                            y: self.y - 1,
-                        };
-                    }
-                }
-                1 => {
-                    // South
-                    if free[2][0] && free[2][1] && free[2][2] {
-                        return Position {
-                            x: self.x,
-                            y: self.y + 1,
-                        };
-                    }
-                }
-                2 => {
-                    // West
-                    if free[0][0] && free[1][0] && free[2][0] {
-                        return Position {
-                            x: self.x - 1,
-                            y: self.y,
-                        };
-                    }
-                }
-                3 => {
-                    // East
-                    if free[0][2] && free[1][2] && free[2][2] {
-                        return Position {
-                            x: self.x + 1,
-                            y: self.y,
-                        };
-                    }
-                }
-                _ => {}
-            }
+use aoc_core::{Observer, ParseError, Solution, SolveError, Stepper};
+use grid::{Direction, Grid, Point, SparseGrid};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// The directions elves consider moving in, in order of preference on the first round.
+const PROPOSALS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+fn proposed_move(elf: Point, grove: &Grove) -> Point {
+    if grove.elves.neighbours_8(elf).next().is_none() {
+        return elf;
+    }
+    for i in 0..4 {
+        let direction = PROPOSALS[(grove.cycle + i) % 4];
+        let ahead = elf.step(direction);
+        if [
+            ahead,
+            ahead.step(direction.turn_left()),
+            ahead.step(direction.turn_right()),
+        ]
+        .iter()
+        .all(|&p| !grove.elves.contains(p))
+        {
+            return ahead;
         }
-        *self
     }
+    elf
 }
 
 #[derive(Debug, Default)]
 struct Grove {
-    elves: BTreeSet<Position>,
-    cycle: u8,
+    elves: SparseGrid,
+    cycle: usize,
 }
 
 impl Grove {
-    fn new(input: &str) -> Self {
-        let mut x = 0;
-        let mut y = 1;
-        let mut grove = Self::default();
-        for c in input.chars() {
-            x += 1;
-            match c {
-                '#' => {
-                    grove.elves.insert(Position { x, y });
-                }
-                '\n' => {
-                    y += 1;
-                    x = 0;
-                }
-                _ => {}
-            }
-        }
-        grove
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let scan = Grid::parse(input, "an elf (#) or empty ground (.)", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Self {
+            elves: scan
+                .iter()
+                .filter(|(_, &elf)| elf)
+                .map(|(p, _)| (p, ()))
+                .collect(),
+            cycle: 0,
+        })
     }
 
     fn empty_tiles(&self) -> usize {
-        let min_x = self.elves.iter().map(|e| e.x).min().unwrap_or(0);
-        let min_y = self.elves.iter().map(|e| e.y).min().unwrap_or(0);
-        let max_x = self.elves.iter().map(|e| e.x).max().unwrap_or(0);
-        let max_y = self.elves.iter().map(|e| e.y).max().unwrap_or(0);
-        (min_x..=max_x).len() * (min_y..=max_y).len() - self.elves.len()
+        let (min, max) = self.elves.bounds().unwrap_or_default();
+        (min.x..=max.x).count() * (min.y..=max.y).count() - self.elves.len()
     }
 
     fn follow_round(&mut self) -> bool {
         let mut changes = false;
-        let mut proposed_position_counts = BTreeMap::<Position, usize>::new();
-        let mut proposed_moves = BTreeMap::<Position, Position>::new();
-        for &elf in self.elves.iter() {
-            let proposed_position = elf.proposed_move(self);
+        let mut proposed_position_counts = BTreeMap::<Point, usize>::new();
+        let mut proposed_moves = BTreeMap::<Point, Point>::new();
+        for elf in self.elves.points() {
+            let proposed_position = proposed_move(elf, self);
             *proposed_position_counts
                 .entry(proposed_position)
                 .or_default() += 1;
@@ -125,28 +83,179 @@ impl Grove {
                 } else {
                     original
                 },
+                (),
             );
         }
         self.cycle = (self.cycle + 1) % 4;
         changes
     }
+
+    fn render(&self) -> impl Display + '_ {
+        self.elves
+            .render(|elf| if elf.is_some() { '#' } else { '.' })
+    }
+}
+
+/// The elves spreading out one round at a time.
+#[derive(Debug)]
+pub struct Spreading {
+    grove: Grove,
+    /// How many rounds to stop after, if the elves aren't to carry on until none of them move
+    limit: Option<u64>,
+    rounds: u64,
+    settled: bool,
+}
+
+impl Spreading {
+    /// Elves that stop after `limit` rounds, or once none of them move if there's no limit.
+    pub fn new(input: &str, limit: Option<u64>) -> Result<Self, ParseError> {
+        Ok(Self {
+            grove: Grove::new(input)?,
+            limit,
+            rounds: 0,
+            settled: false,
+        })
+    }
+
+    /// How many tiles of empty ground are in the smallest rectangle holding every elf.
+    pub fn empty_tiles(&self) -> usize {
+        self.grove.empty_tiles()
+    }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let mut grove = Grove::new(input);
+impl Stepper for Spreading {
+    fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.rounds += 1;
+        self.settled = !self.grove.follow_round();
+        true
+    }
+
+    fn tick(&self) -> u64 {
+        self.rounds
+    }
+
+    fn is_finished(&self) -> bool {
+        self.settled || Some(self.rounds) == self.limit
+    }
+
+    fn render(&self) -> String {
+        self.grove.render().to_string()
+    }
+
+    fn inspect(&self, column: usize, row: usize) -> Option<String> {
+        let (min, max) = self.grove.elves.bounds()?;
+        let p = min + Point::new(column as i64, row as i64);
+        if p.x > max.x || p.y > max.y {
+            return None;
+        }
+        if !self.grove.elves.contains(p) {
+            return Some(format!("Empty ground at {},{}", p.x, p.y));
+        }
+        let plan = if self.grove.elves.neighbours_8(p).next().is_none() {
+            "with no elf next to it"
+        } else {
+            let proposed = proposed_move(p, &self.grove);
+            let direction = PROPOSALS.into_iter().find(|&d| p.step(d) == proposed);
+            match direction {
+                Some(Direction::North) => "proposing to move north",
+                Some(Direction::South) => "proposing to move south",
+                Some(Direction::West) => "proposing to move west",
+                Some(Direction::East) => "proposing to move east",
+                None => "with nowhere to move",
+            }
+        };
+        Some(format!("An elf at {},{} {plan}", p.x, p.y))
+    }
+
+    fn summary(&self) -> String {
+        if self.settled {
+            format!("No elf moved in round {}", self.rounds)
+        } else {
+            format!(
+                "{} rounds, with {} empty tiles between the elves",
+                self.rounds,
+                self.empty_tiles()
+            )
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    part_one_observed(input, &mut ())
+}
+
+/// Like [`part_one`], showing `observer` the elves before the first round and after each one.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
+    let mut grove = Grove::new(input)?;
+    observer.frame(&grove.render());
     for _ in 0..10 {
         grove.follow_round();
+        observer.frame(&grove.render());
     }
-    grove.empty_tiles()
+    Ok(grove.empty_tiles())
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    part_two_observed(input, &mut ())
 }
 
-pub fn part_two(input: &str) -> usize {
+/// Like [`part_two`], showing `observer` the elves before the first round and after each one.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<usize, ParseError> {
     let mut rounds = 1;
-    let mut grove = Grove::new(input);
+    let mut grove = Grove::new(input)?;
+    observer.frame(&grove.render());
     while grove.follow_round() {
+        observer.frame(&grove.render());
         rounds += 1
     }
-    rounds
+    Ok(rounds)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn part_one(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_one(input)?)
+    }
+
+    fn part_two(&self, input: &str) -> Result<usize, SolveError> {
+        Ok(part_two(input)?)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_one_observed(input, observer)?)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<usize, SolveError> {
+        Ok(part_two_observed(input, observer)?)
+    }
+
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        let limit = match part {
+            1 => Some(10),
+            2 => None,
+            _ => return None,
+        };
+        Some(
+            Spreading::new(input, limit)
+                .map(|spreading| Box::new(spreading) as Box<dyn Stepper>)
+                .map_err(SolveError::from),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -155,21 +264,44 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 110);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(110));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 4068);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(4068));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 20);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(20));
+    }
+
+    #[test]
+    fn steps_one_round_at_a_time() {
+        let mut spreading = Spreading::new(include_str!("../example.txt"), Some(10)).unwrap();
+        assert_eq!(
+            spreading.inspect(4, 0).as_deref(),
+            Some("An elf at 4,0 proposing to move north")
+        );
+        assert_eq!(
+            spreading.inspect(0, 0).as_deref(),
+            Some("Empty ground at 0,0")
+        );
+        assert!(spreading.step());
+        assert_eq!(spreading.tick(), 1);
+        spreading.run_to(u64::MAX);
+        assert!(spreading.is_finished());
+        assert_eq!(spreading.tick(), 10);
+        assert_eq!(spreading.empty_tiles(), 110);
+
+        let mut spreading = Spreading::new(include_str!("../example.txt"), None).unwrap();
+        spreading.run_to(u64::MAX);
+        assert_eq!(spreading.summary(), "No elf moved in round 20");
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 968);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(968));
     }
 }
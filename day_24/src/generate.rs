@@ -0,0 +1,135 @@
+//! Random valleys with known answers, for testing.
+
+use std::collections::HashSet;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated valley is, not counting its walls.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+    /// How likely each tile is to start with a blizzard on it
+    pub density: f64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            width: 10,
+            height: 6,
+            density: 0.3,
+        }
+    }
+}
+
+type Tile = (i64, i64);
+
+/// A blizzard's position and direction, within the valley's walls.
+type Blizzard = (Tile, (i64, i64));
+
+fn blow(blizzards: &[Blizzard], width: i64, height: i64) -> Vec<Blizzard> {
+    blizzards
+        .iter()
+        .map(|&((x, y), (dx, dy))| {
+            (
+                ((x + dx).rem_euclid(width), (y + dy).rem_euclid(height)),
+                (dx, dy),
+            )
+        })
+        .collect()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Moves every blizzard minute by minute, keeping track of everywhere the expedition could be.
+/// Gives up once every combination of tile and blizzard layout must have been tried.
+fn journey(
+    blizzards: &mut Vec<Blizzard>,
+    (width, height): (i64, i64),
+    start: Tile,
+    goal: Tile,
+) -> Option<i64> {
+    let give_up = width / gcd(width, height) * height * (width * height + 2);
+    let mut reachable = HashSet::from([start]);
+    for minute in 1..=give_up {
+        *blizzards = blow(blizzards, width, height);
+        let blocked: HashSet<Tile> = blizzards.iter().map(|&(tile, _)| tile).collect();
+        reachable = reachable
+            .iter()
+            .flat_map(|&(x, y)| [(x, y), (x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)])
+            .filter(|&tile| {
+                tile == start
+                    || tile == goal
+                    || (0..width).contains(&tile.0)
+                        && (0..height).contains(&tile.1)
+                        && !blocked.contains(&tile)
+            })
+            .collect();
+        if reachable.contains(&goal) {
+            return Some(minute);
+        }
+    }
+    None
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<i64, i64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (width, height) = (size.width.max(1) as i64, size.height.max(1) as i64);
+    let mut blizzards = Vec::new();
+    let mut input = format!("#.{}\n", "#".repeat(width as usize));
+    for y in 0..height {
+        input.push('#');
+        for x in 0..width {
+            // blizzards going up or down would blow out of the entrance or the exit
+            let directions: &[(char, (i64, i64))] = if x == 0 || x == width - 1 {
+                &[('>', (1, 0)), ('<', (-1, 0))]
+            } else {
+                &[('>', (1, 0)), ('<', (-1, 0)), ('v', (0, 1)), ('^', (0, -1))]
+            };
+            if rng.gen_bool(size.density) {
+                let (c, direction) = directions[rng.gen_range(0..directions.len())];
+                blizzards.push(((x, y), direction));
+                input.push(c);
+            } else {
+                input.push('.');
+            }
+        }
+        input += "#\n";
+    }
+    input += &format!("{}.#\n", "#".repeat(width as usize));
+
+    let (start, goal) = ((0, -1), (width - 1, height));
+    let valley = (width, height);
+    let there = journey(&mut blizzards, valley, start, goal);
+    let part_two = there.and_then(|there| {
+        let back = journey(&mut blizzards, valley, goal, start)?;
+        Some(there + back + journey(&mut blizzards, valley, start, goal)?)
+    });
+    Generated {
+        input,
+        part_one: there,
+        part_two,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let generated = generate(seed, &Size::default());
+            assert_eq!(crate::part_one(&generated.input).ok(), generated.part_one);
+            assert_eq!(crate::part_two(&generated.input).ok(), generated.part_two);
+        }
+    }
+}
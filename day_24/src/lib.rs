@@ -1,144 +1,349 @@
-use std::{collections::BTreeSet, mem::take};
+use std::{collections::BTreeSet, fmt::Display};
+
+use aoc_core::{Observer, ParseError, Solution, SolveError, Stepper};
+use grid::{Direction, Grid, Point};
+
+#[cfg(feature = "generate")]
+pub mod generate;
 
 struct Valley {
-    width: usize,
-    height: usize,
-    /// blizzards moving up. A vec of columns
-    up_blizzards: Vec<Vec<bool>>,
-    /// blizzards moving right. A vec of rows
-    right_blizzards: Vec<Vec<bool>>,
-    /// blizzards moving down. A vec of columns
-    down_blizzards: Vec<Vec<bool>>,
-    /// blizzards moving left. A vec of rows
-    left_blizzards: Vec<Vec<bool>>,
+    width: i64,
+    height: i64,
+    /// The direction of the blizzard starting on each tile, if there is one
+    blizzards: Grid<Option<Direction>>,
 }
 
 impl Valley {
-    fn new(input: &str) -> Self {
-        let width = input.lines().next().unwrap().chars().count();
-        let height = input.lines().filter(|line| !line.is_empty()).count();
-
-        let mut valley = Self {
-            width,
-            height,
-            up_blizzards: vec![vec![false; height]; width],
-            right_blizzards: vec![vec![false; width]; height],
-            down_blizzards: vec![vec![false; height]; width],
-            left_blizzards: vec![vec![false; width]; height],
-        };
-        for (y, row) in input.lines().enumerate() {
-            for (x, c) in row.chars().enumerate() {
-                match c {
-                    '^' => valley.up_blizzards[x][y] = true,
-                    '>' => valley.right_blizzards[y][x] = true,
-                    'v' => valley.down_blizzards[x][y] = true,
-                    '<' => valley.left_blizzards[y][x] = true,
-                    _ => {}
-                }
-            }
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let blizzards = Grid::parse(
+            input,
+            "a wall (#), ground (.) or a blizzard (^, >, v or <)",
+            |c| match c {
+                '#' | '.' => Some(None),
+                '^' => Some(Some(Direction::North)),
+                '>' => Some(Some(Direction::East)),
+                'v' => Some(Some(Direction::South)),
+                '<' => Some(Some(Direction::West)),
+                _ => None,
+            },
+        )?;
+        Ok(Self {
+            width: blizzards.width() as i64,
+            height: blizzards.height() as i64,
+            blizzards,
+        })
+    }
+
+    fn start(&self) -> Point {
+        Point::new(1, 0)
+    }
+
+    fn goal(&self) -> Point {
+        Point::new(self.width - 2, self.height - 1)
+    }
+
+    fn has_blizzard(&self, x: i64, y: i64, direction: Direction) -> bool {
+        self.blizzards.get(Point::new(x, y)) == Some(&Some(direction))
+    }
+
+    fn is_free(&self, p: Point, time: i64) -> bool {
+        if p == self.start() || p == self.goal() {
+            return true;
         }
-        valley
+        !self.is_wall(p) && !BLOWING.iter().any(|&d| self.blizzard_at(p, time, d))
+    }
+
+    fn is_wall(&self, p: Point) -> bool {
+        let Point { x, y } = p;
+        p != self.start()
+            && p != self.goal()
+            && (x <= 0 || x + 1 >= self.width || y <= 0 || y + 1 >= self.height)
     }
 
-    fn is_free(&self, x: usize, y: usize, time: usize) -> bool {
+    /// Whether a blizzard blowing in `direction` is on the tile `p` inside the walls at `time`.
+    fn blizzard_at(&self, p: Point, time: i64, direction: Direction) -> bool {
+        let Point { x, y } = p;
         let v_width = self.width - 2;
         let v_height = self.height - 2;
-        if (y == 0 && x == 1) || (y + 1 == self.height && x + 2 == self.width) {
-            return true;
-        }
-        if x == 0 || x + 1 == self.width || y == 0 || y + 1 == self.height {
-            return false;
+        match direction {
+            Direction::South => {
+                self.has_blizzard(x, (y - time - 1).rem_euclid(v_height) + 1, direction)
+            }
+            Direction::West => {
+                self.has_blizzard((x + time - 1).rem_euclid(v_width) + 1, y, direction)
+            }
+            Direction::North => {
+                self.has_blizzard(x, (y + time - 1).rem_euclid(v_height) + 1, direction)
+            }
+            Direction::East => {
+                self.has_blizzard((x - time - 1).rem_euclid(v_width) + 1, y, direction)
+            }
         }
-        if self.down_blizzards[x][(y + v_height - time % v_height - 1) % v_height + 1] {
-            return false;
+    }
+
+    /// How long until the blizzards are back where they started.
+    fn period(&self) -> i64 {
+        fn gcd(a: i64, b: i64) -> i64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
         }
-        if self.left_blizzards[y][(x + time - 1) % v_width + 1] {
-            return false;
+        let (v_width, v_height) = (self.width - 2, self.height - 2);
+        v_width / gcd(v_width, v_height) * v_height
+    }
+
+    /// Draws the valley at `time`, with the expedition on each tile that `expedition` picks out.
+    fn render<'a>(
+        &'a self,
+        expedition: impl Fn(Point) -> bool + 'a,
+        time: i64,
+    ) -> impl Display + 'a {
+        self.blizzards
+            .render(move |p, _| match (self.is_free(p, time), expedition(p)) {
+                (_, true) => 'E',
+                (true, false) => ' ',
+                (false, false) => '#',
+            })
+    }
+
+    /// The time of arrival at `goal` when setting off from `start` at `start_time`.
+    ///
+    /// Once the way is found, `observer` is shown each minute of it.
+    fn journey_time(
+        &self,
+        start: Point,
+        goal: Point,
+        start_time: i64,
+        observer: &mut dyn Observer,
+    ) -> Result<i64, SolveError> {
+        // The blizzards repeat, so the time within a period is enough to tell states apart.
+        let period = self.period();
+        let path = search::bfs(
+            (start, start_time % period),
+            |&(tile, time)| {
+                let time = (time + 1) % period;
+                tile.neighbours_4()
+                    .chain([tile])
+                    .filter(move |&next| self.is_free(next, time))
+                    .map(move |next| (next, time))
+            },
+            |&(tile, _)| tile == goal,
+        )
+        .ok_or(SolveError::NoAnswer(
+            "There is no way through the blizzards",
+        ))?;
+
+        for &(expedition, time) in path.states.iter() {
+            observer.frame(&self.render(|p| p == expedition, time));
         }
-        if self.up_blizzards[x][(y + time - 1) % v_height + 1] {
+        Ok(start_time + path.cost as i64)
+    }
+}
+
+/// The directions the blizzards blow in.
+const BLOWING: [Direction; 4] = [
+    Direction::South,
+    Direction::West,
+    Direction::North,
+    Direction::East,
+];
+
+/// Every tile the expedition could be on, a minute at a time, as it crosses the valley and
+/// turns back at each end.
+pub struct Crossing {
+    valley: Valley,
+    /// Where each crossing ends, in order
+    goals: Vec<Point>,
+    crossed: usize,
+    time: i64,
+    /// When the current crossing set off
+    set_off: i64,
+    reachable: BTreeSet<Point>,
+    stuck: bool,
+}
+
+impl Crossing {
+    /// An expedition that crosses the valley `crossings` times.
+    pub fn new(input: &str, crossings: usize) -> Result<Self, SolveError> {
+        let valley = Valley::new(input)?;
+        let (start, goal) = (valley.start(), valley.goal());
+        Ok(Self {
+            goals: (0..crossings)
+                .map(|i| if i % 2 == 0 { goal } else { start })
+                .collect(),
+            crossed: 0,
+            time: 0,
+            set_off: 0,
+            reachable: BTreeSet::from([start]),
+            stuck: false,
+            valley,
+        })
+    }
+
+    /// How many minutes have passed.
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+}
+
+impl Stepper for Crossing {
+    fn step(&mut self) -> bool {
+        if self.is_finished() {
             return false;
         }
-        if self.right_blizzards[y][(x + v_width - time % v_width - 1) % v_width + 1] {
-            return false;
+        self.time += 1;
+        let time = self.time;
+        self.reachable = self
+            .reachable
+            .iter()
+            .flat_map(|&tile| tile.neighbours_4().chain([tile]))
+            .filter(|&next| self.valley.is_free(next, time))
+            .collect();
+        let goal = self.goals[self.crossed];
+        if self.reachable.contains(&goal) {
+            // the ends of the valley are out of the blizzards' way, so it's never worth arriving
+            // later than the earliest
+            self.crossed += 1;
+            self.set_off = time;
+            self.reachable = BTreeSet::from([goal]);
+        } else if time - self.set_off
+            > self.valley.period() * self.valley.width * self.valley.height
+        {
+            // every tile has been tried at every point in the blizzards' cycle
+            self.stuck = true;
         }
         true
     }
 
-    fn journey_time(
-        &self,
-        start: (usize, usize),
-        goal: (usize, usize),
-        start_time: usize,
-    ) -> usize {
-        let mut time = start_time;
-        let mut possible_tiles = BTreeSet::<(usize, usize)>::new();
-        possible_tiles.insert(start);
-        loop {
-            time += 1;
-            if possible_tiles.is_empty() {
-                panic!("Ran out of moves");
-            }
-            if time > 1000 {
-                panic!("over 1000 cycles");
-            }
+    fn tick(&self) -> u64 {
+        self.time as u64
+    }
 
-            for &(x, y) in take(&mut possible_tiles).iter() {
-                if y > 0 && self.is_free(x, y - 1, time) {
-                    possible_tiles.insert((x, y - 1));
-                }
-                if self.is_free(x + 1, y, time) {
-                    possible_tiles.insert((x + 1, y));
-                }
-                if y + 1 < self.height && self.is_free(x, y + 1, time) {
-                    possible_tiles.insert((x, y + 1));
-                }
-                if x > 0 && self.is_free(x - 1, y, time) {
-                    possible_tiles.insert((x - 1, y));
-                }
-                if self.is_free(x, y, time) {
-                    possible_tiles.insert((x, y));
-                }
-            }
+    fn is_finished(&self) -> bool {
+        self.stuck || self.crossed == self.goals.len()
+    }
 
-            #[cfg(feature = "print")]
-            {
-                for y in 0..self.height {
-                    for x in 0..self.width {
-                        print!(
-                            "{}",
-                            match (self.is_free(x, y, time), possible_tiles.contains(&(x, y))) {
-                                (true, true) => 'E',
-                                (true, false) => ' ',
-                                (false, true) => panic!(),
-                                (false, false) => '#',
-                            }
-                        );
-                    }
-                    println!();
-                }
-                println!();
-            }
-            if possible_tiles.contains(&goal) {
-                return time;
+    fn render(&self) -> String {
+        self.valley
+            .render(|p| self.reachable.contains(&p), self.time)
+            .to_string()
+    }
+
+    fn inspect(&self, column: usize, row: usize) -> Option<String> {
+        let p = Point::new(column as i64, row as i64);
+        if p.x >= self.valley.width || p.y >= self.valley.height {
+            return None;
+        }
+        let blowing: Vec<_> = BLOWING
+            .into_iter()
+            .filter(|&d| !self.valley.is_wall(p) && self.valley.blizzard_at(p, self.time, d))
+            .collect();
+        let what = if p == self.valley.start() {
+            "The start".to_owned()
+        } else if p == self.valley.goal() {
+            "The goal".to_owned()
+        } else if self.valley.is_wall(p) {
+            "A wall".to_owned()
+        } else {
+            match blowing.as_slice() {
+                [] => "Clear ground".to_owned(),
+                [Direction::North] => "A blizzard blowing north".to_owned(),
+                [Direction::East] => "A blizzard blowing east".to_owned(),
+                [Direction::South] => "A blizzard blowing south".to_owned(),
+                [Direction::West] => "A blizzard blowing west".to_owned(),
+                several => format!("{} blizzards", several.len()),
             }
+        };
+        let expedition = if self.reachable.contains(&p) {
+            ", where the expedition could be"
+        } else {
+            ""
+        };
+        Some(format!("{what} at {},{}{expedition}", p.x, p.y))
+    }
+
+    fn summary(&self) -> String {
+        if self.stuck {
+            "There is no way through the blizzards".to_owned()
+        } else if self.is_finished() {
+            format!("Finished the last crossing in minute {}", self.time)
+        } else {
+            format!(
+                "Minute {} of crossing {} of {}, with {} tiles the expedition could be on",
+                self.time,
+                self.crossed + 1,
+                self.goals.len(),
+                self.reachable.len()
+            )
         }
     }
 }
 
-pub fn part_one(input: &str) -> usize {
-    let valley = Valley::new(input);
-    valley.journey_time((1, 0), (valley.width - 2, valley.height - 1), 0)
+pub fn part_one(input: &str) -> Result<i64, SolveError> {
+    part_one_observed(input, &mut ())
 }
 
-pub fn part_two(input: &str) -> usize {
-    let valley = Valley::new(input);
-    let start = (1, 0);
-    let goal = (valley.width - 2, valley.height - 1);
+/// Like [`part_one`], showing `observer` the valley at each minute of the journey.
+pub fn part_one_observed(input: &str, observer: &mut dyn Observer) -> Result<i64, SolveError> {
+    let valley = Valley::new(input)?;
+    valley.journey_time(valley.start(), valley.goal(), 0, observer)
+}
+
+pub fn part_two(input: &str) -> Result<i64, SolveError> {
+    part_two_observed(input, &mut ())
+}
+
+/// Like [`part_two`], showing `observer` the valley at each minute of all three journeys.
+pub fn part_two_observed(input: &str, observer: &mut dyn Observer) -> Result<i64, SolveError> {
+    let valley = Valley::new(input)?;
+    let start = valley.start();
+    let goal = valley.goal();
+
+    let there = valley.journey_time(start, goal, 0, observer)?;
+    let back = valley.journey_time(goal, start, there, observer)?;
+    valley.journey_time(start, goal, back, observer)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type PartOne = i64;
+    type PartTwo = i64;
 
-    let there = valley.journey_time(start, goal, 0);
-    let back = valley.journey_time(goal, start, there);
-    let again = valley.journey_time(start, goal, back);
-    again
+    fn part_one(&self, input: &str) -> Result<i64, SolveError> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &str) -> Result<i64, SolveError> {
+        part_two(input)
+    }
+
+    fn part_one_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<i64, SolveError> {
+        part_one_observed(input, observer)
+    }
+
+    fn part_two_observed(
+        &self,
+        input: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<i64, SolveError> {
+        part_two_observed(input, observer)
+    }
+
+    fn stepper(&self, part: u8, input: &str) -> Option<Result<Box<dyn Stepper>, SolveError>> {
+        let crossings = match part {
+            1 => 1,
+            2 => 3,
+            _ => return None,
+        };
+        Some(Crossing::new(input, crossings).map(|c| Box::new(c) as Box<dyn Stepper>))
+    }
 }
 
 #[cfg(test)]
@@ -147,21 +352,65 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        assert_eq!(part_one(include_str!("../example.txt")), 18);
+        assert_eq!(part_one(include_str!("../example.txt")), Ok(18));
     }
 
     #[test]
     fn challenge_part_one() {
-        assert_eq!(part_one(include_str!("../challenge.txt")), 277);
+        assert_eq!(part_one(include_str!("../challenge.txt")), Ok(277));
     }
 
     #[test]
     fn example_part_two() {
-        assert_eq!(part_two(include_str!("../example.txt")), 54);
+        assert_eq!(part_two(include_str!("../example.txt")), Ok(54));
     }
 
     #[test]
     fn challenge_part_two() {
-        assert_eq!(part_two(include_str!("../challenge.txt")), 877);
+        assert_eq!(part_two(include_str!("../challenge.txt")), Ok(877));
+    }
+
+    #[test]
+    fn shows_each_minute_of_the_journey() {
+        struct Frames(Vec<String>);
+
+        impl Observer for Frames {
+            fn frame(&mut self, frame: &dyn Display) {
+                self.0.push(frame.to_string());
+            }
+        }
+
+        let mut frames = Frames(Vec::new());
+        assert_eq!(
+            part_one_observed(include_str!("../example.txt"), &mut frames),
+            Ok(18)
+        );
+        assert_eq!(frames.0.len(), 19);
+        assert_eq!(frames.0[0].lines().next(), Some("#E######"));
+    }
+
+    #[test]
+    fn steps_one_minute_at_a_time() {
+        let mut crossing = Crossing::new(include_str!("../example.txt"), 1).unwrap();
+        assert_eq!(
+            crossing.inspect(1, 0).as_deref(),
+            Some("The start at 1,0, where the expedition could be")
+        );
+        assert_eq!(
+            crossing.inspect(1, 1).as_deref(),
+            Some("A blizzard blowing east at 1,1")
+        );
+        assert_eq!(crossing.inspect(0, 0).as_deref(), Some("A wall at 0,0"));
+        assert!(crossing.step());
+        assert_eq!(crossing.render().lines().nth(1), Some("#E## # #"));
+        crossing.run_to(u64::MAX);
+        assert_eq!(crossing.time(), 18);
+
+        let mut crossing = Crossing::new(include_str!("../example.txt"), 3).unwrap();
+        crossing.run_to(u64::MAX);
+        assert_eq!(
+            crossing.summary(),
+            "Finished the last crossing in minute 54"
+        );
     }
 }
@@ -0,0 +1,66 @@
+//! Random fuel requirements with known answers, for testing.
+
+use alloc::{string::String, vec::Vec};
+use core::convert::Infallible;
+
+use aoc_core::Generated;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How big a generated list of fuel requirements is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub numbers: usize,
+    pub max_number: i64,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size {
+            numbers: 100,
+            max_number: 1_000_000_000_000,
+        }
+    }
+}
+
+/// Writes `number` in SNAFU, picking each digit from -2 to 2 that leaves the rest divisible
+/// by five.
+fn snafu(mut number: i64) -> String {
+    let mut digits = Vec::new();
+    loop {
+        let digit = (number + 2).rem_euclid(5) - 2;
+        digits.push(b"=-012"[(digit + 2) as usize] as char);
+        number = (number - digit) / 5;
+        if number == 0 {
+            return digits.iter().rev().collect();
+        }
+    }
+}
+
+pub fn generate(seed: u64, size: &Size) -> Generated<String, Infallible> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let numbers: Vec<i64> = (0..size.numbers.max(1))
+        .map(|_| rng.gen_range(1..=size.max_number.max(1)))
+        .collect();
+    Generated {
+        input: numbers.iter().map(|&n| snafu(n) + "\n").collect(),
+        part_one: Some(snafu(numbers.iter().sum())),
+        part_two: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference() {
+        for seed in 0..100 {
+            let size = Size {
+                max_number: 10i64.pow(seed as u32 % 13),
+                ..Size::default()
+            };
+            let generated = generate(seed, &size);
+            assert_eq!(Some(crate::part_one(&generated.input)), generated.part_one);
+        }
+    }
+}
@@ -1,3 +1,21 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::{string::String, vec::Vec};
+use core::convert::Infallible;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use aoc_core::io::{with_lines, ReadError};
+use aoc_core::{Solution, SolveError};
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
 fn snafu_to_num(snafu: &str) -> i64 {
     let mut num = 0;
     for s in snafu.chars() {
@@ -41,14 +59,34 @@ fn num_to_snafu(mut num: i64) -> String {
     }
 }
 
-fn parse_list(input: &str) -> Vec<i64> {
-    input.lines().map(snafu_to_num).collect()
+fn snafu_sum<S: AsRef<str>>(list: impl IntoIterator<Item = S>) -> String {
+    let sum = list.into_iter().map(|s| snafu_to_num(s.as_ref())).sum();
+
+    num_to_snafu(sum)
 }
 
 pub fn part_one(input: &str) -> String {
-    let sum = parse_list(input).iter().sum::<i64>();
+    snafu_sum(input.lines())
+}
 
-    num_to_snafu(sum)
+#[cfg(feature = "std")]
+pub fn part_one_from_reader(reader: impl BufRead) -> Result<String, ReadError> {
+    with_lines(reader, |list| snafu_sum(list))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type PartOne = String;
+    type PartTwo = Infallible;
+
+    fn part_one(&self, input: &str) -> Result<String, SolveError> {
+        Ok(part_one(input))
+    }
+
+    fn part_two(&self, _input: &str) -> Result<Infallible, SolveError> {
+        Err(SolveError::NoSuchPart)
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +98,15 @@ mod tests {
         assert_eq!(part_one(include_str!("../example.txt")), "2=-1=0");
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn example_from_reader() {
+        assert_eq!(
+            part_one_from_reader(&include_bytes!("../example.txt")[..]).unwrap(),
+            "2=-1=0"
+        );
+    }
+
     #[test]
     fn challenge_part_one() {
         assert_eq!(
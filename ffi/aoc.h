@@ -0,0 +1,66 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated by cbindgen from ffi/src/lib.rs when the crate is built. Don't edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// How a call to `aoc_solve` went.
+typedef enum AocStatus {
+  // The answer was written to the buffer.
+  AOC_STATUS_OK = 0,
+  // The day isn't between 1 and 25.
+  AOC_STATUS_NO_SUCH_DAY = 1,
+  // The day doesn't have the requested part.
+  AOC_STATUS_NO_SUCH_PART = 2,
+  // The input isn't valid UTF-8.
+  AOC_STATUS_INVALID_UTF8 = 3,
+  // The input couldn't be parsed.
+  AOC_STATUS_PARSE_ERROR = 4,
+  // The input parsed, but didn't describe a valid puzzle.
+  AOC_STATUS_INVALID_INPUT = 5,
+  // The input made sense, but there was no answer to be found in it.
+  AOC_STATUS_NO_ANSWER = 6,
+  // The solution panicked, which it does on some inputs it doesn't expect.
+  AOC_STATUS_PANIC = 7,
+  // The buffer is too small for the answer. Its `len` has been set to the size needed.
+  AOC_STATUS_BUFFER_TOO_SMALL = 8,
+  // The input or the buffer was null, or the buffer's data was null though it had capacity.
+  AOC_STATUS_NULL_POINTER = 9,
+} AocStatus;
+
+// Memory the caller owns for `aoc_solve` to write text into.
+typedef struct AocBuffer {
+  // Where the text is written, followed by a NUL when there is room for one. May be null if
+  // `capacity` is 0, to find out how much room the answer needs.
+  char *data;
+  // How many bytes `data` has room for.
+  size_t capacity;
+  // How many bytes of text were written, or were needed if the buffer was too small. The NUL
+  // isn't counted.
+  size_t len;
+} AocBuffer;
+
+// Solves part `part` of day `day` for the `len` bytes of UTF-8 text at `input`.
+//
+// The answer is written to `out` as text, the way `aoc run` prints it. When there is an error
+// other than `AOC_STATUS_BUFFER_TOO_SMALL`, a description of it is written instead, if it fits.
+//
+// # Safety
+//
+// `input` must point to `len` readable bytes, and `out` must point to an `AocBuffer` whose `data`
+// has `capacity` writable bytes. Neither is kept after the call returns.
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t len,
+                         struct AocBuffer *out);
+
+// A short, static, NUL terminated description of `status`.
+const char *aoc_status_message(enum AocStatus status);
+
+#endif  /* AOC_H */
@@ -0,0 +1,12 @@
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml should be a valid configuration");
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("the bindings should generate")
+        .write_to_file(crate_dir.join("aoc.h"));
+}
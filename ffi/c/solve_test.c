@@ -0,0 +1,69 @@
+// Exercises libaoc_ffi through aoc.h. Exits non-zero on the first check that fails.
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static const char EXAMPLE[] = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+static int failures = 0;
+
+static AocStatus solve(uint8_t day, uint8_t part, const char *input, char *data, size_t capacity,
+                       size_t *len) {
+    AocBuffer out = {.data = data, .capacity = capacity, .len = 0};
+    AocStatus status = aoc_solve(day, part, (const uint8_t *)input, strlen(input), &out);
+    *len = out.len;
+    return status;
+}
+
+static void expect_status(const char *what, AocStatus actual, AocStatus expected) {
+    if (actual != expected) {
+        fprintf(stderr, "%s: expected status %d (%s) but got %d (%s)\n", what, expected,
+                aoc_status_message(expected), actual, aoc_status_message(actual));
+        failures++;
+    }
+}
+
+int main(void) {
+    char data[64];
+    size_t len;
+
+    AocStatus status = solve(1, 1, EXAMPLE, data, sizeof data, &len);
+    expect_status("day 1 part 1", status, AOC_STATUS_OK);
+    if (status == AOC_STATUS_OK && strcmp(data, "24000") != 0) {
+        fprintf(stderr, "day 1 part 1: expected 24000 but got %s\n", data);
+        failures++;
+    }
+
+    status = solve(1, 2, EXAMPLE, data, 2, &len);
+    expect_status("small buffer", status, AOC_STATUS_BUFFER_TOO_SMALL);
+    if (len != 5) {
+        fprintf(stderr, "small buffer: expected to need 5 bytes but needed %zu\n", len);
+        failures++;
+    }
+
+    expect_status("day 26", solve(26, 1, EXAMPLE, data, sizeof data, &len),
+                  AOC_STATUS_NO_SUCH_DAY);
+    expect_status("day 25 part 2", solve(25, 2, "1=\n", data, sizeof data, &len),
+                  AOC_STATUS_NO_SUCH_PART);
+    expect_status("misspelt input", solve(11, 1, "x\n", data, sizeof data, &len),
+                  AOC_STATUS_PARSE_ERROR);
+    expect_status("no jets", solve(17, 1, "\n", data, sizeof data, &len),
+                  AOC_STATUS_INVALID_INPUT);
+    expect_status("null buffer", aoc_solve(1, 1, (const uint8_t *)EXAMPLE, 1, NULL),
+                  AOC_STATUS_NULL_POINTER);
+    expect_status("null data", solve(1, 1, EXAMPLE, NULL, sizeof data, &len),
+                  AOC_STATUS_NULL_POINTER);
+    status = solve(1, 1, EXAMPLE, NULL, 0, &len);
+    expect_status("size query", status, AOC_STATUS_BUFFER_TOO_SMALL);
+    if (len != 5) {
+        fprintf(stderr, "size query: expected to need 5 bytes but needed %zu\n", len);
+        failures++;
+    }
+
+    if (failures == 0) {
+        printf("all checks passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
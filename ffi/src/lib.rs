@@ -0,0 +1,258 @@
+//! A C interface to every day's solution, built as `libaoc_ffi`. The declarations are in the
+//! generated `aoc.h` next to this crate's manifest.
+
+use std::{
+    any::Any,
+    ffi::{c_char, CStr},
+    panic::{catch_unwind, AssertUnwindSafe},
+    slice, str,
+};
+
+use solutions::{AnySolution, SolveError};
+
+/// How a call to `aoc_solve` went.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The answer was written to the buffer.
+    Ok = 0,
+    /// The day isn't between 1 and 25.
+    NoSuchDay = 1,
+    /// The day doesn't have the requested part.
+    NoSuchPart = 2,
+    /// The input isn't valid UTF-8.
+    InvalidUtf8 = 3,
+    /// The input couldn't be parsed.
+    ParseError = 4,
+    /// The input parsed, but didn't describe a valid puzzle.
+    InvalidInput = 5,
+    /// The input made sense, but there was no answer to be found in it.
+    NoAnswer = 6,
+    /// The solution panicked, which it does on some inputs it doesn't expect.
+    Panic = 7,
+    /// The buffer is too small for the answer. Its `len` has been set to the size needed.
+    BufferTooSmall = 8,
+    /// The input or the buffer was null, or the buffer's data was null though it had capacity.
+    NullPointer = 9,
+}
+
+impl From<&SolveError> for AocStatus {
+    fn from(error: &SolveError) -> Self {
+        match error {
+            SolveError::NoSuchPart => AocStatus::NoSuchPart,
+            SolveError::NoAnswer(_) => AocStatus::NoAnswer,
+            SolveError::Invalid(_) => AocStatus::InvalidInput,
+            SolveError::Parse(_) => AocStatus::ParseError,
+        }
+    }
+}
+
+/// Memory the caller owns for `aoc_solve` to write text into.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocBuffer {
+    /// Where the text is written, followed by a NUL when there is room for one. May be null if
+    /// `capacity` is 0, to find out how much room the answer needs.
+    pub data: *mut c_char,
+    /// How many bytes `data` has room for.
+    pub capacity: usize,
+    /// How many bytes of text were written, or were needed if the buffer was too small. The NUL
+    /// isn't counted.
+    pub len: usize,
+}
+
+impl AocBuffer {
+    /// Copies `text` in, or records how much room it needs if it doesn't fit.
+    fn write(&mut self, text: &str) -> bool {
+        self.len = text.len();
+        if self.data.is_null() {
+            return text.is_empty();
+        }
+        if text.len() > self.capacity {
+            return false;
+        }
+        // SAFETY: the caller promised `capacity` writable bytes at `data`, and we checked the
+        // text fits
+        let out = unsafe { slice::from_raw_parts_mut(self.data.cast::<u8>(), self.capacity) };
+        out[..text.len()].copy_from_slice(text.as_bytes());
+        if let Some(nul) = out.get_mut(text.len()) {
+            *nul = 0;
+        }
+        true
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("The solution panicked")
+}
+
+fn solve(day: u8, part: u8, input: &[u8]) -> (AocStatus, String) {
+    let Some(solution) = solutions::get(day) else {
+        return (AocStatus::NoSuchDay, format!("There is no day {day}"));
+    };
+    solve_with(solution, part, input)
+}
+
+/// Solves part `part` with `solution`, catching any panic.
+fn solve_with(solution: &dyn AnySolution, part: u8, input: &[u8]) -> (AocStatus, String) {
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(e) => return (AocStatus::InvalidUtf8, e.to_string()),
+    };
+    match catch_unwind(AssertUnwindSafe(|| solution.solve(part, input))) {
+        Ok(Ok(answer)) => (AocStatus::Ok, answer),
+        Ok(Err(e)) => ((&e).into(), e.to_string()),
+        Err(payload) => (AocStatus::Panic, panic_message(&*payload).to_owned()),
+    }
+}
+
+/// Solves part `part` of day `day` for the `len` bytes of UTF-8 text at `input`.
+///
+/// The answer is written to `out` as text, the way `aoc run` prints it. When there is an error
+/// other than `AOC_STATUS_BUFFER_TOO_SMALL`, a description of it is written instead, if it fits.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, and `out` must point to an `AocBuffer` whose `data`
+/// has `capacity` writable bytes. Neither is kept after the call returns.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out: *mut AocBuffer,
+) -> AocStatus {
+    // SAFETY: the caller promised `out` is valid if it isn't null
+    let Some(out) = (unsafe { out.as_mut() }) else {
+        return AocStatus::NullPointer;
+    };
+    if out.data.is_null() && out.capacity > 0 {
+        out.len = 0;
+        return AocStatus::NullPointer;
+    }
+    if input.is_null() {
+        out.write("The input is null");
+        return AocStatus::NullPointer;
+    }
+    // SAFETY: the caller promised `len` readable bytes at `input`
+    let input = unsafe { slice::from_raw_parts(input, len) };
+    let (status, text) = solve(day, part, input);
+    if out.write(&text) || status != AocStatus::Ok {
+        status
+    } else {
+        AocStatus::BufferTooSmall
+    }
+}
+
+/// A short, static, NUL terminated description of `status`.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: AocStatus) -> *const c_char {
+    let message: &'static CStr = match status {
+        AocStatus::Ok => c"The answer was written",
+        AocStatus::NoSuchDay => c"There is no such day",
+        AocStatus::NoSuchPart => c"The day has no such part",
+        AocStatus::InvalidUtf8 => c"The input isn't UTF-8",
+        AocStatus::ParseError => c"The input couldn't be parsed",
+        AocStatus::InvalidInput => c"The input isn't a valid puzzle",
+        AocStatus::NoAnswer => c"There is no answer for the input",
+        AocStatus::Panic => c"The solution panicked",
+        AocStatus::BufferTooSmall => c"The buffer is too small for the answer",
+        AocStatus::NullPointer => c"A pointer was null",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solutions::Solution;
+
+    fn call(day: u8, part: u8, input: &str, capacity: usize) -> (AocStatus, usize, String) {
+        let mut data = vec![0x7f as c_char; capacity];
+        let mut out = AocBuffer {
+            data: data.as_mut_ptr(),
+            capacity,
+            len: 0,
+        };
+        let status = unsafe { aoc_solve(day, part, input.as_ptr(), input.len(), &mut out) };
+        let written = data[..out.len.min(capacity)]
+            .iter()
+            .map(|&c| c as u8 as char)
+            .collect();
+        (status, out.len, written)
+    }
+
+    #[test]
+    fn solves_into_the_buffer() {
+        let example = include_str!("../../day_01/example_1.txt");
+        assert_eq!(call(1, 1, example, 64), (AocStatus::Ok, 5, "24000".into()));
+        assert_eq!(call(1, 1, example, 5).0, AocStatus::Ok);
+        assert_eq!(call(1, 1, example, 4).0, AocStatus::BufferTooSmall);
+        assert_eq!(call(1, 1, example, 4).1, 5);
+    }
+
+    #[test]
+    fn maps_each_failure() {
+        assert_eq!(call(26, 1, "", 64).0, AocStatus::NoSuchDay);
+        assert_eq!(call(25, 2, "1=\n", 64).0, AocStatus::NoSuchPart);
+        assert_eq!(call(11, 1, "x\n", 64).0, AocStatus::ParseError);
+        let no_start = "Valve BB has flow rate=1; tunnel leads to valve BB\n";
+        assert_eq!(
+            call(16, 1, no_start, 64),
+            (
+                AocStatus::InvalidInput,
+                37,
+                "There is no start valve to start from".into()
+            )
+        );
+        assert_eq!(call(20, 1, "x\n", 64).0, AocStatus::ParseError);
+        assert_eq!(call(17, 1, "x\n", 64).0, AocStatus::ParseError);
+    }
+
+    struct Panicking;
+
+    impl Solution for Panicking {
+        type PartOne = u8;
+        type PartTwo = u8;
+
+        fn part_one(&self, _input: &str) -> Result<u8, SolveError> {
+            panic!("unexpected input")
+        }
+
+        fn part_two(&self, _input: &str) -> Result<u8, SolveError> {
+            Err(SolveError::NoSuchPart)
+        }
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(
+            solve_with(&Panicking, 1, b""),
+            (AocStatus::Panic, "unexpected input".into())
+        );
+    }
+
+    #[test]
+    fn rejects_null_pointers() {
+        let status = unsafe { aoc_solve(1, 1, std::ptr::null(), 0, std::ptr::null_mut()) };
+        assert_eq!(status, AocStatus::NullPointer);
+        let example = include_str!("../../day_01/example_1.txt");
+        let mut out = AocBuffer {
+            data: std::ptr::null_mut(),
+            capacity: 64,
+            len: 0,
+        };
+        let solve =
+            |out: &mut AocBuffer| unsafe { aoc_solve(1, 1, example.as_ptr(), example.len(), out) };
+        assert_eq!(solve(&mut out), AocStatus::NullPointer);
+        // with no capacity, null data asks how much room the answer needs
+        out.capacity = 0;
+        assert_eq!(solve(&mut out), AocStatus::BufferTooSmall);
+        assert_eq!(out.len, 5);
+    }
+}
@@ -0,0 +1,38 @@
+//! Builds `c/solve_test.c` against the shared library and runs it.
+
+use std::{env, path::PathBuf, process::Command};
+
+#[test]
+fn c_program_passes() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // the test runs from target/<profile>/deps, and the shared library is built one level up
+    let exe = env::current_exe().unwrap();
+    let library_dir = exe.parent().and_then(|deps| deps.parent()).unwrap();
+    let program = env::temp_dir().join(format!("aoc_ffi_solve_test_{}", std::process::id()));
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| String::from("cc")))
+        .args(["-Wall", "-Wextra", "-Werror", "-I"])
+        .arg(&manifest_dir)
+        .arg("-o")
+        .arg(&program)
+        .arg(manifest_dir.join("c/solve_test.c"))
+        .arg("-L")
+        .arg(library_dir)
+        .arg("-laoc_ffi")
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .status()
+        .expect("a C compiler should be installed");
+    assert!(status.success(), "the C program should compile");
+
+    let output = Command::new(&program).output().unwrap();
+    let _ = std::fs::remove_file(&program);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "all checks passed\n"
+    );
+}
@@ -0,0 +1,306 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    iter::successors,
+    ops::{Index, IndexMut},
+};
+
+use aoc_core::parse::{Expected, ParseError};
+use nom::Offset;
+
+use crate::{Direction, Point};
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid where every cell starts as `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: alloc::vec![fill; width * height],
+        }
+    }
+
+    /// A grid made of `rows`, where rows shorter than the longest are padded with `fill`.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let rows: Vec<Vec<T>> = rows.into_iter().map(|r| r.into_iter().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for row in rows {
+            let padding = width - row.len();
+            cells.extend(row);
+            cells.extend(core::iter::repeat_n(fill.clone(), padding));
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one line of `input` per row and one character per cell.
+    ///
+    /// Every row must be as wide as the first. Characters that `cell` rejects are reported as
+    /// errors expecting `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &'static str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let at = |position: &str| &input[input.offset(position)..];
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                if Some(row_width) == width {
+                    return Err(ParseError::new(
+                        input,
+                        at(&line[i..]),
+                        Expected::Described("a line ending"),
+                    ));
+                }
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::new(input, at(&line[i..]), Expected::Described(expected))
+                })?;
+                cells.push(value);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if row_width < width => {
+                    return Err(ParseError::new(
+                        input,
+                        at(&line[line.len()..]),
+                        Expected::Described(expected),
+                    ));
+                }
+                Some(_) => {}
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(self.index_of(p)?)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let index = self.index_of(p)?;
+        self.cells.get_mut(index)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.cells.get(y * self.width..(y + 1) * self.width)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Adds a row after the last, which must be exactly as wide as the grid.
+    ///
+    /// # Panics
+    ///
+    /// If the row has the wrong number of cells.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let before = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - before,
+            self.width,
+            "row has the wrong width"
+        );
+        self.height += 1;
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    /// The position of the first cell, row by row, that satisfies `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.point_of(i))
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The cells in a straight line from `start` to the edge of the grid, excluding `start`.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        successors(Some(start), move |&p| Some(p.step(direction)))
+            .skip(1)
+            .map_while(|p| Some((p, self.get(p)?)))
+    }
+
+    /// The cells sharing an edge with `p`, clockwise from north.
+    pub fn neighbours_4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours_4().filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// The cells sharing an edge or a corner with `p`, clockwise from north.
+    pub fn neighbours_8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours_8().filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// Draws the grid one row per line, choosing a character for each cell and its position
+    /// with `cell`.
+    pub fn render<'a>(&'a self, cell: impl Fn(Point, &T) -> char + 'a) -> impl Display + 'a {
+        crate::render(
+            Point::new(0, 0),
+            Point::new(self.width as i64 - 1, self.height as i64 - 1),
+            move |p| cell(p, &self[p]),
+        )
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = digits("123\n456\n\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(
+            grid.render(|_, &d| if d % 2 == 0 { '#' } else { '.' })
+                .to_string(),
+            ".#.\n#.#\n"
+        );
+    }
+
+    #[test]
+    fn reports_bad_cells_and_ragged_rows() {
+        let bad_cell = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((bad_cell.line, bad_cell.column), (2, 2));
+        assert_eq!(bad_cell.expected, Expected::Described("a digit"));
+        let short_row = digits("123\n45\n").unwrap_err();
+        assert_eq!((short_row.line, short_row.column), (2, 3));
+        let long_row = digits("123\n4567\n").unwrap_err();
+        assert_eq!((long_row.line, long_row.column), (2, 4));
+        assert_eq!(long_row.expected, Expected::Described("a line ending"));
+    }
+
+    #[test]
+    fn neighbours_and_rays_stay_inside() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        let values = |cells: &mut dyn Iterator<Item = (Point, &u32)>| {
+            cells.map(|(_, &v)| v).collect::<Vec<_>>()
+        };
+        assert_eq!(values(&mut grid.neighbours_4(Point::new(0, 0))), vec![2, 4]);
+        assert_eq!(
+            values(&mut grid.neighbours_8(Point::new(0, 0))),
+            vec![2, 5, 4]
+        );
+        assert_eq!(
+            values(&mut grid.neighbours_8(Point::new(1, 1))),
+            vec![2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(
+            values(&mut grid.ray(Point::new(0, 2), Direction::North)),
+            vec![4, 1]
+        );
+        assert_eq!(grid.position(|&v| v > 4), Some(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn pads_and_grows_rows() {
+        let mut grid = Grid::from_rows([vec![1], vec![2, 3]], 0);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [&[1, 0][..], &[2, 3]]);
+        grid.push_row([4, 5]);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.row(2), Some(&[4, 5][..]));
+    }
+}
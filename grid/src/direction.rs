@@ -0,0 +1,49 @@
+use crate::Point;
+
+/// One of the four directions along the edges of a grid. North is towards row `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [North, East, South, West];
+
+    /// The change in position from taking one step this way.
+    pub fn offset(self) -> Point {
+        match self {
+            North => Point::new(0, -1),
+            East => Point::new(1, 0),
+            South => Point::new(0, 1),
+            West => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            North => West,
+            East => North,
+            South => East,
+            West => South,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            North => East,
+            East => South,
+            South => West,
+            West => North,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
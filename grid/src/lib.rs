@@ -0,0 +1,42 @@
+//! Two dimensional grids shared by the days whose puzzles take place on a map.
+//!
+//! Positions are [`Point`]s with `x` increasing to the east and `y` increasing to the south, so
+//! that the first line of puzzle input is row `0`. [`Grid`] stores every cell of a rectangle,
+//! while [`SparseGrid`] only stores the occupied cells of an unbounded plane.
+
+#![no_std]
+
+extern crate alloc;
+
+mod dense;
+mod direction;
+mod point;
+mod sparse;
+
+use core::fmt::{self, Display};
+
+pub use dense::Grid;
+pub use direction::Direction;
+pub use point::Point;
+pub use sparse::SparseGrid;
+
+/// Writes the rows from `min` to `max` inclusive, one line each, choosing a character per cell.
+fn render(min: Point, max: Point, cell: impl Fn(Point) -> char) -> impl Display {
+    struct Render<F>(F);
+
+    impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> Display for Render<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    Render(move |f: &mut fmt::Formatter<'_>| {
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{}", cell(Point { x, y }))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,66 @@
+use core::ops::{Add, AddAssign, Sub};
+
+use crate::Direction;
+
+/// A position on a grid, or the offset between two positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The adjacent position in `direction`.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The four positions sharing an edge with this one, clockwise from north.
+    pub fn neighbours_4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The eight positions sharing an edge or a corner with this one, clockwise from north.
+    pub fn neighbours_8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().flat_map(move |d| {
+            let edge = self.step(d);
+            [edge, edge.step(d.turn_right())]
+        })
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Self) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Self) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::collections::{btree_map, BTreeMap};
+use core::fmt::Display;
+
+use crate::Point;
+
+/// The occupied cells of an unbounded grid, for maps that grow or are mostly empty.
+///
+/// A `SparseGrid<()>` is a set of points.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SparseGrid<T = ()> {
+    cells: BTreeMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Occupies `p` with `value`, returning what was there before.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// The number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Every occupied cell with its position.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The smallest and largest `x` and `y` of any occupied cell, as two corners.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.points().fold(None, |bounds, p| {
+            let (min, max) = bounds.unwrap_or((p, p));
+            Some((
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ))
+        })
+    }
+
+    /// The occupied cells sharing an edge with `p`, clockwise from north.
+    pub fn neighbours_4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours_4().filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// The occupied cells sharing an edge or a corner with `p`, clockwise from north.
+    pub fn neighbours_8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours_8().filter_map(|n| Some((n, self.get(n)?)))
+    }
+
+    /// Draws the rectangle from `min` to `max` inclusive one row per line, choosing a character
+    /// for each position with `cell`.
+    pub fn render_within<'a>(
+        &'a self,
+        min: Point,
+        max: Point,
+        cell: impl Fn(Point, Option<&T>) -> char + 'a,
+    ) -> impl Display + 'a {
+        crate::render(min, max, move |p| cell(p, self.get(p)))
+    }
+
+    /// Draws the smallest rectangle holding every occupied cell, choosing a character for each
+    /// position with `cell`.
+    pub fn render<'a>(&'a self, cell: impl Fn(Option<&T>) -> char + 'a) -> impl Display + 'a {
+        let (min, max) = self
+            .bounds()
+            .unwrap_or((Point::new(0, 0), Point::new(-1, -1)));
+        self.render_within(min, max, move |_, value| cell(value))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter)
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Point, T);
+    type IntoIter = btree_map::IntoIter<Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec::Vec};
+
+    #[test]
+    fn tracks_occupied_cells() {
+        let mut grid: SparseGrid = [Point::new(-2, 1), Point::new(3, -1)]
+            .into_iter()
+            .map(|p| (p, ()))
+            .collect();
+        assert_eq!(grid.insert(Point::new(0, 0), ()), None);
+        assert_eq!(grid.insert(Point::new(0, 0), ()), Some(()));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(3, 1))));
+        assert_eq!(
+            grid.neighbours_8(Point::new(-1, 0))
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(-2, 1)]
+        );
+        assert_eq!(
+            grid.render(|cell| if cell.is_some() { '#' } else { '.' })
+                .to_string(),
+            ".....#\n..#...\n#.....\n"
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! A stand-in for the Advent of Code website, for testing `aoc fetch` and `aoc submit` offline.
+//!
+//! It serves puzzle inputs and checks answers at the same paths as the real site, and replies the
+//! way it does: inputs need a session cookie, answers are posted as a form, and the verdict is a
+//! sentence in an `<article>`. A wrong answer makes that session wait before answering again.
+
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// The year whose puzzles are served.
+pub const YEAR: u16 = 2022;
+
+/// How long a session has to wait after a wrong answer, unless changed.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// One day's puzzle as a player sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub input: String,
+    /// The answer to each part, or `None` where any answer is wrong
+    pub answers: [Option<String>; 2],
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Puzzles by session and day. A session of `None` is every session without its own puzzle
+    puzzles: HashMap<(Option<String>, u8), Puzzle>,
+    /// How many parts of each day each session has answered
+    stars: HashMap<(String, u8), u8>,
+    /// When each session that gave a wrong answer can answer again
+    waiting: HashMap<String, Instant>,
+    cooldown: Duration,
+    /// Each request's method and path, in the order they came
+    requests: Vec<String>,
+}
+
+impl State {
+    fn puzzle(&self, session: &str, day: u8) -> Option<&Puzzle> {
+        self.puzzles
+            .get(&(Some(session.to_owned()), day))
+            .or_else(|| self.puzzles.get(&(None, day)))
+    }
+}
+
+/// The server, which answers requests on a thread of its own until it's dropped.
+pub struct PuzzleServer {
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    address: SocketAddr,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PuzzleServer {
+    /// Starts serving on `address`, such as `127.0.0.1:0` for any free port, with no puzzles.
+    pub fn start(address: &str) -> io::Result<Self> {
+        let server = Arc::new(Server::http(address).map_err(io::Error::other)?);
+        let address = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| io::Error::other("The server isn't listening on a TCP port"))?;
+        let state = Arc::new(Mutex::new(State {
+            cooldown: DEFAULT_COOLDOWN,
+            ..State::default()
+        }));
+        let thread = {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let _ = handle(&state, request);
+                }
+            })
+        };
+        Ok(PuzzleServer {
+            server,
+            state,
+            address,
+            thread: Some(thread),
+        })
+    }
+
+    /// The URL the site is served from, to be used as the base URL of requests.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Sets `day`'s puzzle for `session`, or for every other session when it's `None`.
+    pub fn add_puzzle(&self, session: Option<&str>, day: u8, puzzle: Puzzle) {
+        self.state()
+            .puzzles
+            .insert((session.map(str::to_owned), day), puzzle);
+    }
+
+    /// Changes how long a session has to wait after a wrong answer.
+    pub fn set_cooldown(&self, cooldown: Duration) {
+        self.state().cooldown = cooldown;
+    }
+
+    /// The method and path of every request so far, such as `GET /2022/day/1/input`.
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for PuzzleServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle(state: &Mutex<State>, mut request: Request) -> io::Result<()> {
+    let path = request.url().to_owned();
+    let method = request.method().clone();
+    let session = request
+        .headers()
+        .iter()
+        .filter(|header| header.field.equiv("Cookie"))
+        .flat_map(|header| header.value.as_str().split(';'))
+        .find_map(|cookie| cookie.trim().strip_prefix("session="))
+        .map(str::to_owned);
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body)?;
+
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    state.requests.push(format!("{method} {path}"));
+    let (status, text) = respond(&mut state, &method, &path, session.as_deref(), &body);
+    drop(state);
+
+    let content_type = if text.starts_with('<') {
+        "text/html; charset=utf-8"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    let header = Header::from_bytes("Content-Type", content_type).expect("the header is valid");
+    request.respond(
+        Response::from_string(text)
+            .with_status_code(status)
+            .with_header(header),
+    )
+}
+
+/// The status and body of the reply to a request.
+fn respond(
+    state: &mut State,
+    method: &Method,
+    path: &str,
+    session: Option<&str>,
+    body: &str,
+) -> (u16, String) {
+    let not_found = (404, String::from("404 Not Found"));
+    let Some(rest) = path.strip_prefix(&format!("/{YEAR}/day/")) else {
+        return not_found;
+    };
+    let Some((day, page)) = rest.split_once('/') else {
+        return not_found;
+    };
+    let Some(day) = day.parse::<u8>().ok().filter(|day| (1..=25).contains(day)) else {
+        return not_found;
+    };
+    let Some(session) = session else {
+        return (
+            400,
+            String::from(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+        );
+    };
+    match (method, page) {
+        (Method::Get, "input") => match state.puzzle(session, day) {
+            Some(puzzle) => (200, puzzle.input.clone()),
+            None => not_found,
+        },
+        (Method::Post, "answer") if state.puzzle(session, day).is_some() => {
+            let field = |name: &str| {
+                body.split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| decode_form_value(value))
+            };
+            match (field("level"), field("answer")) {
+                (Some(level), Some(answer)) => {
+                    (200, article(&check(state, session, day, &level, &answer)))
+                }
+                _ => (400, String::from("400 Bad Request")),
+            }
+        }
+        _ => not_found,
+    }
+}
+
+/// The verdict on `answer` to part `level` of `day`.
+fn check(state: &mut State, session: &str, day: u8, level: &str, answer: &str) -> String {
+    let now = Instant::now();
+    if let Some(left) = state
+        .waiting
+        .get(session)
+        .and_then(|until| until.checked_duration_since(now))
+    {
+        return format!(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have {}s left to wait.",
+            left.as_secs() + 1
+        );
+    }
+    let stars = state
+        .stars
+        .get(&(session.to_owned(), day))
+        .copied()
+        .unwrap_or(0);
+    let expected = match state.puzzle(session, day) {
+        Some(puzzle) if level == (stars + 1).to_string() && stars < 2 => {
+            puzzle.answers[usize::from(stars)].clone()
+        }
+        _ => {
+            return String::from(
+                "You don't seem to be solving the right level.  Did you already complete it?",
+            )
+        }
+    };
+    let answer = answer.trim();
+    if expected.as_deref().map(str::trim) == Some(answer) {
+        state.stars.insert((session.to_owned(), day), stars + 1);
+        return String::from(
+            "That's the right answer!  You are one gold star closer to finding the star fruit.",
+        );
+    }
+    let cooldown = state.cooldown;
+    state.waiting.insert(session.to_owned(), now + cooldown);
+    let hint = match (
+        answer.parse::<i64>(),
+        expected.and_then(|e| e.trim().parse::<i64>().ok()),
+    ) {
+        (Ok(given), Some(expected)) if given > expected => "; your answer is too high",
+        (Ok(given), Some(expected)) if given < expected => "; your answer is too low",
+        _ => "",
+    };
+    format!(
+        "That's not the right answer{hint}.  If you're stuck, make sure you're using the full \
+         input data.  Please wait {}s before trying again.",
+        cooldown.as_secs()
+    )
+}
+
+fn article(text: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>\n")
+}
+
+/// Undoes the `+` and `%XX` escapes of a form field's value.
+fn decode_form_value(value: &str) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => match tail
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(decoded) => {
+                    bytes.push(decoded);
+                    rest = &tail[2..];
+                    continue;
+                }
+                None => bytes.push(b'%'),
+            },
+            byte => bytes.push(byte),
+        }
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> State {
+        let mut state = State {
+            cooldown: DEFAULT_COOLDOWN,
+            ..State::default()
+        };
+        state.puzzles.insert(
+            (None, 1),
+            Puzzle {
+                input: String::from("1000\n"),
+                answers: [Some(String::from("24000")), Some(String::from("45000"))],
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn serves_inputs_to_sessions() {
+        let mut state = state();
+        let get =
+            |state: &mut State, path, session| respond(state, &Method::Get, path, session, "");
+        assert_eq!(
+            get(&mut state, "/2022/day/1/input", Some("abc")),
+            (200, String::from("1000\n"))
+        );
+        assert_eq!(get(&mut state, "/2022/day/1/input", None).0, 400);
+        assert_eq!(get(&mut state, "/2022/day/2/input", Some("abc")).0, 404);
+        assert_eq!(get(&mut state, "/2021/day/1/input", Some("abc")).0, 404);
+        let post = respond(
+            &mut state,
+            &Method::Post,
+            "/2022/day/2/answer",
+            Some("abc"),
+            "level=1&answer=5",
+        );
+        assert_eq!(post.0, 404);
+    }
+
+    #[test]
+    fn checks_answers_in_order() {
+        let mut state = state();
+        assert!(check(&mut state, "abc", 1, "2", "45000").contains("the right level"));
+        assert!(check(&mut state, "abc", 1, "1", "24000").starts_with("That's the right answer"));
+        assert!(check(&mut state, "abc", 1, "2", "50000").contains("too high"));
+        assert!(check(&mut state, "abc", 1, "2", "45000").contains("too recently"));
+        assert!(check(&mut state, "xyz", 1, "1", "24000").starts_with("That's the right answer"));
+    }
+
+    #[test]
+    fn decodes_form_values() {
+        assert_eq!(decode_form_value("CMZ"), "CMZ");
+        assert_eq!(decode_form_value("a+b%2Cc%zz"), "a b,c%zz");
+    }
+}
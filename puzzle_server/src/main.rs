@@ -0,0 +1,103 @@
+//! Serves each day's example from an answers file as everyone's puzzle, until interrupted.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+};
+
+use clap::Parser;
+use puzzle_server::{Puzzle, PuzzleServer};
+use serde::Deserialize;
+
+#[derive(Debug, Parser)]
+#[command(about = "A stand-in for the Advent of Code website, serving the examples as inputs")]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8022")]
+    address: String,
+    /// File listing the inputs and their answers, as used by `aoc verify`
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Answers {
+    answers: Vec<Answer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Answer {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: toml::Value,
+}
+
+/// The first input listed for each day that isn't a challenge input, with its answers.
+fn examples(path: &Path) -> Result<BTreeMap<u8, Puzzle>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let answers: Answers =
+        toml::from_str(&text).map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut chosen: BTreeMap<u8, (String, Puzzle)> = BTreeMap::new();
+    for answer in answers.answers {
+        let is_challenge = Path::new(&answer.input)
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("challenge"));
+        if is_challenge || !(1..=2).contains(&answer.part) {
+            continue;
+        }
+        let (input, puzzle) = match chosen.get_mut(&answer.day) {
+            Some(chosen) => chosen,
+            None => {
+                let input = fs::read_to_string(base.join(&answer.input)).unwrap_or_default();
+                let puzzle = Puzzle {
+                    input,
+                    answers: [None, None],
+                };
+                chosen
+                    .entry(answer.day)
+                    .or_insert((answer.input.clone(), puzzle))
+            }
+        };
+        if *input == answer.input {
+            puzzle.answers[usize::from(answer.part - 1)] = Some(match answer.answer {
+                toml::Value::String(text) => text,
+                other => other.to_string(),
+            });
+        }
+    }
+    Ok(chosen
+        .into_iter()
+        .map(|(day, (_, puzzle))| (day, puzzle))
+        .collect())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let examples = match examples(&cli.answers) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match PuzzleServer::start(&cli.address) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Failed to listen on {}: {e}", cli.address);
+            return ExitCode::FAILURE;
+        }
+    };
+    for (day, puzzle) in examples {
+        server.add_puzzle(None, day, puzzle);
+    }
+    eprintln!("Serving the examples at {}", server.url());
+    loop {
+        thread::park();
+    }
+}
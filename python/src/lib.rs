@@ -0,0 +1,275 @@
+//! The solutions as a Python module, `aoc2022`, built with maturin.
+//!
+//! Each day is a submodule, such as `aoc2022.day_07`, with `part_one` and `part_two` functions
+//! that take the input and any of the day's parameters as keyword arguments. Days 7, 16 and 21
+//! also have a class for the model their solutions are built on.
+
+use std::collections::BTreeMap;
+
+use pyo3::{
+    create_exception, exceptions::PyValueError, prelude::*, types::PyDict, IntoPyObjectExt,
+};
+use solutions::Structured;
+
+create_exception!(
+    aoc2022,
+    SolveError,
+    PyValueError,
+    "The input couldn't be parsed, or didn't have an answer."
+);
+
+fn solve_error(e: impl ToString) -> PyErr {
+    SolveError::new_err(e.to_string())
+}
+
+fn to_python(py: Python<'_>, answer: Structured) -> PyResult<PyObject> {
+    match answer {
+        Structured::Integer(n) => n.into_py_any(py),
+        Structured::Boolean(b) => b.into_py_any(py),
+        Structured::Text(text) => text.into_py_any(py),
+        Structured::List(items) => items
+            .into_iter()
+            .map(|item| to_python(py, item))
+            .collect::<PyResult<Vec<_>>>()?
+            .into_py_any(py),
+    }
+}
+
+fn solve(
+    py: Python<'_>,
+    day: u8,
+    part: u8,
+    input: &str,
+    params: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PyObject> {
+    let mut named = Vec::new();
+    for (name, value) in params.into_iter().flatten() {
+        named.push((name.str()?.to_string(), value.str()?.to_string()));
+    }
+    let named: Vec<_> = named
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let solution = solutions::get_with(day, &named).map_err(PyValueError::new_err)?;
+    let answer = py
+        .allow_threads(|| {
+            solution
+                .solve_answer(part, input, &mut ())
+                .map(|answer| answer.structured())
+        })
+        .map_err(solve_error)?;
+    to_python(py, answer)
+}
+
+/// Adds `day_NN` with its `part_one` and `part_two` to `parent`, plus any model classes.
+macro_rules! day_module {
+    ($parent:expr, $name:literal, $day:literal $(, $class:ty)*) => {{
+        /// Solves part one. Keyword arguments change the day's parameters.
+        #[pyfunction]
+        #[pyo3(signature = (input, **params))]
+        fn part_one(
+            py: Python<'_>,
+            input: &str,
+            params: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<PyObject> {
+            solve(py, $day, 1, input, params)
+        }
+
+        /// Solves part two. Keyword arguments change the day's parameters.
+        #[pyfunction]
+        #[pyo3(signature = (input, **params))]
+        fn part_two(
+            py: Python<'_>,
+            input: &str,
+            params: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<PyObject> {
+            solve(py, $day, 2, input, params)
+        }
+
+        let module = PyModule::new($parent.py(), $name)?;
+        module.add_function(wrap_pyfunction!(part_one, &module)?)?;
+        module.add_function(wrap_pyfunction!(part_two, &module)?)?;
+        $(module.add_class::<$class>()?;)*
+        $parent.add_submodule(&module)?;
+    }};
+}
+
+/// Day 7's directories, with the total size of the files within each.
+#[pyclass(module = "aoc2022.day_07", frozen)]
+struct FileSystem {
+    directory_sizes: BTreeMap<String, u64>,
+}
+
+#[pymethods]
+impl FileSystem {
+    #[new]
+    fn new(input: &str) -> PyResult<Self> {
+        let file_system = day_07::FileSystem::new_from_observations(input).map_err(solve_error)?;
+        Ok(Self {
+            directory_sizes: file_system.directory_sizes().into_iter().collect(),
+        })
+    }
+
+    /// The size of each directory by its path, such as `/a/e`.
+    #[getter]
+    fn directory_sizes(&self) -> BTreeMap<String, u64> {
+        self.directory_sizes.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FileSystem({} directories)", self.directory_sizes.len())
+    }
+}
+
+/// Day 16's valves worth opening and the one you start at, with the walking times between them.
+#[pyclass(module = "aoc2022.day_16", frozen)]
+struct World {
+    start: String,
+    flow_rates: BTreeMap<String, u16>,
+    distances: BTreeMap<String, BTreeMap<String, u16>>,
+}
+
+#[pymethods]
+impl World {
+    #[new]
+    #[pyo3(signature = (input, start_valve = "AA"))]
+    fn new(input: &str, start_valve: &str) -> PyResult<Self> {
+        let world = day_16::World::prepare(input, start_valve).map_err(solve_error)?;
+        let flow_rates: BTreeMap<_, _> = world
+            .flow_rates()
+            .map(|(name, flow_rate)| (name.to_owned(), flow_rate))
+            .collect();
+        let distances = flow_rates
+            .keys()
+            .map(|name| {
+                let to = world
+                    .distances_from(name)
+                    .map(|(other, distance)| (other.to_owned(), distance))
+                    .collect();
+                (name.clone(), to)
+            })
+            .collect();
+        Ok(Self {
+            start: world.start().to_owned(),
+            flow_rates,
+            distances,
+        })
+    }
+
+    #[getter]
+    fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// The flow rate of each valve kept, by name.
+    #[getter]
+    fn flow_rates(&self) -> BTreeMap<String, u16> {
+        self.flow_rates.clone()
+    }
+
+    /// The minutes it takes to walk from each valve kept to each valve worth opening.
+    #[getter]
+    fn distances(&self) -> BTreeMap<String, BTreeMap<String, u16>> {
+        self.distances.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "World(start={:?}, {} valves)",
+            self.start,
+            self.flow_rates.len()
+        )
+    }
+}
+
+/// Day 21's monkeys and their jobs.
+#[pyclass(module = "aoc2022.day_21", frozen)]
+struct Troop {
+    input: String,
+}
+
+impl Troop {
+    fn parsed(&self) -> day_21::Troop<'_> {
+        day_21::Troop::new(&self.input).expect("the input was parsed when the troop was made")
+    }
+}
+
+#[pymethods]
+impl Troop {
+    #[new]
+    fn new(input: String) -> PyResult<Self> {
+        day_21::Troop::new(&input).map_err(solve_error)?;
+        Ok(Self { input })
+    }
+
+    /// Each monkey's job by its name, as either a number or a tuple such as `("pppw", "+",
+    /// "sjmn")`.
+    #[getter]
+    fn jobs(&self, py: Python<'_>) -> PyResult<BTreeMap<String, PyObject>> {
+        self.parsed()
+            .jobs()
+            .map(|(name, job)| {
+                let job = match *job {
+                    day_21::Job::Number(n) => n.into_py_any(py)?,
+                    day_21::Job::Dependent { a, op, b } => {
+                        let op = match op {
+                            day_21::Operation::Plus => "+",
+                            day_21::Operation::Minus => "-",
+                            day_21::Operation::Mul => "*",
+                            day_21::Operation::Div => "/",
+                            day_21::Operation::Eq => "=",
+                        };
+                        (a, op, b).into_py_any(py)?
+                    }
+                };
+                Ok((name.to_owned(), job))
+            })
+            .collect()
+    }
+
+    /// What `root` yells, or `None` if a monkey it depends on is missing.
+    fn root_value(&self) -> Option<i64> {
+        self.parsed().root_value()
+    }
+
+    /// What `humn` has to yell for both sides of `root` to be equal, or `None` if there's no
+    /// such number.
+    fn humn_value(&self) -> Option<i64> {
+        self.parsed().humn_value()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Troop({} monkeys)", self.parsed().jobs().count())
+    }
+}
+
+#[pymodule]
+fn aoc2022(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("SolveError", m.py().get_type::<SolveError>())?;
+    day_module!(m, "day_01", 1);
+    day_module!(m, "day_02", 2);
+    day_module!(m, "day_03", 3);
+    day_module!(m, "day_04", 4);
+    day_module!(m, "day_05", 5);
+    day_module!(m, "day_06", 6);
+    day_module!(m, "day_07", 7, FileSystem);
+    day_module!(m, "day_08", 8);
+    day_module!(m, "day_09", 9);
+    day_module!(m, "day_10", 10);
+    day_module!(m, "day_11", 11);
+    day_module!(m, "day_12", 12);
+    day_module!(m, "day_13", 13);
+    day_module!(m, "day_14", 14);
+    day_module!(m, "day_15", 15);
+    day_module!(m, "day_16", 16, World);
+    day_module!(m, "day_17", 17);
+    day_module!(m, "day_18", 18);
+    day_module!(m, "day_19", 19);
+    day_module!(m, "day_20", 20);
+    day_module!(m, "day_21", 21, Troop);
+    day_module!(m, "day_22", 22);
+    day_module!(m, "day_23", 23);
+    day_module!(m, "day_24", 24);
+    day_module!(m, "day_25", 25);
+    Ok(())
+}
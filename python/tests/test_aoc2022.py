@@ -0,0 +1,65 @@
+"""Run with `python -m unittest discover python/tests` once the module is installed, such as with
+`maturin develop` from the `python` directory."""
+
+import pathlib
+import unittest
+
+import aoc2022
+
+ROOT = pathlib.Path(__file__).resolve().parents[2]
+
+
+def example(day, name="example.txt"):
+    return (ROOT / f"day_{day:02}" / name).read_text()
+
+
+class Parts(unittest.TestCase):
+    def test_answers_keep_their_types(self):
+        self.assertEqual(aoc2022.day_01.part_one(example(1, "example_1.txt")), 24000)
+        self.assertEqual(aoc2022.day_25.part_one(example(25)), "2=-1=0")
+        screen = aoc2022.day_10.part_two(example(10))
+        self.assertEqual(len(screen), 6)
+        self.assertEqual(screen[0][:4], [True, True, False, False])
+
+    def test_parameters_are_keyword_arguments(self):
+        self.assertEqual(aoc2022.day_15.part_one(example(15), row=10), 26)
+        self.assertEqual(
+            aoc2022.day_20.part_two(example(20), decryption_key=1, rounds=1),
+            aoc2022.day_20.part_one(example(20)),
+        )
+        with self.assertRaisesRegex(ValueError, "key: Unknown parameter"):
+            aoc2022.day_20.part_two(example(20), key=1)
+
+    def test_errors(self):
+        with self.assertRaises(aoc2022.SolveError):
+            aoc2022.day_11.part_one("x\n")
+        with self.assertRaisesRegex(aoc2022.SolveError, "part"):
+            aoc2022.day_25.part_two(example(25))
+
+
+class Models(unittest.TestCase):
+    def test_file_system(self):
+        file_system = aoc2022.day_07.FileSystem(example(7))
+        self.assertEqual(
+            file_system.directory_sizes,
+            {"/": 48381165, "/a": 94853, "/a/e": 584, "/d": 24933642},
+        )
+
+    def test_world(self):
+        world = aoc2022.day_16.World(example(16))
+        self.assertEqual(world.start, "AA")
+        self.assertEqual(world.flow_rates["JJ"], 21)
+        self.assertEqual(world.distances["AA"]["HH"], 5)
+        with self.assertRaises(aoc2022.SolveError):
+            aoc2022.day_16.World(example(16), start_valve="ZZ")
+
+    def test_troop(self):
+        troop = aoc2022.day_21.Troop(example(21))
+        self.assertEqual(troop.jobs["root"], ("pppw", "+", "sjmn"))
+        self.assertEqual(troop.jobs["dbpl"], 5)
+        self.assertEqual(troop.root_value(), 152)
+        self.assertEqual(troop.humn_value(), 301)
+
+
+if __name__ == "__main__":
+    unittest.main()
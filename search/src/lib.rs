@@ -0,0 +1,241 @@
+//! Shortest path searches over graphs described by a `neighbours` closure.
+//!
+//! States only need to be `Ord + Clone`, so positions, names and tuples of them can be searched
+//! directly. Every search remembers how it reached each state, so the route can be rebuilt as a
+//! [`Path`] as well as its cost.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, BinaryHeap, VecDeque},
+    vec::Vec,
+};
+use core::{cmp::Reverse, ops::Add};
+
+/// A route through the graph and what it cost to follow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// Every state on the route, from the start to the goal inclusive.
+    pub states: Vec<S>,
+}
+
+/// The states a search reached, with the cheapest known cost of each and how it was reached.
+#[derive(Debug, Clone)]
+pub struct Explored<S, C> {
+    visited: BTreeMap<S, (Option<S>, C)>,
+}
+
+impl<S: Ord + Clone, C: Copy> Explored<S, C> {
+    fn new(start: S, cost: C) -> Self {
+        let mut visited = BTreeMap::new();
+        visited.insert(start, (None, cost));
+        Explored { visited }
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.visited.contains_key(state)
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.visited.get(state).map(|&(_, cost)| cost)
+    }
+
+    /// The number of states reached, including the start.
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+
+    /// Every state reached, in order, with its cost.
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> {
+        self.visited.iter().map(|(state, &(_, cost))| (state, cost))
+    }
+
+    /// The route taken from the start to `goal`, if `goal` was reached.
+    pub fn path_to(&self, goal: &S) -> Option<Path<S, C>> {
+        let cost = self.cost(goal)?;
+        let mut states = Vec::new();
+        let mut state = Some(goal.clone());
+        while let Some(s) = state {
+            state = self.visited.get(&s).and_then(|(parent, _)| parent.clone());
+            states.push(s);
+        }
+        states.reverse();
+        Some(Path { cost, states })
+    }
+}
+
+fn breadth_first<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> (Explored<S, usize>, Option<S>)
+where
+    S: Ord + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut explored = Explored::new(start.clone(), 0);
+    let mut frontier = VecDeque::from([(start, 0)]);
+    while let Some((state, distance)) = frontier.pop_front() {
+        if is_goal(&state) {
+            return (explored, Some(state));
+        }
+        for next in neighbours(&state) {
+            if !explored.contains(&next) {
+                explored
+                    .visited
+                    .insert(next.clone(), (Some(state.clone()), distance + 1));
+                frontier.push_back((next, distance + 1));
+            }
+        }
+    }
+    (explored, None)
+}
+
+/// The shortest route from `start` to a state satisfying `is_goal`, where every step costs `1`.
+pub fn bfs<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Ord + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let (explored, goal) = breadth_first(start, neighbours, is_goal);
+    explored.path_to(&goal?)
+}
+
+/// Every state reachable from `start` with its distance, where every step costs `1`.
+pub fn bfs_all<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Explored<S, usize>
+where
+    S: Ord + Clone,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(start, neighbours, |_| false).0
+}
+
+/// The cheapest route from `start` to a state satisfying `is_goal`, where `neighbours` gives
+/// each next state with the cost of stepping to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Ord + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but exploring first the states that `heuristic` estimates to be closest to
+/// a goal.
+///
+/// The route is only guaranteed to be the cheapest if `heuristic` never overestimates.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Ord + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut explored = Explored::new(start.clone(), C::default());
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+    while let Some(Reverse((_, cost, state))) = frontier.pop() {
+        if explored.cost(&state) < Some(cost) {
+            // a cheaper way here was found after this one was queued
+            continue;
+        }
+        if is_goal(&state) {
+            return explored.path_to(&state);
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if explored.cost(&next).is_none_or(|known| next_cost < known) {
+                explored
+                    .visited
+                    .insert(next.clone(), (Some(state.clone()), next_cost));
+                frontier.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// A-B costs 1, B-C costs 1, A-C costs 5 and C-D costs 1, in both directions.
+    fn roads(town: &char) -> Vec<(char, u32)> {
+        match town {
+            'A' => vec![('B', 1), ('C', 5)],
+            'B' => vec![('A', 1), ('C', 1)],
+            'C' => vec![('A', 5), ('B', 1), ('D', 1)],
+            'D' => vec![('C', 1)],
+            _ => vec![],
+        }
+    }
+
+    fn towns(town: &char) -> impl Iterator<Item = char> {
+        roads(town).into_iter().map(|(t, _)| t)
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        assert_eq!(
+            bfs('A', towns, |&t| t == 'D'),
+            Some(Path {
+                cost: 2,
+                states: vec!['A', 'C', 'D']
+            })
+        );
+        assert_eq!(bfs('A', towns, |&t| t == 'E'), None);
+        let explored = bfs_all('D', towns);
+        assert_eq!(
+            explored.iter().collect::<Vec<_>>(),
+            [(&'A', 2), (&'B', 2), (&'C', 1), (&'D', 0)]
+        );
+    }
+
+    #[test]
+    fn dijkstra_weighs_steps() {
+        assert_eq!(
+            dijkstra('A', roads, |&t| t == 'D'),
+            Some(Path {
+                cost: 3,
+                states: vec!['A', 'B', 'C', 'D']
+            })
+        );
+        assert_eq!(
+            dijkstra('A', roads, |&t| t == 'A').map(|p| p.states),
+            Some(vec!['A'])
+        );
+    }
+
+    #[test]
+    fn astar_on_a_line() {
+        let path = astar(
+            0i32,
+            |&n| [(n - 1, 1), (n + 1, 1)],
+            |&n| (10 - n).abs(),
+            |&n| n == 10,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.states, (0..=10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::LazyLock;
+
+pub use aoc_core::{
+    Answer, AnySolution, Explanation, Observer, ParamError, Parameters, Solution, SolveError, Step,
+    Stepper, Structured,
+};
+
+/// Every day's solution with the puzzle's own parameters.
+static SOLUTIONS: LazyLock<Vec<Box<dyn AnySolution>>> = LazyLock::new(|| {
+    (1..=25)
+        .map(|day| get_with(day, &[]).expect("the puzzle's own parameters are always valid"))
+        .collect()
+});
+
+/// Sets each named parameter on a default `P`, then makes the solution with it.
+fn configured<P: Parameters + Default>(
+    params: &[(&str, &str)],
+    make: impl FnOnce(P) -> Box<dyn AnySolution>,
+) -> Result<Box<dyn AnySolution>, String> {
+    let mut p = P::default();
+    for &(name, value) in params {
+        p.set(name, value).map_err(|e| format!("{name}: {e}"))?;
+    }
+    Ok(make(p))
+}
+
+/// Makes the solution for a day with some of its parameters changed from the puzzle's, each
+/// given as a name and a value.
+pub fn get_with(day: u8, params: &[(&str, &str)]) -> Result<Box<dyn AnySolution>, String> {
+    match day {
+        1 => configured(params, |()| Box::new(day_01::Day01)),
+        2 => configured(params, |()| Box::new(day_02::Day02)),
+        3 => configured(params, |()| Box::new(day_03::Day03)),
+        4 => configured(params, |()| Box::new(day_04::Day04)),
+        5 => configured(params, |()| Box::new(day_05::Day05)),
+        6 => configured(params, |()| Box::new(day_06::Day06)),
+        7 => configured(params, |params| Box::new(day_07::Day07 { params })),
+        8 => configured(params, |()| Box::new(day_08::Day08)),
+        9 => configured(params, |()| Box::new(day_09::Day09)),
+        10 => configured(params, |params| Box::new(day_10::Day10 { params })),
+        11 => configured(params, |params| Box::new(day_11::Day11 { params })),
+        12 => configured(params, |()| Box::new(day_12::Day12)),
+        13 => configured(params, |()| Box::new(day_13::Day13)),
+        14 => configured(params, |params| Box::new(day_14::Day14 { params })),
+        15 => configured(params, |params| Box::new(day_15::Day15 { params })),
+        16 => configured(params, |params| Box::new(day_16::Day16 { params })),
+        17 => configured(params, |params| Box::new(day_17::Day17 { params })),
+        18 => configured(params, |()| Box::new(day_18::Day18)),
+        19 => configured(params, |params| Box::new(day_19::Day19 { params })),
+        20 => configured(params, |params| Box::new(day_20::Day20 { params })),
+        21 => configured(params, |()| Box::new(day_21::Day21)),
+        22 => configured(params, |()| Box::new(day_22::Day22)),
+        23 => configured(params, |()| Box::new(day_23::Day23)),
+        24 => configured(params, |()| Box::new(day_24::Day24)),
+        25 => configured(params, |()| Box::new(day_25::Day25)),
+        _ => Err(format!("There is no day {day}")),
+    }
+}
+
+/// Looks up the solution for a day numbered from 1 to 25.
+pub fn get(day: u8) -> Option<&'static dyn AnySolution> {
+    SOLUTIONS
+        .get(usize::from(day).checked_sub(1)?)
+        .map(|solution| solution.as_ref())
+}
+
+/// Every day's solution, paired with its day number.
+pub fn all() -> impl Iterator<Item = (u8, &'static dyn AnySolution)> {
+    (1..).zip(SOLUTIONS.iter().map(|solution| solution.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_by_day() {
+        assert_eq!(
+            get(1)
+                .unwrap()
+                .solve(1, include_str!("../../day_01/example_1.txt")),
+            Ok(String::from("24000"))
+        );
+        assert_eq!(
+            get(25)
+                .unwrap()
+                .solve(1, include_str!("../../day_25/example.txt")),
+            Ok(String::from("2=-1=0"))
+        );
+    }
+
+    #[test]
+    fn missing_days_and_parts() {
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+        assert_eq!(
+            get(25)
+                .unwrap()
+                .solve(2, include_str!("../../day_25/example.txt")),
+            Err(SolveError::NoSuchPart)
+        );
+        assert_eq!(all().count(), 25);
+    }
+
+    #[test]
+    fn lists_the_days_that_explain() {
+        let explained = |part| -> Vec<u8> {
+            all()
+                .filter(|(_, solution)| solution.explains(part))
+                .map(|(day, _)| day)
+                .collect()
+        };
+        assert_eq!(explained(1), [1, 2, 3, 5, 11]);
+        assert_eq!(explained(2), [1, 2, 3, 5, 11, 21]);
+    }
+
+    #[test]
+    fn changes_parameters() {
+        let example = include_str!("../../day_20/example.txt");
+        let solution = get_with(20, &[("decryption_key", "1"), ("rounds", "1")]).unwrap();
+        assert_eq!(
+            solution.solve(2, example),
+            get(20).unwrap().solve(1, example)
+        );
+        assert_eq!(
+            get_with(20, &[("key", "1")]).err().unwrap(),
+            "key: Unknown parameter, expected one of decryption_key, rounds"
+        );
+        assert_eq!(
+            get_with(1, &[("key", "1")]).err().unwrap(),
+            "key: There are no parameters to set"
+        );
+        assert_eq!(get_with(26, &[]).err().unwrap(), "There is no day 26");
+    }
+}
@@ -0,0 +1,35 @@
+#!/bin/sh
+# Builds target/playground/index.html, a single page with the solutions inlined so that it works
+# when opened straight from disk.
+#
+# Needs the wasm32-unknown-unknown target (`rustup target add wasm32-unknown-unknown`) and a
+# wasm-bindgen CLI of the same version as the wasm-bindgen crate in Cargo.lock
+# (`cargo install wasm-bindgen-cli --version <version>`).
+set -eu
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+out="$root/target/playground"
+mkdir -p "$out"
+
+cargo build --manifest-path "$root/Cargo.toml" --release --target wasm32-unknown-unknown \
+    -p aoc_wasm
+wasm-bindgen --target no-modules --no-typescript --out-dir "$out" \
+    "$root/target/wasm32-unknown-unknown/release/aoc_wasm.wasm"
+
+{
+    printf 'const WASM_BASE64 = "'
+    base64 -w0 "$out/aoc_wasm_bg.wasm"
+    printf '";\n'
+} > "$out/wasm_base64.js"
+
+awk -v glue="$out/aoc_wasm.js" -v wasm="$out/wasm_base64.js" '
+    function include(path,    line) {
+        while ((getline line < path) > 0) print line
+        close(path)
+    }
+    /\/\* WASM_BINDGEN_GLUE \*\// { include(glue); next }
+    /\/\* WASM_BASE64 \*\// { include(wasm); next }
+    { print }
+' "$root/wasm/playground.html" > "$out/index.html"
+
+echo "Open $out/index.html in a browser"
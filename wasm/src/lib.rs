@@ -0,0 +1,116 @@
+//! Every day's solution for the browser, through wasm-bindgen. `build.sh` bundles it into the
+//! playground page.
+
+use std::fmt::Display;
+
+use solutions::{Observer, SolveError};
+use wasm_bindgen::prelude::*;
+
+/// An answer, with the final picture from the days that draw one.
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Solved {
+    answer: String,
+    drawing: Option<String>,
+}
+
+#[wasm_bindgen]
+impl Solved {
+    /// The answer as `aoc run` prints it, which is several lines for day 10's screen.
+    #[wasm_bindgen(getter)]
+    pub fn answer(&self) -> String {
+        self.answer.clone()
+    }
+
+    /// The last step of the simulation, such as day 14's cave full of sand, if the day draws one.
+    #[wasm_bindgen(getter)]
+    pub fn drawing(&self) -> Option<String> {
+        self.drawing.clone()
+    }
+}
+
+/// Counts the frames without drawing them.
+#[derive(Default)]
+struct FrameCount(u64);
+
+impl Observer for FrameCount {
+    fn frame(&mut self, _frame: &dyn Display) {
+        self.0 += 1;
+    }
+}
+
+/// Draws only the frame numbered `wanted`.
+struct NthFrame {
+    seen: u64,
+    wanted: u64,
+    drawing: Option<String>,
+}
+
+impl Observer for NthFrame {
+    fn frame(&mut self, frame: &dyn Display) {
+        self.seen += 1;
+        if self.seen == self.wanted {
+            self.drawing = Some(frame.to_string());
+        }
+    }
+}
+
+fn solve_text(day: u8, part: u8, input: &str) -> Result<Solved, String> {
+    let solution = solutions::get(day).ok_or_else(|| format!("There is no day {day}"))?;
+    let describe = |e: SolveError| match e {
+        SolveError::NoSuchPart => format!("Day {day} has no part {part}"),
+        e => e.to_string(),
+    };
+    // drawing every frame of the bigger caves would take far longer than solving, so the frames
+    // are counted first and only the last one is drawn on a second run
+    let mut count = FrameCount::default();
+    let answer = solution
+        .solve_observed(part, input, &mut count)
+        .map_err(describe)?;
+    let drawing = if count.0 == 0 {
+        None
+    } else {
+        let mut last = NthFrame {
+            seen: 0,
+            wanted: count.0,
+            drawing: None,
+        };
+        solution
+            .solve_observed(part, input, &mut last)
+            .map_err(describe)?;
+        last.drawing
+    };
+    Ok(Solved { answer, drawing })
+}
+
+/// Solves part `part` of day `day`, throwing an `Error` describing why when it can't.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<Solved, JsError> {
+    solve_text(day, part, input).map_err(|e| JsError::new(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_with_the_final_drawing() {
+        let solved = solve_text(14, 1, include_str!("../../day_14/example.txt")).unwrap();
+        assert_eq!(solved.answer(), "24");
+        let drawing = solved.drawing().unwrap();
+        assert_eq!(drawing.matches('o').count(), 24);
+    }
+
+    #[test]
+    fn leaves_out_the_drawing_for_other_days() {
+        assert_eq!(
+            solve_text(1, 1, include_str!("../../day_01/example_1.txt")),
+            Ok(Solved {
+                answer: String::from("24000"),
+                drawing: None,
+            })
+        );
+        assert_eq!(solve_text(25, 2, "").unwrap_err(), "Day 25 has no part 2");
+        assert_eq!(solve_text(0, 1, "").unwrap_err(), "There is no day 0");
+    }
+}